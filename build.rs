@@ -0,0 +1,5 @@
+fn main() {
+    // Declare the cfg set by cargo-tarpaulin, so newer compilers don't warn about it. Older Cargo
+    // versions don't know this instruction, and ignore it.
+    println!("cargo:rustc-check-cfg=cfg(tarpaulin)");
+}
@@ -0,0 +1,257 @@
+//! App version module, which provides the `AppVersion` struct.
+//!
+//! Android apps carry an integer `versionCode`, which orders releases, and a free-form
+//! `versionName` shown to users, such as `5.2.1-beta (build 1204)`.
+
+use std::cmp::Ordering;
+use std::convert::TryFrom;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+
+use crate::{Cmp, VersionBuf};
+
+/// Android app version, pairing a `versionCode` with an optional `versionName`.
+///
+/// Versions are ordered by code first, like the Play Store, and by name using `Version`
+/// comparison if the codes are equal. A missing name is older than any name, and a name that
+/// isn't a valid `Version`, such as `beta`, is older than any valid one.
+///
+/// # Examples
+///
+/// ```
+/// use version_compare::AppVersion;
+///
+/// let a = AppVersion::new(1204, "5.2.1-beta (build 1204)");
+/// let b = AppVersion::new(1205, "5.2.1");
+///
+/// assert!(a < b);
+/// assert_eq!(a.code(), 1204);
+/// assert_eq!(a.to_string(), "5.2.1-beta (build 1204) (1204)");
+/// ```
+#[derive(Debug, Clone)]
+pub struct AppVersion {
+    code: u64,
+    name: Option<String>,
+    version: Option<VersionBuf>,
+}
+
+impl AppVersion {
+    /// Create an app version from a `versionCode` and `versionName`.
+    ///
+    /// Android puts no constraints on the name, so any name is accepted. Names that aren't a
+    /// valid `Version` are only compared as text.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use version_compare::AppVersion;
+    ///
+    /// assert!(AppVersion::new(42, "1.0").version().is_some());
+    /// assert_eq!(AppVersion::new(42, "beta").name(), Some("beta"));
+    /// assert!(AppVersion::new(42, "beta").version().is_none());
+    /// ```
+    pub fn new<S: Into<String>>(code: u64, name: S) -> Self {
+        let name = name.into();
+        AppVersion {
+            code,
+            version: VersionBuf::try_from(name.as_str()).ok(),
+            name: Some(name),
+        }
+    }
+
+    /// Create an app version from a `versionCode` only, for apps without `versionName`.
+    pub fn from_code(code: u64) -> Self {
+        AppVersion {
+            code,
+            name: None,
+            version: None,
+        }
+    }
+
+    /// Get the `versionCode`.
+    pub fn code(&self) -> u64 {
+        self.code
+    }
+
+    /// Get the `versionName`, if any.
+    pub fn name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+
+    /// Get the `versionName` as version, if it has one that is a valid `Version`.
+    pub fn version(&self) -> Option<&VersionBuf> {
+        self.version.as_ref()
+    }
+
+    /// Check whether this version is consistent with `other`, which is the case unless one has a
+    /// higher code but a lower name.
+    ///
+    /// Versions are always consistent if either has no name that is a valid `Version`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use version_compare::AppVersion;
+    ///
+    /// let a = AppVersion::new(10, "2.0");
+    ///
+    /// assert!(a.is_consistent_with(&AppVersion::new(11, "2.1")));
+    /// assert!(a.is_consistent_with(&AppVersion::new(11, "2.0")));
+    /// assert!(!a.is_consistent_with(&AppVersion::new(11, "1.9")));
+    /// assert!(a.is_consistent_with(&AppVersion::from_code(9)));
+    /// ```
+    pub fn is_consistent_with(&self, other: &AppVersion) -> bool {
+        let (version, other_version) = match (&self.version, &other.version) {
+            (Some(version), Some(other_version)) => (version, other_version),
+            _ => return true,
+        };
+        let code: Cmp = self.code.cmp(&other.code).into();
+        !matches!(
+            (code, version.compare(other_version)),
+            (Cmp::Lt, Cmp::Gt) | (Cmp::Gt, Cmp::Lt)
+        )
+    }
+
+    /// Find all inconsistent pairs in the given versions, such as an app store release history.
+    ///
+    /// Each pair holds the version with the lower code first, and the version with the higher code
+    /// but lower name second, in the order the versions are given.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use version_compare::AppVersion;
+    ///
+    /// let history = [
+    ///     AppVersion::new(100, "1.0"),
+    ///     AppVersion::new(101, "1.1"),
+    ///     AppVersion::new(102, "1.0.1"),
+    /// ];
+    /// let inconsistent = AppVersion::find_inconsistent(&history);
+    ///
+    /// assert_eq!(inconsistent, vec![(&history[1], &history[2])]);
+    /// ```
+    pub fn find_inconsistent<'a, I>(versions: I) -> Vec<(&'a AppVersion, &'a AppVersion)>
+    where
+        I: IntoIterator<Item = &'a AppVersion>,
+    {
+        let versions: Vec<_> = versions.into_iter().collect();
+        let mut inconsistent = Vec::new();
+        for (i, a) in versions.iter().enumerate() {
+            for b in &versions[i + 1..] {
+                if !a.is_consistent_with(b) {
+                    let pair = if a.code < b.code { (*a, *b) } else { (*b, *a) };
+                    inconsistent.push(pair);
+                }
+            }
+        }
+        inconsistent
+    }
+
+    /// Get the key this version is ordered and hashed by.
+    ///
+    /// The raw name is only part of it if it isn't a valid `Version`, so `1.0` equals `1.0.0`.
+    fn key(&self) -> (u64, Option<&VersionBuf>, Option<&str>) {
+        let name = match self.version {
+            Some(_) => None,
+            None => self.name.as_deref(),
+        };
+        (self.code, self.version.as_ref(), name)
+    }
+}
+
+impl Ord for AppVersion {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.key().cmp(&other.key())
+    }
+}
+
+impl PartialOrd for AppVersion {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl PartialEq for AppVersion {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for AppVersion {}
+
+impl Hash for AppVersion {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.key().hash(state);
+    }
+}
+
+/// Format the version like Android, as name followed by the code in parentheses.
+impl fmt::Display for AppVersion {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match &self.name {
+            Some(name) => write!(f, "{} ({})", name, self.code),
+            None => write!(f, "({})", self.code),
+        }
+    }
+}
+
+#[cfg_attr(tarpaulin, skip)]
+#[cfg(test)]
+mod tests {
+    use crate::test::hash_of;
+
+    use super::AppVersion;
+
+    #[test]
+    fn ord() {
+        let versions = [
+            AppVersion::from_code(1),
+            AppVersion::new(1, "9.0"),
+            AppVersion::new(2, "Release"),
+            AppVersion::new(2, "beta"),
+            AppVersion::new(2, "1.0-beta (build 2)"),
+            AppVersion::new(2, "1.0.1"),
+            AppVersion::new(3, "0.1"),
+        ];
+        for (i, a) in versions.iter().enumerate() {
+            for (j, b) in versions.iter().enumerate() {
+                assert_eq!(a.cmp(b), i.cmp(&j), "Comparing {} to {}", a, b);
+            }
+        }
+        assert_eq!(AppVersion::new(5, "1.0"), AppVersion::new(5, "1.0.0"),);
+    }
+
+    #[test]
+    fn name_without_version() {
+        let version = AppVersion::new(7, "beta");
+        assert_eq!(version.name(), Some("beta"));
+        assert!(version.version().is_none());
+        assert_eq!(version.to_string(), "beta (7)");
+        assert!(version.is_consistent_with(&AppVersion::new(8, "1.0")));
+        assert_ne!(version, AppVersion::new(7, "Beta"));
+        assert_eq!(hash_of(&version), hash_of(&AppVersion::new(7, "beta")));
+        assert_eq!(
+            hash_of(&AppVersion::new(7, "1.0")),
+            hash_of(&AppVersion::new(7, "1.0.0")),
+        );
+    }
+
+    #[test]
+    fn find_inconsistent() {
+        let history = [
+            AppVersion::new(1, "1.0"),
+            AppVersion::new(3, "1.2"),
+            AppVersion::new(2, "1.3"),
+            AppVersion::from_code(4),
+            AppVersion::new(5, "1.2"),
+            AppVersion::new(6, "1.1"),
+        ];
+        let inconsistent = AppVersion::find_inconsistent(&history);
+        let codes: Vec<_> = inconsistent
+            .iter()
+            .map(|(a, b)| (a.code(), b.code()))
+            .collect();
+        assert_eq!(codes, vec![(2, 3), (3, 6), (2, 5), (2, 6), (5, 6)]);
+    }
+}
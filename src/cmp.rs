@@ -8,6 +8,8 @@
 
 use std::cmp::Ordering;
 
+use crate::Error;
+
 /// Comparison operators enum.
 #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
 pub enum Cmp {
@@ -39,7 +41,7 @@ pub enum Cmp {
 impl Cmp {
     /// Get a comparison operator by it's sign.
     /// Whitespaces are stripped from the sign string.
-    /// An error is returned if the sign isn't recognized.
+    /// An `Error::UnknownOperatorSign` is returned if the sign isn't recognized.
     ///
     /// The following signs are supported:
     ///
@@ -60,22 +62,24 @@ impl Cmp {
     /// assert_eq!(Cmp::from_sign("  >=   "), Ok(Cmp::Ge));
     /// assert!(Cmp::from_sign("*").is_err());
     /// ```
-    #[allow(clippy::result_unit_err)]
-    pub fn from_sign<S: AsRef<str>>(sign: S) -> Result<Cmp, ()> {
-        match sign.as_ref().trim() {
+    pub fn from_sign<S: AsRef<str>>(sign: S) -> Result<Cmp, Error> {
+        let sign = sign.as_ref();
+        match sign.trim() {
             "==" | "=" => Ok(Cmp::Eq),
             "!=" | "!" | "<>" => Ok(Cmp::Ne),
             "<" => Ok(Cmp::Lt),
             "<=" => Ok(Cmp::Le),
             ">=" => Ok(Cmp::Ge),
             ">" => Ok(Cmp::Gt),
-            _ => Err(()),
+            _ => Err(Error::UnknownOperatorSign {
+                at: sign.len() - sign.trim_start().len(),
+            }),
         }
     }
 
     /// Get a comparison operator by it's name.
     /// Names are case-insensitive, and whitespaces are stripped from the string.
-    /// An error is returned if the name isn't recognized.
+    /// An `Error::UnknownOperatorName` is returned if the name isn't recognized.
     ///
     /// # Examples
     ///
@@ -87,16 +91,18 @@ impl Cmp {
     /// assert_eq!(Cmp::from_name("  Ge   "), Ok(Cmp::Ge));
     /// assert!(Cmp::from_name("abc").is_err());
     /// ```
-    #[allow(clippy::result_unit_err)]
-    pub fn from_name<S: AsRef<str>>(sign: S) -> Result<Cmp, ()> {
-        match sign.as_ref().trim().to_lowercase().as_str() {
+    pub fn from_name<S: AsRef<str>>(sign: S) -> Result<Cmp, Error> {
+        let sign = sign.as_ref();
+        match sign.trim().to_lowercase().as_str() {
             "eq" => Ok(Cmp::Eq),
             "ne" => Ok(Cmp::Ne),
             "lt" => Ok(Cmp::Lt),
             "le" => Ok(Cmp::Le),
             "ge" => Ok(Cmp::Ge),
             "gt" => Ok(Cmp::Gt),
-            _ => Err(()),
+            _ => Err(Error::UnknownOperatorName {
+                at: sign.len() - sign.trim_start().len(),
+            }),
         }
     }
 
@@ -337,6 +343,7 @@ mod tests {
     use std::cmp::Ordering;
 
     use super::Cmp;
+    use crate::Error;
 
     #[test]
     fn from_sign() {
@@ -353,7 +360,14 @@ mod tests {
 
         // Exceptional cases
         assert_eq!(Cmp::from_sign("  <=  ").unwrap(), Cmp::Le);
-        assert_eq!(Cmp::from_sign("*"), Err(()));
+        assert_eq!(
+            Cmp::from_sign("*"),
+            Err(Error::UnknownOperatorSign { at: 0 })
+        );
+        assert_eq!(
+            Cmp::from_sign("  =>"),
+            Err(Error::UnknownOperatorSign { at: 2 })
+        );
     }
 
     #[test]
@@ -368,7 +382,14 @@ mod tests {
 
         // Exceptional cases
         assert_eq!(Cmp::from_name("  Le  ").unwrap(), Cmp::Le);
-        assert_eq!(Cmp::from_name("abc"), Err(()));
+        assert_eq!(
+            Cmp::from_name("abc"),
+            Err(Error::UnknownOperatorName { at: 0 })
+        );
+        assert_eq!(
+            Cmp::from_name(" equal"),
+            Err(Error::UnknownOperatorName { at: 1 })
+        );
     }
 
     #[test]
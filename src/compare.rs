@@ -1,14 +1,15 @@
 //! Version compare module, with useful static comparison methods.
 
 use crate::version::Version;
-use crate::Cmp;
+use crate::{Cmp, Error};
 
 /// Compare two version number strings to each other.
 ///
 /// This compares version `a` to version `b`, and returns whether version `a` is greater, less
 /// or equal to version `b`.
 ///
-/// If either version number string is invalid an error is returned.
+/// If either version number string is invalid an error is returned. Version `a` is parsed first,
+/// so if both are invalid the error for `a` is returned.
 ///
 /// One of the following operators is returned:
 ///
@@ -25,14 +26,13 @@ use crate::Cmp;
 /// assert_eq!(compare("1.2.3", "1.2.4"), Ok(Cmp::Lt));
 /// assert_eq!(compare("1", "0.1"), Ok(Cmp::Gt));
 /// ```
-#[allow(clippy::result_unit_err)]
-pub fn compare<A, B>(a: A, b: B) -> Result<Cmp, ()>
+pub fn compare<A, B>(a: A, b: B) -> Result<Cmp, Error>
 where
     A: AsRef<str>,
     B: AsRef<str>,
 {
-    let a = Version::from(a.as_ref()).ok_or(())?;
-    let b = Version::from(b.as_ref()).ok_or(())?;
+    let a = Version::from(a.as_ref())?;
+    let b = Version::from(b.as_ref())?;
     Ok(a.compare(b))
 }
 
@@ -52,14 +52,13 @@ where
 /// assert!(compare_to("1", "0.1", Cmp::Gt).unwrap());
 /// assert!(compare_to("1", "0.1", Cmp::Ge).unwrap());
 /// ```
-#[allow(clippy::result_unit_err)]
-pub fn compare_to<A, B>(a: A, b: B, operator: Cmp) -> Result<bool, ()>
+pub fn compare_to<A, B>(a: A, b: B, operator: Cmp) -> Result<bool, Error>
 where
     A: AsRef<str>,
     B: AsRef<str>,
 {
-    let a = Version::from(a.as_ref()).ok_or(())?;
-    let b = Version::from(b.as_ref()).ok_or(())?;
+    let a = Version::from(a.as_ref())?;
+    let b = Version::from(b.as_ref())?;
     Ok(a.compare_to(b, operator))
 }
 
@@ -67,7 +66,7 @@ where
 #[cfg(test)]
 mod tests {
     use crate::test::{COMBIS, COMBIS_ERROR};
-    use crate::Cmp;
+    use crate::{Cmp, Error};
 
     #[test]
     fn compare() {
@@ -106,14 +105,25 @@ mod tests {
 
         // Compare each error version in the version set
         for entry in COMBIS_ERROR {
-            let result = super::compare_to(entry.0, entry.1, entry.2);
-
-            if result.is_ok() {
-                assert!(!result.unwrap())
+            if let Ok(result) = super::compare_to(entry.0, entry.1, entry.2) {
+                assert!(!result)
             }
         }
 
         // Assert an exceptional case, compare to not equal
         assert!(super::compare_to("1.2.3", "1.2", Cmp::Ne).unwrap());
     }
+
+    #[test]
+    fn compare_error() {
+        assert_eq!(
+            super::compare("1.2", "dev"),
+            Err(Error::NoNumericPart { at: 0 })
+        );
+        assert_eq!(super::compare_to("1.2.dev", "1.2 dev", Cmp::Eq), Ok(true));
+        assert_eq!(
+            super::compare_to("snapshot", "1", Cmp::Lt),
+            Err(Error::NoNumericPart { at: 0 })
+        );
+    }
 }
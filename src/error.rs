@@ -0,0 +1,246 @@
+//! Error module, which provides the `Error` enum.
+//!
+//! All fallible operations in this library return this error, describing what went wrong and
+//! where in the input it went wrong.
+
+use std::error;
+use std::fmt;
+
+/// Error enum, describing why parsing or comparing failed.
+///
+/// Variants that point into the input carry byte offsets in the `at` field.
+///
+/// # Examples
+///
+/// ```
+/// use version_compare::{Error, Version};
+///
+/// assert_eq!(
+///     Version::from("alpha.dev").unwrap_err(),
+///     Error::NoNumericPart { at: 0 },
+/// );
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Error {
+    /// The version string has text parts, but no numeric part.
+    ///
+    /// Holds the byte offset of the first text part in the version string.
+    NoNumericPart {
+        /// Byte offset of the first text part.
+        at: usize,
+    },
+
+    /// A version part index is out of range.
+    PartOutOfRange {
+        /// The requested part index.
+        index: usize,
+
+        /// The number of parts in the version.
+        len: usize,
+    },
+
+    /// A comparison operator sign isn't recognized.
+    UnknownOperatorSign {
+        /// Byte offset of the sign in the input, leading whitespace is skipped.
+        at: usize,
+    },
+
+    /// A comparison operator name isn't recognized.
+    UnknownOperatorName {
+        /// Byte offset of the name in the input, leading whitespace is skipped.
+        at: usize,
+    },
+
+    /// A number doesn't fit the integer type it must be parsed into.
+    NumberOverflow {
+        /// Byte offset of the number in the input.
+        at: usize,
+    },
+
+    /// A requirement clause has a comparison operator, but no version.
+    MissingVersion {
+        /// Byte offset where the version was expected.
+        at: usize,
+    },
+
+    /// A requirement has an empty clause, such as two subsequent commas.
+    EmptyClause {
+        /// Byte offset of the comma ending the empty clause.
+        at: usize,
+    },
+
+    /// A required version component is empty, such as the epoch in `:1.0`.
+    EmptyPart {
+        /// Byte offset where the component was expected.
+        at: usize,
+    },
+
+    /// A version contains a character that isn't allowed at its position.
+    UnexpectedChar {
+        /// Byte offset of the character.
+        at: usize,
+    },
+
+    /// A number has a leading zero where that isn't allowed, such as in `1.02.3` for SemVer.
+    LeadingZero {
+        /// Byte offset of the number.
+        at: usize,
+    },
+
+    /// An opening bracket has no matching closing bracket, such as in `[1.0,2.0`.
+    UnclosedBracket {
+        /// Byte offset of the opening bracket.
+        at: usize,
+    },
+
+    /// A range is invalid, such as a range with its bounds in reverse order.
+    InvalidRange {
+        /// Byte offset of the range.
+        at: usize,
+    },
+
+    /// A version has more components than its scheme allows, such as `1.2.3.4` for Apple.
+    TooManyParts {
+        /// Byte offset of the separator before the first extra component.
+        at: usize,
+    },
+}
+
+impl Error {
+    /// Shift the byte offset of this error by `by` bytes.
+    ///
+    /// Used when the erroneous input is part of a larger input, such as a version in a
+    /// requirement.
+    pub(crate) fn offset(self, by: usize) -> Self {
+        match self {
+            Error::NoNumericPart { at } => Error::NoNumericPart { at: at + by },
+            Error::UnknownOperatorSign { at } => Error::UnknownOperatorSign { at: at + by },
+            Error::UnknownOperatorName { at } => Error::UnknownOperatorName { at: at + by },
+            Error::NumberOverflow { at } => Error::NumberOverflow { at: at + by },
+            Error::MissingVersion { at } => Error::MissingVersion { at: at + by },
+            Error::EmptyClause { at } => Error::EmptyClause { at: at + by },
+            Error::EmptyPart { at } => Error::EmptyPart { at: at + by },
+            Error::UnexpectedChar { at } => Error::UnexpectedChar { at: at + by },
+            Error::LeadingZero { at } => Error::LeadingZero { at: at + by },
+            Error::UnclosedBracket { at } => Error::UnclosedBracket { at: at + by },
+            Error::InvalidRange { at } => Error::InvalidRange { at: at + by },
+            Error::TooManyParts { at } => Error::TooManyParts { at: at + by },
+            err @ Error::PartOutOfRange { .. } => err,
+        }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::NoNumericPart { at } => write!(
+                f,
+                "version has no numeric part, found text at byte {} instead",
+                at
+            ),
+            Error::PartOutOfRange { index, len } => write!(
+                f,
+                "version part index {} is out of range for version with {} parts",
+                index, len
+            ),
+            Error::UnknownOperatorSign { at } => {
+                write!(f, "unknown comparison operator sign at byte {}", at)
+            }
+            Error::UnknownOperatorName { at } => {
+                write!(f, "unknown comparison operator name at byte {}", at)
+            }
+            Error::NumberOverflow { at } => write!(f, "number at byte {} is too large", at),
+            Error::MissingVersion { at } => write!(f, "expected a version at byte {}", at),
+            Error::EmptyClause { at } => write!(f, "empty requirement clause at byte {}", at),
+            Error::EmptyPart { at } => write!(f, "empty version component at byte {}", at),
+            Error::UnexpectedChar { at } => write!(f, "unexpected character at byte {}", at),
+            Error::LeadingZero { at } => write!(f, "number with leading zero at byte {}", at),
+            Error::UnclosedBracket { at } => write!(f, "unclosed bracket at byte {}", at),
+            Error::InvalidRange { at } => write!(f, "invalid range at byte {}", at),
+            Error::TooManyParts { at } => write!(f, "too many version components at byte {}", at),
+        }
+    }
+}
+
+impl error::Error for Error {}
+
+#[cfg_attr(tarpaulin, skip)]
+#[cfg(test)]
+mod tests {
+    use super::Error;
+
+    #[test]
+    fn display() {
+        assert_eq!(
+            format!("{}", Error::NoNumericPart { at: 3 }),
+            "version has no numeric part, found text at byte 3 instead",
+        );
+        assert_eq!(
+            format!("{}", Error::PartOutOfRange { index: 3, len: 2 }),
+            "version part index 3 is out of range for version with 2 parts",
+        );
+        assert_eq!(
+            format!("{}", Error::UnknownOperatorSign { at: 1 }),
+            "unknown comparison operator sign at byte 1",
+        );
+        assert_eq!(
+            format!("{}", Error::UnknownOperatorName { at: 0 }),
+            "unknown comparison operator name at byte 0",
+        );
+        assert_eq!(
+            format!("{}", Error::NumberOverflow { at: 2 }),
+            "number at byte 2 is too large",
+        );
+        assert_eq!(
+            format!("{}", Error::MissingVersion { at: 2 }),
+            "expected a version at byte 2",
+        );
+        assert_eq!(
+            format!("{}", Error::EmptyClause { at: 4 }),
+            "empty requirement clause at byte 4",
+        );
+        assert_eq!(
+            format!("{}", Error::EmptyPart { at: 2 }),
+            "empty version component at byte 2",
+        );
+        assert_eq!(
+            format!("{}", Error::UnexpectedChar { at: 1 }),
+            "unexpected character at byte 1",
+        );
+        assert_eq!(
+            format!("{}", Error::LeadingZero { at: 2 }),
+            "number with leading zero at byte 2",
+        );
+        assert_eq!(
+            format!("{}", Error::UnclosedBracket { at: 0 }),
+            "unclosed bracket at byte 0",
+        );
+        assert_eq!(
+            format!("{}", Error::InvalidRange { at: 6 }),
+            "invalid range at byte 6",
+        );
+        assert_eq!(
+            format!("{}", Error::TooManyParts { at: 5 }),
+            "too many version components at byte 5",
+        );
+    }
+
+    #[test]
+    fn offset() {
+        assert_eq!(
+            Error::NoNumericPart { at: 1 }.offset(3),
+            Error::NoNumericPart { at: 4 },
+        );
+        assert_eq!(
+            Error::PartOutOfRange { index: 1, len: 1 }.offset(3),
+            Error::PartOutOfRange { index: 1, len: 1 },
+        );
+    }
+
+    #[test]
+    fn std_error() {
+        let err: Box<dyn std::error::Error> = Box::new(Error::NoNumericPart { at: 0 });
+        assert!(err.source().is_none());
+    }
+}
@@ -72,11 +72,47 @@
 //! * Parse complex and unspecified formats
 //! * Static, standalone methods to easily compare version strings in a single line
 //!   of code
+//! * Owned versions with `VersionBuf`, that don't borrow from the version string
+//! * Sort versions, or use them as key in ordered and hashed collections
+//! * Version requirements with `VersionReq`, such as `>= 1.2, < 2.0`
+//! * Version ranges with `VersionRange`, supporting union, intersection and complement
+//! * npm ranges with `NpmRange`, compatible with node-semver, such as `^1.2.3 || 2.x`
+//! * Cargo requirements with `CargoReq`, such as `^0.2.3` or `~1.2, <1.2.5`, with explicit
+//!   bounds for display
+//! * CocoaPods requirements with `PodRequirement`, such as `~> 1.2, >= 1.2.3`, and Swift
+//!   Package Manager requirements with `SwiftRequirement`, such as `.upToNextMinor(from: "1.2.3")`
+//! * Android app versions with `AppVersion`, pairing a `versionCode` with a `versionName`, and
+//!   detecting inconsistent pairs in a release history
+//! * Version schemes of specific ecosystems, selected through `Manifest` or used through their
+//!   dedicated type:
+//!   * Debian, like `dpkg --compare-versions` (`DebianVersion`)
+//!   * RPM, like `rpmvercmp` (`RpmVersion`)
+//!   * Python PEP 440, with normalization and specifiers such as `~=1.4.2` (`Pep440Version`)
+//!   * Strict SemVer 2.0, with pre-release ordering and build metadata (`SemverVersion`)
+//!   * Maven, like `ComparableVersion` of Maven 3.9, with canonical forms and ranges such as
+//!     `(,1.0],[1.2,)` (`MavenVersion`, `MavenRange`)
+//!   * Alpine, like `apk version -t` (`ApkVersion`)
+//!   * Gentoo, like the Package Manager Specification (`GentooVersion`)
+//!   * Arch Linux, like pacman's `vercmp` (`ArchVersion`)
+//!   * Go modules, with pseudo-versions such as `v0.0.0-20191109021931-daa7c04131f5`
+//!     (`GoVersion`)
+//!   * RubyGems, like `Gem::Version`, with requirements such as `~> 7.0, >= 7.0.4`
+//!     (`GemVersion`, `GemRequirement`)
+//!   * NuGet, with 4-part versions and ranges such as `[1.0,2.0)` or `1.*`
+//!     (`NuGetVersion`, `NuGetRange`)
+//!   * PHP, like `version_compare()`, with Composer constraints such as
+//!     `^1.2 || ~2.0.3@beta` (`PhpVersion`, `ComposerConstraint`)
+//!   * Gradle, like `StaticVersionComparator`, with dynamic versions such as `1.+` and rich
+//!     constraints such as `{strictly [1.0,2.0); prefer 1.5}` (`GradleVersion`, `GradleConstraint`)
+//!   * Apple bundle versions, validated like App Store Connect with diagnostics for rejected
+//!     versions (`AppleVersion`)
 //!
 //! ### Semver
 //!
 //! Version numbers using the [semver](http://semver.org/) format are compared
 //! correctly with no additional configuration.
+//! To validate them strictly, and to order pre-releases and ignore build metadata
+//! exactly as specified, use the `Semver` scheme or `SemverVersion`.
 //!
 //! If your version number strings follow this exact format you may be better off
 //! using the [`semver`](https://crates.io/crates/semver) crate for more format
@@ -86,18 +122,37 @@
 //!
 //! _[View complete README](https://github.com/timvisee/version-compare/blob/master/README.md)_
 
+mod app_version;
 mod cmp;
 mod compare;
+mod error;
 mod manifest;
+mod number;
 mod part;
+mod scheme;
 mod version;
+mod version_buf;
+mod version_range;
+mod version_req;
 
 #[cfg(test)]
 mod test;
 
 // Re-exports
+pub use crate::app_version::AppVersion;
 pub use crate::cmp::Cmp;
 pub use crate::compare::{compare, compare_to};
+pub use crate::error::Error;
 pub use crate::manifest::Manifest;
-pub use crate::part::Part;
+pub use crate::number::{Number, NumberBuf};
+pub use crate::part::{Part, PartBuf};
+pub use crate::scheme::{
+    ApkVersion, AppleVersion, ArchVersion, CargoReq, ComposerConstraint, ComposerStability,
+    DebianVersion, GemRequirement, GemVersion, GentooVersion, GoVersion, GradleConstraint,
+    GradleVersion, MavenRange, MavenVersion, NpmRange, NuGetRange, NuGetVersion, Pep440Specifier,
+    Pep440Version, PhpVersion, PodRequirement, RpmVersion, Scheme, SemverVersion, SwiftRequirement,
+};
 pub use crate::version::Version;
+pub use crate::version_buf::VersionBuf;
+pub use crate::version_range::VersionRange;
+pub use crate::version_req::VersionReq;
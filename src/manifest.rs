@@ -2,7 +2,10 @@
 //!
 //! A version manifest can be used to configure and specify how versions are parsed and compared.
 //! For example, you can configure the maximum depth of a version number, and set whether text
-//! parts are ignored in a version string.
+//! parts are ignored in a version string. The version scheme selects how versions of a specific
+//! ecosystem are parsed and ordered.
+
+use crate::Scheme;
 
 /// Version manifest (configuration).
 ///
@@ -40,6 +43,13 @@ pub struct Manifest {
     ///
     /// Issue: https://github.com/timvisee/version-compare/issues/27
     pub gnu_ordering: bool,
+
+    /// The version scheme, defining how versions are parsed and ordered.
+    ///
+    /// Any scheme other than `Scheme::Generic` validates version strings when parsing, and
+    /// compares versions using the ordering of that scheme. The other options above only affect
+    /// the generic scheme.
+    pub scheme: Scheme,
 }
 
 /// Version manifest implementation.
@@ -0,0 +1,266 @@
+//! Version number module.
+//!
+//! A module that provides the `Number` struct, an arbitrary-precision non-negative number used in
+//! numeric version parts. Numbers that fit an `u64` are stored as is, larger numbers borrow their
+//! digits from the version string. Comparing numbers never allocates.
+//!
+//! The `NumberBuf` struct is the owned counterpart of `Number`, used by `PartBuf`.
+
+use std::cmp::Ordering;
+use std::fmt;
+
+/// Arbitrary-precision, non-negative number, as used in numeric version parts.
+///
+/// Numbers are compared by magnitude, leading zeros are insignificant.
+///
+/// # Examples
+///
+/// ```
+/// use version_compare::Number;
+///
+/// let a = Number::parse("20240131123045").unwrap();
+/// let b = Number::parse("123456789012345678901234567890").unwrap();
+///
+/// assert!(a < b);
+/// assert_eq!(a.as_u64(), Some(20240131123045));
+/// assert_eq!(b.as_u64(), None);
+/// assert_eq!(Number::parse("007"), Some(Number::from(7)));
+/// ```
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Number<'a>(Repr<'a>);
+
+/// Internal number representation.
+///
+/// A number is only stored as digits if it doesn't fit an `u64`, so each value has exactly one
+/// representation, and derived equality and hashing are correct.
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+enum Repr<'a> {
+    /// Number fitting an `u64`.
+    Small(u64),
+
+    /// Number not fitting an `u64`, holds the digits without leading zeros.
+    Large(&'a str),
+}
+
+impl<'a> Number<'a> {
+    /// Parse a number from a string of ASCII digits, of any length.
+    ///
+    /// `None` is returned if the string is empty or contains anything other than ASCII digits.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use version_compare::Number;
+    ///
+    /// assert_eq!(Number::parse("12"), Some(Number::from(12)));
+    /// assert!(Number::parse("99999999999999999999999").is_some());
+    /// assert!(Number::parse("").is_none());
+    /// assert!(Number::parse("1a").is_none());
+    /// ```
+    pub fn parse(digits: &'a str) -> Option<Self> {
+        if digits.is_empty() || !digits.bytes().all(|b| b.is_ascii_digit()) {
+            return None;
+        }
+
+        match digits.parse::<u64>() {
+            Ok(n) => Some(Number(Repr::Small(n))),
+            Err(_) => Some(Number(Repr::Large(digits.trim_start_matches('0')))),
+        }
+    }
+
+    /// Get the number as `u64`, if it fits.
+    pub fn as_u64(&self) -> Option<u64> {
+        match self.0 {
+            Repr::Small(n) => Some(n),
+            Repr::Large(_) => None,
+        }
+    }
+
+    /// Check whether this number is zero.
+    pub fn is_zero(&self) -> bool {
+        self.0 == Repr::Small(0)
+    }
+}
+
+impl<'a> From<u64> for Number<'a> {
+    fn from(n: u64) -> Self {
+        Number(Repr::Small(n))
+    }
+}
+
+impl<'a> Ord for Number<'a> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match (self.0, other.0) {
+            (Repr::Small(a), Repr::Small(b)) => a.cmp(&b),
+            (Repr::Small(_), Repr::Large(_)) => Ordering::Less,
+            (Repr::Large(_), Repr::Small(_)) => Ordering::Greater,
+            // Digits without leading zeros, compare by length first
+            (Repr::Large(a), Repr::Large(b)) => a.len().cmp(&b.len()).then_with(|| a.cmp(b)),
+        }
+    }
+}
+
+impl<'a> PartialOrd for Number<'a> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<'a> fmt::Display for Number<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.0 {
+            Repr::Small(n) => write!(f, "{}", n),
+            Repr::Large(digits) => write!(f, "{}", digits),
+        }
+    }
+}
+
+// Show numbers like plain integers as debug output
+impl<'a> fmt::Debug for Number<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
+
+/// Owned arbitrary-precision, non-negative number.
+///
+/// The owned counterpart of `Number`, which doesn't borrow from the version string.
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub struct NumberBuf(ReprBuf);
+
+/// Internal owned number representation, see `Repr`.
+#[derive(Clone, PartialEq, Eq, Hash)]
+enum ReprBuf {
+    Small(u64),
+    Large(String),
+}
+
+impl NumberBuf {
+    /// Borrow this owned number as `Number`.
+    pub fn as_number(&self) -> Number<'_> {
+        match &self.0 {
+            ReprBuf::Small(n) => Number(Repr::Small(*n)),
+            ReprBuf::Large(digits) => Number(Repr::Large(digits)),
+        }
+    }
+}
+
+impl From<u64> for NumberBuf {
+    fn from(n: u64) -> Self {
+        NumberBuf(ReprBuf::Small(n))
+    }
+}
+
+impl<'a> From<Number<'a>> for NumberBuf {
+    fn from(number: Number<'a>) -> Self {
+        match number.0 {
+            Repr::Small(n) => NumberBuf(ReprBuf::Small(n)),
+            Repr::Large(digits) => NumberBuf(ReprBuf::Large(digits.to_string())),
+        }
+    }
+}
+
+impl Ord for NumberBuf {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.as_number().cmp(&other.as_number())
+    }
+}
+
+impl PartialOrd for NumberBuf {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl fmt::Display for NumberBuf {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Display::fmt(&self.as_number(), f)
+    }
+}
+
+impl fmt::Debug for NumberBuf {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Display::fmt(&self.as_number(), f)
+    }
+}
+
+#[cfg_attr(tarpaulin, skip)]
+#[cfg(test)]
+mod tests {
+    use super::{Number, NumberBuf};
+
+    #[test]
+    fn parse() {
+        assert_eq!(Number::parse("0"), Some(Number::from(0)));
+        assert_eq!(Number::parse("000"), Some(Number::from(0)));
+        assert_eq!(Number::parse("0042"), Some(Number::from(42)));
+        assert_eq!(
+            Number::parse("18446744073709551615"),
+            Some(Number::from(u64::MAX))
+        );
+        assert_eq!(
+            Number::parse("18446744073709551616"),
+            Number::parse("00018446744073709551616"),
+        );
+        assert_eq!(Number::parse(""), None);
+        assert_eq!(Number::parse("-1"), None);
+        assert_eq!(Number::parse("1.0"), None);
+        assert_eq!(Number::parse("١٢"), None);
+    }
+
+    #[test]
+    fn ord() {
+        let numbers = [
+            "0",
+            "1",
+            "20240131123045",
+            "18446744073709551615",
+            "18446744073709551616",
+            "18446744073709551617",
+            "99999999999999999999",
+            "100000000000000000000",
+        ];
+        for (i, a) in numbers.iter().enumerate() {
+            for (j, b) in numbers.iter().enumerate() {
+                let (a, b) = (Number::parse(a).unwrap(), Number::parse(b).unwrap());
+                assert_eq!(a.cmp(&b), i.cmp(&j), "Testing {} against {}", a, b);
+            }
+        }
+    }
+
+    #[test]
+    fn as_u64() {
+        assert_eq!(Number::parse("12").unwrap().as_u64(), Some(12));
+        assert_eq!(
+            Number::parse("18446744073709551616").unwrap().as_u64(),
+            None
+        );
+    }
+
+    #[test]
+    fn is_zero() {
+        assert!(Number::parse("000").unwrap().is_zero());
+        assert!(!Number::parse("010").unwrap().is_zero());
+    }
+
+    #[test]
+    fn display() {
+        assert_eq!(format!("{}", Number::parse("012").unwrap()), "12");
+        assert_eq!(
+            format!("{:?}", Number::parse("0018446744073709551616").unwrap()),
+            "18446744073709551616"
+        );
+    }
+
+    #[test]
+    fn number_buf() {
+        for digits in &["0", "42", "123456789012345678901234567890"] {
+            let number = Number::parse(digits).unwrap();
+            let owned = NumberBuf::from(number);
+
+            assert_eq!(owned.as_number(), number);
+            assert_eq!(format!("{}", owned), format!("{}", number));
+        }
+        assert!(NumberBuf::from(2) < NumberBuf::from(10));
+    }
+}
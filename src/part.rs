@@ -3,9 +3,13 @@
 //! A module that provides the `Part` enum, with the specification of all available version
 //! parts. Each version string is broken down into these version parts when being parsed to a
 //! `Version`.
+//!
+//! The `PartBuf` enum is the owned counterpart of `Part`, used by `VersionBuf`.
 
 use std::fmt;
 
+use crate::{Number, NumberBuf};
+
 /// Version string part enum.
 ///
 /// Each version string is broken down into these version parts when being parsed to a `Version`.
@@ -13,8 +17,8 @@ use std::fmt;
 pub enum Part<'a> {
     /// Numeric part, most common in version strings.
     ///
-    /// Holds the numerical value.
-    Number(i32),
+    /// Holds the numerical value, which may have any number of digits.
+    Number(Number<'a>),
 
     /// A text part.
     ///
@@ -31,14 +35,88 @@ impl<'a> fmt::Display for Part<'a> {
     }
 }
 
+/// Owned version string part enum.
+///
+/// The owned counterpart of `Part`, which doesn't borrow from the version string. Each version
+/// string is broken down into these version parts when being parsed to a `VersionBuf`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PartBuf {
+    /// Numeric part, most common in version strings.
+    ///
+    /// Holds the numerical value, which may have any number of digits.
+    Number(NumberBuf),
+
+    /// A text part.
+    ///
+    /// These parts usually hold text with an yet unknown definition. Holds the owned string.
+    Text(String),
+}
+
+impl PartBuf {
+    /// Borrow this owned part as `Part`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use version_compare::{Part, PartBuf};
+    ///
+    /// assert_eq!(PartBuf::Number(1.into()).as_part(), Part::Number(1.into()));
+    /// assert_eq!(PartBuf::Text("dev".into()).as_part(), Part::Text("dev"));
+    /// ```
+    pub fn as_part(&self) -> Part<'_> {
+        match self {
+            PartBuf::Number(n) => Part::Number(n.as_number()),
+            PartBuf::Text(t) => Part::Text(t),
+        }
+    }
+}
+
+impl<'a> From<Part<'a>> for PartBuf {
+    fn from(part: Part<'a>) -> Self {
+        match part {
+            Part::Number(n) => PartBuf::Number(n.into()),
+            Part::Text(t) => PartBuf::Text(t.to_string()),
+        }
+    }
+}
+
+impl fmt::Display for PartBuf {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.as_part().fmt(f)
+    }
+}
+
 #[cfg_attr(tarpaulin, skip)]
 #[cfg(test)]
 mod tests {
-    use super::Part;
+    use super::{Part, PartBuf};
 
     #[test]
     fn display() {
-        assert_eq!(format!("{}", Part::Number(123)), "123");
+        assert_eq!(format!("{}", Part::Number(123.into())), "123");
         assert_eq!(format!("{}", Part::Text("123")), "123");
+        assert_eq!(format!("{}", PartBuf::Number(123.into())), "123");
+        assert_eq!(format!("{}", PartBuf::Text("123".into())), "123");
+    }
+
+    #[test]
+    fn as_part() {
+        assert_eq!(
+            PartBuf::Number(123.into()).as_part(),
+            Part::Number(123.into())
+        );
+        assert_eq!(PartBuf::Text("abc".into()).as_part(), Part::Text("abc"));
+    }
+
+    #[test]
+    fn from_part() {
+        assert_eq!(
+            PartBuf::from(Part::Number(123.into())),
+            PartBuf::Number(123.into())
+        );
+        assert_eq!(
+            PartBuf::from(Part::Text("abc")),
+            PartBuf::Text("abc".into())
+        );
     }
 }
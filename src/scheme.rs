@@ -0,0 +1,308 @@
+//! Version scheme module, which provides the `Scheme` enum.
+//!
+//! A version scheme defines how versions of a specific ecosystem are parsed and ordered, such as
+//! Debian package versions. Each scheme has a dedicated parsed version type, and can be selected
+//! for `Version` through the `scheme` field of a `Manifest`.
+
+mod apk;
+mod apple;
+mod arch;
+mod cargo_req;
+mod composer_constraint;
+mod debian;
+mod gem_requirement;
+mod gentoo;
+mod go;
+mod gradle;
+mod gradle_constraint;
+mod maven;
+mod maven_range;
+mod npm_range;
+mod nuget;
+mod nuget_range;
+mod pep440;
+mod pep440_specifier;
+mod php;
+mod pod_requirement;
+mod rpm;
+mod rubygems;
+mod semver;
+mod swift_requirement;
+
+use std::hash::{Hash, Hasher};
+
+use crate::Cmp;
+
+pub use self::apk::ApkVersion;
+pub use self::apple::AppleVersion;
+pub use self::arch::ArchVersion;
+pub use self::cargo_req::CargoReq;
+pub use self::composer_constraint::{ComposerConstraint, ComposerStability};
+pub use self::debian::DebianVersion;
+pub use self::gem_requirement::GemRequirement;
+pub use self::gentoo::GentooVersion;
+pub use self::go::GoVersion;
+pub use self::gradle::GradleVersion;
+pub use self::gradle_constraint::GradleConstraint;
+pub use self::maven::MavenVersion;
+pub use self::maven_range::MavenRange;
+pub use self::npm_range::NpmRange;
+pub use self::nuget::NuGetVersion;
+pub use self::nuget_range::NuGetRange;
+pub use self::pep440::Pep440Version;
+pub use self::pep440_specifier::Pep440Specifier;
+pub use self::php::PhpVersion;
+pub use self::pod_requirement::PodRequirement;
+pub use self::rpm::RpmVersion;
+pub use self::rubygems::GemVersion;
+pub use self::semver::SemverVersion;
+pub use self::swift_requirement::SwiftRequirement;
+
+/// Version scheme, defining how versions are parsed and ordered.
+///
+/// The generic scheme is the default, and parses version numbers with no specific format using a
+/// best-effort approach. Other schemes validate version strings when parsing, and reproduce the
+/// ordering of their ecosystem exactly.
+///
+/// # Examples
+///
+/// ```
+/// use version_compare::{Cmp, Manifest, Scheme, Version};
+///
+/// let mut manifest = Manifest::default();
+/// manifest.scheme = Scheme::Debian;
+///
+/// let a = Version::from_manifest("1.0~rc1-1", &manifest).unwrap();
+/// let b = Version::from_manifest("1.0-1", &manifest).unwrap();
+///
+/// assert_eq!(a.compare(&b), Cmp::Lt);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum Scheme {
+    /// Generic best-effort scheme, for version numbers with no specific format.
+    Generic,
+
+    /// Debian package versions, ordered like `dpkg --compare-versions`.
+    ///
+    /// See `DebianVersion`.
+    Debian,
+
+    /// RPM package versions, ordered like rpm's `rpmvercmp`.
+    ///
+    /// See `RpmVersion`.
+    Rpm,
+
+    /// Python package versions, as specified by PEP 440.
+    ///
+    /// See `Pep440Version`.
+    Pep440,
+
+    /// Semantic versions, as specified by Semantic Versioning 2.0.0.
+    ///
+    /// See `SemverVersion`.
+    Semver,
+
+    /// Maven artifact versions, ordered like Maven's `ComparableVersion`.
+    ///
+    /// See `MavenVersion`.
+    Maven,
+
+    /// Alpine apk package versions, ordered like `apk version -t`.
+    ///
+    /// See `ApkVersion`.
+    Apk,
+
+    /// Gentoo package versions, ordered like the Package Manager Specification.
+    ///
+    /// See `GentooVersion`.
+    Gentoo,
+
+    /// Arch Linux package versions, ordered like pacman's `vercmp`.
+    ///
+    /// See `ArchVersion`.
+    Arch,
+
+    /// Go module versions, including pseudo-versions, ordered like the `go` command.
+    ///
+    /// See `GoVersion`.
+    Go,
+
+    /// RubyGems versions, ordered like `Gem::Version`.
+    ///
+    /// See `GemVersion`.
+    RubyGems,
+
+    /// NuGet package versions, ordered like `NuGet.Versioning`.
+    ///
+    /// See `NuGetVersion`.
+    NuGet,
+
+    /// PHP versions, ordered like PHP's `version_compare()`.
+    ///
+    /// See `PhpVersion`.
+    Php,
+
+    /// Gradle dependency versions, ordered like Gradle's `StaticVersionComparator`.
+    ///
+    /// See `GradleVersion`.
+    Gradle,
+
+    /// Apple bundle versions, validated like App Store Connect.
+    ///
+    /// See `AppleVersion`.
+    Apple,
+}
+
+impl Scheme {
+    /// Validate the given version string against this scheme.
+    pub(crate) fn validate(self, version: &str) -> Result<(), crate::Error> {
+        match self {
+            Scheme::Generic => Ok(()),
+            Scheme::Debian => DebianVersion::parse(version).map(|_| ()),
+            Scheme::Rpm => RpmVersion::parse(version).map(|_| ()),
+            Scheme::Pep440 => Pep440Version::parse(version).map(|_| ()),
+            Scheme::Semver => SemverVersion::parse(version).map(|_| ()),
+            Scheme::Maven => Ok(()),
+            Scheme::Apk => ApkVersion::parse(version).map(|_| ()),
+            Scheme::Gentoo => GentooVersion::parse(version).map(|_| ()),
+            Scheme::Arch => ArchVersion::parse(version).map(|_| ()),
+            Scheme::Go => GoVersion::parse(version).map(|_| ()),
+            Scheme::RubyGems => GemVersion::parse(version).map(|_| ()),
+            Scheme::NuGet => NuGetVersion::parse(version).map(|_| ()),
+            Scheme::Php => PhpVersion::parse(version).map(|_| ()),
+            Scheme::Gradle => Ok(()),
+            Scheme::Apple => AppleVersion::parse(version).map(|_| ()),
+        }
+    }
+
+    /// Compare two version strings using this scheme.
+    ///
+    /// `None` is returned for the generic scheme, or if any of the versions is invalid for this
+    /// scheme, in which case the generic ordering should be used.
+    pub(crate) fn compare(self, a: &str, b: &str) -> Option<Cmp> {
+        match self {
+            Scheme::Generic => None,
+            Scheme::Debian => compare_parsed(DebianVersion::parse(a), DebianVersion::parse(b)),
+            Scheme::Rpm => compare_parsed(RpmVersion::parse(a), RpmVersion::parse(b)),
+            Scheme::Pep440 => compare_parsed(Pep440Version::parse(a), Pep440Version::parse(b)),
+            Scheme::Semver => compare_parsed(SemverVersion::parse(a), SemverVersion::parse(b)),
+            Scheme::Maven => Some(MavenVersion::parse(a).cmp(&MavenVersion::parse(b)).into()),
+            Scheme::Apk => compare_parsed(ApkVersion::parse(a), ApkVersion::parse(b)),
+            Scheme::Gentoo => compare_parsed(GentooVersion::parse(a), GentooVersion::parse(b)),
+            Scheme::Arch => compare_parsed(ArchVersion::parse(a), ArchVersion::parse(b)),
+            Scheme::Go => compare_parsed(GoVersion::parse(a), GoVersion::parse(b)),
+            Scheme::RubyGems => compare_parsed(GemVersion::parse(a), GemVersion::parse(b)),
+            Scheme::NuGet => compare_parsed(NuGetVersion::parse(a), NuGetVersion::parse(b)),
+            Scheme::Php => compare_parsed(PhpVersion::parse(a), PhpVersion::parse(b)),
+            Scheme::Gradle => Some(GradleVersion::parse(a).cmp(&GradleVersion::parse(b)).into()),
+            Scheme::Apple => compare_parsed(AppleVersion::parse(a), AppleVersion::parse(b)),
+        }
+    }
+
+    /// Hash the given version string using this scheme, consistent with `compare`.
+    ///
+    /// Returns `false` if nothing was hashed, in which case the generic hash should be used.
+    pub(crate) fn hash<H: Hasher>(self, version: &str, state: &mut H) -> bool {
+        match self {
+            Scheme::Generic => false,
+            Scheme::Debian => hash_parsed(DebianVersion::parse(version), state),
+            Scheme::Rpm => hash_parsed(RpmVersion::parse(version), state),
+            Scheme::Pep440 => hash_parsed(Pep440Version::parse(version), state),
+            Scheme::Semver => hash_parsed(SemverVersion::parse(version), state),
+            Scheme::Maven => {
+                MavenVersion::parse(version).hash(state);
+                true
+            }
+            Scheme::Apk => hash_parsed(ApkVersion::parse(version), state),
+            Scheme::Gentoo => hash_parsed(GentooVersion::parse(version), state),
+            Scheme::Arch => hash_parsed(ArchVersion::parse(version), state),
+            Scheme::Go => hash_parsed(GoVersion::parse(version), state),
+            Scheme::RubyGems => hash_parsed(GemVersion::parse(version), state),
+            Scheme::NuGet => hash_parsed(NuGetVersion::parse(version), state),
+            Scheme::Php => hash_parsed(PhpVersion::parse(version), state),
+            Scheme::Gradle => {
+                GradleVersion::parse(version).hash(state);
+                true
+            }
+            Scheme::Apple => hash_parsed(AppleVersion::parse(version), state),
+        }
+    }
+}
+
+impl Default for Scheme {
+    fn default() -> Self {
+        Scheme::Generic
+    }
+}
+
+/// Compare two parsed versions, if both parsed successfully.
+fn compare_parsed<T: Ord, E>(a: Result<T, E>, b: Result<T, E>) -> Option<Cmp> {
+    Some(a.ok()?.cmp(&b.ok()?).into())
+}
+
+/// Hash a parsed version, if it parsed successfully.
+fn hash_parsed<T: Hash, E, H: Hasher>(version: Result<T, E>, state: &mut H) -> bool {
+    match version {
+        Ok(version) => {
+            version.hash(state);
+            true
+        }
+        Err(_) => false,
+    }
+}
+
+#[cfg_attr(tarpaulin, skip)]
+#[cfg(test)]
+mod tests {
+    use crate::test::hash_of;
+    use crate::{Cmp, Manifest, Version};
+
+    use super::Scheme;
+
+    /// Per scheme, two versions the scheme orders, and a version it rejects if it validates.
+    const SCHEMES: &[(Scheme, &str, &str, Cmp, Option<&str>)] = &[
+        (Scheme::Debian, "1.0~rc1-1", "1.0-1", Cmp::Lt, Some("1.0-")),
+        (Scheme::Rpm, "1.0~rc1", "1.0", Cmp::Lt, None),
+        (Scheme::Pep440, "1.0.post1", "1.0.0", Cmp::Gt, Some("1.0.*")),
+        (Scheme::Semver, "1.0.0-a.1", "1.0.0-b", Cmp::Lt, Some("1.0")),
+        (Scheme::Maven, "1.0-GA", "1", Cmp::Eq, None),
+        (Scheme::Apk, "1.0_rc1", "1.0", Cmp::Lt, Some("1.0_dev")),
+        (Scheme::Gentoo, "1.010", "1.01", Cmp::Eq, Some("1.0_git")),
+        (Scheme::Arch, "1.0-1", "1_0-01", Cmp::Eq, Some("1.0-1-1")),
+        (Scheme::Go, "v1.2.3-pre", "v1.2.3", Cmp::Lt, Some("1.2.3")),
+        (Scheme::RubyGems, "1.0.a", "1.0", Cmp::Lt, Some("1.2_3")),
+        (Scheme::NuGet, "1.0-RC", "1.0-rc", Cmp::Eq, Some("1.0-")),
+        (Scheme::Php, "1.0RC1", "1.0", Cmp::Lt, Some("1.0-")),
+        (Scheme::Gradle, "1.0-RC1", "1.0.rc.1", Cmp::Eq, None),
+        (Scheme::Apple, "1.10", "1.9", Cmp::Gt, Some("1.0-beta")),
+    ];
+
+    #[test]
+    fn manifest() {
+        for (scheme, a, b, cmp, invalid) in SCHEMES {
+            let manifest = Manifest {
+                scheme: *scheme,
+                ..Manifest::default()
+            };
+            let parse = |v| {
+                Version::from_manifest(v, &manifest)
+                    .unwrap_or_else(|err| panic!("Parsing {:?} as {:?}: {}", v, scheme, err))
+            };
+            let (a, b) = (parse(a), parse(b));
+            assert_eq!(a.compare(&b), *cmp, "Comparing {} to {}", a, b);
+            assert_eq!(b.compare(&a), cmp.flip(), "Comparing {} to {}", b, a);
+            if *cmp == Cmp::Eq {
+                assert_eq!(hash_of(&a), hash_of(&b), "Hashing {} and {}", a, b);
+            }
+            if let Some(invalid) = invalid {
+                assert!(
+                    Version::from_manifest(invalid, &manifest).is_err(),
+                    "Parsing {:?} as {:?}",
+                    invalid,
+                    scheme,
+                );
+            }
+        }
+    }
+}
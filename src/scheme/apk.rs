@@ -0,0 +1,400 @@
+//! Alpine apk version scheme, which provides the `ApkVersion` struct.
+//!
+//! apk package versions have the form `{number}{.number}...{letter}{_suffix{number}}...`,
+//! optionally followed by a commit hash `~hash` and a package revision `-rN`.
+
+use std::cmp::Ordering;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+
+use crate::{Error, Number};
+
+/// Suffixes sorting before a release, in order.
+const PRE_SUFFIXES: &[&str] = &["alpha", "beta", "pre", "rc"];
+
+/// Suffixes sorting after a release, in order.
+const POST_SUFFIXES: &[&str] = &["cvs", "svn", "git", "hg", "p"];
+
+/// Alpine apk package version, ordered like `apk version -t`.
+///
+/// Versions are compared token by token. Numbers are compared numerically, except for numbers
+/// after a dot with a leading zero, which are compared as strings like fractions. The suffixes
+/// `_alpha`, `_beta`, `_pre` and `_rc` sort before a release, and `_cvs`, `_svn`, `_git`, `_hg`
+/// and `_p` after it, in that order. The package revision `-rN` is compared last.
+///
+/// # Examples
+///
+/// ```
+/// use version_compare::ApkVersion;
+///
+/// let a = ApkVersion::parse("1.2.3_rc1-r2").unwrap();
+/// let b = ApkVersion::parse("1.2.3-r0").unwrap();
+/// let c = ApkVersion::parse("1.2.3_p1-r0").unwrap();
+///
+/// assert_eq!(a.version(), "1.2.3_rc1");
+/// assert_eq!(a.revision(), Some(2.into()));
+/// assert!(a < b);
+/// assert!(b < c);
+/// ```
+#[derive(Debug, Clone)]
+pub struct ApkVersion<'a> {
+    version: &'a str,
+    tokens: Vec<Token<'a>>,
+}
+
+/// Version token kinds, in the order they may appear.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+enum Kind {
+    InitialDigit,
+    Digit,
+    Letter,
+    Suffix,
+    SuffixNumber,
+    CommitHash,
+    Revision,
+    End,
+}
+
+/// A single version token, such as a number or a suffix.
+#[derive(Debug, Clone, Copy)]
+struct Token<'a> {
+    kind: Kind,
+    value: &'a str,
+}
+
+impl<'a> ApkVersion<'a> {
+    /// Parse an apk version string.
+    ///
+    /// An error is returned for anything `apk` considers invalid, such as an unknown suffix, a
+    /// missing number, more than one letter, or a revision without `-r`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use version_compare::{ApkVersion, Error};
+    ///
+    /// assert!(ApkVersion::parse("1.0_git20240101~1a2b3c-r1").is_ok());
+    /// assert_eq!(ApkVersion::parse("1.0_dev").unwrap_err(), Error::UnexpectedChar { at: 4 });
+    /// assert_eq!(ApkVersion::parse("1.0-1").unwrap_err(), Error::UnexpectedChar { at: 4 });
+    /// assert_eq!(ApkVersion::parse("1..0").unwrap_err(), Error::EmptyPart { at: 2 });
+    /// ```
+    pub fn parse(version: &'a str) -> Result<Self, Error> {
+        let bytes = version.as_bytes();
+        let digits = |at: usize| {
+            let len = bytes[at..]
+                .iter()
+                .take_while(|c| c.is_ascii_digit())
+                .count();
+            if len == 0 {
+                return Err(Error::EmptyPart { at });
+            }
+            Ok(&version[at..at + len])
+        };
+
+        let mut tokens = vec![Token {
+            kind: Kind::InitialDigit,
+            value: digits(0)?,
+        }];
+        let mut at = tokens[0].value.len();
+        while at < bytes.len() {
+            let previous = tokens[tokens.len() - 1].kind;
+            let (kind, start) = match bytes[at] {
+                b'a'..=b'z' if previous <= Kind::Digit => (Kind::Letter, at),
+                b'.' if previous <= Kind::Digit => (Kind::Digit, at + 1),
+                b'_' if previous <= Kind::SuffixNumber => (Kind::Suffix, at + 1),
+                b'0'..=b'9' if previous == Kind::Suffix => (Kind::SuffixNumber, at),
+                b'~' if previous < Kind::CommitHash => (Kind::CommitHash, at + 1),
+                b'-' if previous < Kind::Revision && bytes.get(at + 1) == Some(&b'r') => {
+                    (Kind::Revision, at + 2)
+                }
+                b'-' if previous < Kind::Revision => {
+                    return Err(Error::UnexpectedChar { at: at + 1 })
+                }
+                _ => return Err(Error::UnexpectedChar { at }),
+            };
+
+            let value = match kind {
+                Kind::Letter => &version[at..at + 1],
+                Kind::Suffix => {
+                    let len = bytes[start..]
+                        .iter()
+                        .take_while(|c| c.is_ascii_lowercase())
+                        .count();
+                    let suffix = &version[start..start + len];
+                    if suffix.is_empty() {
+                        return Err(Error::EmptyPart { at: start });
+                    }
+                    if suffix_rank(suffix).is_none() {
+                        return Err(Error::UnexpectedChar { at: start });
+                    }
+                    suffix
+                }
+                Kind::CommitHash => {
+                    let len = bytes[start..]
+                        .iter()
+                        .take_while(|c| c.is_ascii_hexdigit() && !c.is_ascii_uppercase())
+                        .count();
+                    if len == 0 {
+                        return Err(Error::EmptyPart { at: start });
+                    }
+                    &version[start..start + len]
+                }
+                _ => digits(start)?,
+            };
+            tokens.push(Token { kind, value });
+            at = start + value.len();
+        }
+
+        Ok(ApkVersion { version, tokens })
+    }
+
+    /// Get the version without the package revision.
+    pub fn version(&self) -> &'a str {
+        match self.tokens.last() {
+            Some(token) if token.kind == Kind::Revision => {
+                &self.version[..self.version.len() - token.value.len() - 2]
+            }
+            _ => self.version,
+        }
+    }
+
+    /// Get the package revision, the number after `-r`, if any.
+    pub fn revision(&self) -> Option<Number<'a>> {
+        self.tokens
+            .last()
+            .filter(|token| token.kind == Kind::Revision)
+            .and_then(|token| Number::parse(token.value))
+    }
+
+    /// Get the full version string.
+    pub fn as_str(&self) -> &'a str {
+        self.version
+    }
+}
+
+impl<'a> Ord for ApkVersion<'a> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        let mut i = 0;
+        let (a, b) = loop {
+            let (a, b) = (self.tokens.get(i), other.tokens.get(i));
+            match (a, b) {
+                (Some(a), Some(b)) if a.kind == b.kind => {
+                    let ord = a.cmp_value(b);
+                    if ord != Ordering::Equal {
+                        return ord;
+                    }
+                }
+                _ => break (a, b),
+            }
+            i += 1;
+        };
+
+        // With all leading tokens equal, the longer version is greater, unless it continues
+        // with a pre-release suffix
+        let kind = |token: Option<&Token>| token.map_or(Kind::End, |token| token.kind);
+        if kind(a) == kind(b) {
+            return Ordering::Equal;
+        }
+        if a.map_or(false, Token::is_pre_suffix) {
+            return Ordering::Less;
+        }
+        if b.map_or(false, Token::is_pre_suffix) {
+            return Ordering::Greater;
+        }
+        kind(b).cmp(&kind(a))
+    }
+}
+
+impl<'a> PartialOrd for ApkVersion<'a> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<'a> PartialEq for ApkVersion<'a> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl<'a> Eq for ApkVersion<'a> {}
+
+/// Numbers are hashed without leading zeros, and suffixes by rank.
+impl<'a> Hash for ApkVersion<'a> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.tokens.len().hash(state);
+        for token in &self.tokens {
+            token.kind.hash(state);
+            match token.kind {
+                Kind::Suffix => suffix_rank(token.value).hash(state),
+                Kind::InitialDigit | Kind::SuffixNumber | Kind::Revision => {
+                    token.value.trim_start_matches('0').hash(state)
+                }
+                _ => token.value.hash(state),
+            }
+        }
+    }
+}
+
+impl<'a> fmt::Display for ApkVersion<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.version)
+    }
+}
+
+impl<'a> Token<'a> {
+    /// Compare the value of this token to another token of the same kind.
+    fn cmp_value(&self, other: &Self) -> Ordering {
+        match self.kind {
+            // Numbers after a dot with a leading zero are compared as strings, like fractions
+            Kind::Digit if self.value.starts_with('0') || other.value.starts_with('0') => {
+                self.value.cmp(other.value)
+            }
+            Kind::InitialDigit | Kind::Digit | Kind::SuffixNumber | Kind::Revision => {
+                let (a, b) = (
+                    self.value.trim_start_matches('0'),
+                    other.value.trim_start_matches('0'),
+                );
+                a.len().cmp(&b.len()).then_with(|| a.cmp(b))
+            }
+            Kind::Suffix => suffix_rank(self.value).cmp(&suffix_rank(other.value)),
+            _ => self.value.cmp(other.value),
+        }
+    }
+
+    /// Check whether this token is a suffix sorting before a release.
+    fn is_pre_suffix(&self) -> bool {
+        self.kind == Kind::Suffix && PRE_SUFFIXES.contains(&self.value)
+    }
+}
+
+/// Get the rank of a suffix, pre-release suffixes rank below zero.
+fn suffix_rank(suffix: &str) -> Option<isize> {
+    if let Some(i) = PRE_SUFFIXES.iter().position(|s| *s == suffix) {
+        return Some(i as isize - PRE_SUFFIXES.len() as isize);
+    }
+    POST_SUFFIXES
+        .iter()
+        .position(|s| *s == suffix)
+        .map(|i| i as isize + 1)
+}
+
+#[cfg_attr(tarpaulin, skip)]
+#[cfg(test)]
+mod tests {
+
+    use crate::test::hash_of;
+    use crate::{Cmp, Error};
+
+    use super::ApkVersion;
+
+    /// Version comparisons, as `apk version -t` reports them.
+    const APK_VERSION_TEST: &[(&str, &str, Cmp)] = &[
+        ("1.0", "1.0", Cmp::Eq),
+        ("1.0", "1.0.1", Cmp::Lt),
+        ("2.0", "1.9.9", Cmp::Gt),
+        ("1.0.0", "1.0", Cmp::Gt),
+        ("01.0", "1.0", Cmp::Eq),
+        ("1.01", "1.1", Cmp::Lt),
+        ("1.05", "1.5", Cmp::Lt),
+        ("1.10", "1.9", Cmp::Gt),
+        ("1.010", "1.01", Cmp::Gt),
+        ("1.0a", "1.0b", Cmp::Lt),
+        ("1.0a", "1.0", Cmp::Gt),
+        ("1.0z", "1.0.1", Cmp::Lt),
+        ("1.0_alpha", "1.0", Cmp::Lt),
+        ("1.0_alpha", "1.0_beta", Cmp::Lt),
+        ("1.0_beta", "1.0_pre", Cmp::Lt),
+        ("1.0_pre", "1.0_rc", Cmp::Lt),
+        ("1.0_rc", "1.0_rc1", Cmp::Lt),
+        ("1.0_rc1", "1.0_rc2", Cmp::Lt),
+        ("1.0_rc10", "1.0_rc9", Cmp::Gt),
+        ("1.0_rc1", "1.0", Cmp::Lt),
+        ("1.0", "1.0_cvs", Cmp::Lt),
+        ("1.0_cvs", "1.0_svn", Cmp::Lt),
+        ("1.0_svn", "1.0_git", Cmp::Lt),
+        ("1.0_git", "1.0_hg", Cmp::Lt),
+        ("1.0_hg", "1.0_p", Cmp::Lt),
+        ("1.0_p1", "1.0_p2", Cmp::Lt),
+        ("1.0_p1", "1.0.1", Cmp::Lt),
+        ("1.0_alpha", "1.0_p", Cmp::Lt),
+        ("1.0_alpha1", "1.0_alpha", Cmp::Gt),
+        ("1.0_alpha_pre", "1.0_alpha", Cmp::Lt),
+        ("1.0_p1_p1", "1.0_p1", Cmp::Gt),
+        ("1.0_git20240101", "1.0_git20240102", Cmp::Lt),
+        ("1.0-r1", "1.0-r2", Cmp::Lt),
+        ("1.0-r10", "1.0-r9", Cmp::Gt),
+        ("1.0", "1.0-r0", Cmp::Lt),
+        ("1.0-r0", "1.0-r00", Cmp::Eq),
+        ("1.0_rc1-r1", "1.0-r0", Cmp::Lt),
+        ("1.0_p1-r0", "1.0-r5", Cmp::Gt),
+        ("1.0.1-r0", "1.0-r5", Cmp::Gt),
+        ("1.0~abc", "1.0~abd", Cmp::Lt),
+        ("1.0~abc", "1.0", Cmp::Gt),
+        ("1.0~abc-r1", "1.0-r1", Cmp::Gt),
+        ("1.0_p1~abc", "1.0_p1", Cmp::Gt),
+    ];
+
+    #[test]
+    fn parse() {
+        let version = ApkVersion::parse("1.2.3b_rc1_p2~f00d-r10").unwrap();
+        assert_eq!(version.version(), "1.2.3b_rc1_p2~f00d");
+        assert_eq!(version.revision(), Some(10.into()));
+        assert_eq!(version.as_str(), "1.2.3b_rc1_p2~f00d-r10");
+
+        let version = ApkVersion::parse("20240101").unwrap();
+        assert_eq!(version.version(), "20240101");
+        assert_eq!(version.revision(), None);
+    }
+
+    #[test]
+    fn parse_error() {
+        let errors = [
+            ("", Error::EmptyPart { at: 0 }),
+            ("a1.0", Error::EmptyPart { at: 0 }),
+            ("1.", Error::EmptyPart { at: 2 }),
+            ("1..0", Error::EmptyPart { at: 2 }),
+            ("1.0ab", Error::UnexpectedChar { at: 4 }),
+            ("1.0a1", Error::UnexpectedChar { at: 4 }),
+            ("1.0a.1", Error::UnexpectedChar { at: 4 }),
+            ("1.0A", Error::UnexpectedChar { at: 3 }),
+            ("1.0_", Error::EmptyPart { at: 4 }),
+            ("1.0_dev1", Error::UnexpectedChar { at: 4 }),
+            ("1.0_alpha.1", Error::UnexpectedChar { at: 9 }),
+            ("1.0-1", Error::UnexpectedChar { at: 4 }),
+            ("1.0-r", Error::EmptyPart { at: 5 }),
+            ("1.0-r1-r1", Error::UnexpectedChar { at: 6 }),
+            ("1.0-r1_p1", Error::UnexpectedChar { at: 6 }),
+            ("1.0~", Error::EmptyPart { at: 4 }),
+            ("1.0~abc~abc", Error::UnexpectedChar { at: 7 }),
+            ("1.0 ", Error::UnexpectedChar { at: 3 }),
+        ];
+        for (version, err) in &errors {
+            assert_eq!(
+                ApkVersion::parse(version).unwrap_err(),
+                *err,
+                "Parsing {:?}",
+                version,
+            );
+        }
+    }
+
+    #[test]
+    fn compare() {
+        for (a, b, cmp) in APK_VERSION_TEST {
+            let (a, b) = (ApkVersion::parse(a).unwrap(), ApkVersion::parse(b).unwrap());
+            assert_eq!(Cmp::from(a.cmp(&b)), *cmp, "Comparing {} to {}", a, b);
+            assert_eq!(Cmp::from(b.cmp(&a)), cmp.flip(), "Comparing {} to {}", b, a);
+        }
+    }
+
+    #[test]
+    fn hash_eq() {
+        for (a, b, cmp) in APK_VERSION_TEST {
+            if *cmp == Cmp::Eq {
+                let (a, b) = (ApkVersion::parse(a).unwrap(), ApkVersion::parse(b).unwrap());
+                assert_eq!(hash_of(&a), hash_of(&b), "Hashing {} and {}", a, b);
+            }
+        }
+    }
+}
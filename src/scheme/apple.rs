@@ -0,0 +1,271 @@
+//! Apple version scheme, which provides the `AppleVersion` struct.
+//!
+//! Apple bundles carry a marketing version in `CFBundleShortVersionString`, such as `2.1.0`, and
+//! a build version in `CFBundleVersion`, such as `145`. Both are one to three period-separated
+//! integers, as validated by App Store Connect.
+
+use std::cmp::Ordering;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+
+use crate::Error;
+
+/// Maximum number of integers in a version.
+const MAX_NUMBERS: usize = 3;
+
+/// Apple bundle version, as used for `CFBundleShortVersionString` and `CFBundleVersion`.
+///
+/// A version is one to three period-separated non-negative integers, such as `1`, `1.2` or
+/// `1.2.3`. Anything else, such as whitespace, letters, signs, empty components or a fourth
+/// integer, is rejected like App Store Connect does.
+///
+/// Versions are ordered by their integers, missing integers being zero, so `1.2` equals `1.2.0`.
+/// Leading zeros are insignificant.
+///
+/// # Examples
+///
+/// ```
+/// use version_compare::AppleVersion;
+///
+/// let a = AppleVersion::parse("1.9").unwrap();
+/// let b = AppleVersion::parse("1.10.0").unwrap();
+///
+/// assert!(a < b);
+/// assert_eq!(a, AppleVersion::parse("1.9.0").unwrap());
+/// assert_eq!(b.minor(), 10);
+/// ```
+#[derive(Debug, Clone)]
+pub struct AppleVersion<'a> {
+    version: &'a str,
+    numbers: [u64; MAX_NUMBERS],
+}
+
+impl<'a> AppleVersion<'a> {
+    /// Parse an Apple bundle version string.
+    ///
+    /// Returns the first problem found, use `diagnose` to get all of them.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use version_compare::{AppleVersion, Error};
+    ///
+    /// assert!(AppleVersion::parse("2.1.0").is_ok());
+    /// assert_eq!(AppleVersion::parse("2.1.0b1").unwrap_err(), Error::UnexpectedChar { at: 5 });
+    /// assert_eq!(AppleVersion::parse("2.1.0.1").unwrap_err(), Error::TooManyParts { at: 5 });
+    /// ```
+    pub fn parse(version: &'a str) -> Result<Self, Error> {
+        let mut numbers = [0; MAX_NUMBERS];
+        match check(version, &mut numbers).into_iter().next() {
+            Some(err) => Err(err),
+            None => Ok(AppleVersion { version, numbers }),
+        }
+    }
+
+    /// Get all problems of the given version string, for reporting why it's rejected.
+    ///
+    /// Problems are ordered by their byte offset, and an empty list means the version is valid.
+    /// Each problem describes itself through its `Display` implementation.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use version_compare::{AppleVersion, Error};
+    ///
+    /// assert!(AppleVersion::diagnose("1.0.2").is_empty());
+    /// assert_eq!(
+    ///     AppleVersion::diagnose("1..2-beta"),
+    ///     vec![Error::EmptyPart { at: 2 }, Error::UnexpectedChar { at: 4 }],
+    /// );
+    /// assert_eq!(
+    ///     AppleVersion::diagnose("v1")[0].to_string(),
+    ///     "unexpected character at byte 0",
+    /// );
+    /// ```
+    pub fn diagnose(version: &str) -> Vec<Error> {
+        check(version, &mut [0; MAX_NUMBERS])
+    }
+
+    /// Get the first integer, the major version.
+    pub fn major(&self) -> u64 {
+        self.numbers[0]
+    }
+
+    /// Get the second integer, the minor version, zero if missing.
+    pub fn minor(&self) -> u64 {
+        self.numbers[1]
+    }
+
+    /// Get the third integer, the patch version, zero if missing.
+    pub fn patch(&self) -> u64 {
+        self.numbers[2]
+    }
+
+    /// Get the version string.
+    pub fn as_str(&self) -> &'a str {
+        self.version
+    }
+}
+
+impl<'a> Ord for AppleVersion<'a> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.numbers.cmp(&other.numbers)
+    }
+}
+
+impl<'a> PartialOrd for AppleVersion<'a> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<'a> PartialEq for AppleVersion<'a> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl<'a> Eq for AppleVersion<'a> {}
+
+impl<'a> Hash for AppleVersion<'a> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.numbers.hash(state);
+    }
+}
+
+impl<'a> fmt::Display for AppleVersion<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.version)
+    }
+}
+
+/// Check the given version string, parsing its integers into `numbers`, and return all problems.
+fn check(version: &str, numbers: &mut [u64; MAX_NUMBERS]) -> Vec<Error> {
+    let mut errors = Vec::new();
+    let mut at = 0;
+    for (i, component) in version.split('.').enumerate() {
+        if i == MAX_NUMBERS {
+            errors.push(Error::TooManyParts { at: at - 1 });
+            break;
+        }
+        if component.is_empty() {
+            errors.push(Error::EmptyPart { at });
+        } else if let Some(j) = component.find(|c: char| !c.is_ascii_digit()) {
+            errors.push(Error::UnexpectedChar { at: at + j });
+        } else {
+            match component.parse() {
+                Ok(n) => numbers[i] = n,
+                Err(_) => errors.push(Error::NumberOverflow { at }),
+            }
+        }
+        at += component.len() + 1;
+    }
+    errors
+}
+
+#[cfg_attr(tarpaulin, skip)]
+#[cfg(test)]
+mod tests {
+
+    use crate::test::hash_of;
+    use crate::{Cmp, Error};
+
+    use super::AppleVersion;
+
+    /// Version comparisons.
+    const COMPARE: &[(&str, &str, Cmp)] = &[
+        ("1", "1.0", Cmp::Eq),
+        ("1.0", "1.0.0", Cmp::Eq),
+        ("1.01", "1.1", Cmp::Eq),
+        ("1.2", "1.10", Cmp::Lt),
+        ("1.9.9", "1.10", Cmp::Lt),
+        ("2", "1.99.99", Cmp::Gt),
+        ("1.0.1", "1.0", Cmp::Gt),
+        ("0.0.1", "0", Cmp::Gt),
+        ("202401011230", "202312312359", Cmp::Gt),
+    ];
+
+    #[test]
+    fn parse() {
+        let version = AppleVersion::parse("10.14.1").unwrap();
+        assert_eq!(version.major(), 10);
+        assert_eq!(version.minor(), 14);
+        assert_eq!(version.patch(), 1);
+        assert_eq!(version.as_str(), "10.14.1");
+
+        let version = AppleVersion::parse("145").unwrap();
+        assert_eq!(version.major(), 145);
+        assert_eq!(version.minor(), 0);
+        assert_eq!(version.patch(), 0);
+    }
+
+    #[test]
+    fn parse_error() {
+        let errors = [
+            ("", Error::EmptyPart { at: 0 }),
+            (".1", Error::EmptyPart { at: 0 }),
+            ("1.", Error::EmptyPart { at: 2 }),
+            ("1..2", Error::EmptyPart { at: 2 }),
+            ("1.2.3.4", Error::TooManyParts { at: 5 }),
+            ("1.2.3.", Error::TooManyParts { at: 5 }),
+            ("v1.0", Error::UnexpectedChar { at: 0 }),
+            ("1.0b1", Error::UnexpectedChar { at: 3 }),
+            ("1.0-1", Error::UnexpectedChar { at: 3 }),
+            ("1,0", Error::UnexpectedChar { at: 1 }),
+            (" 1.0", Error::UnexpectedChar { at: 0 }),
+            ("1.0 ", Error::UnexpectedChar { at: 3 }),
+            ("+1", Error::UnexpectedChar { at: 0 }),
+            ("1.٣", Error::UnexpectedChar { at: 2 }),
+            ("1.18446744073709551616", Error::NumberOverflow { at: 2 }),
+        ];
+        for (version, err) in &errors {
+            assert_eq!(
+                AppleVersion::parse(version).unwrap_err(),
+                *err,
+                "Parsing {:?}",
+                version,
+            );
+        }
+    }
+
+    #[test]
+    fn diagnose() {
+        assert!(AppleVersion::diagnose("1.2.3").is_empty());
+        assert_eq!(
+            AppleVersion::diagnose("1.x.3.4"),
+            vec![
+                Error::UnexpectedChar { at: 2 },
+                Error::TooManyParts { at: 5 }
+            ],
+        );
+        assert_eq!(
+            AppleVersion::diagnose("1.2.3.4")[0].to_string(),
+            "too many version components at byte 5",
+        );
+    }
+
+    #[test]
+    fn compare() {
+        for (a, b, cmp) in COMPARE {
+            let (a, b) = (
+                AppleVersion::parse(a).unwrap(),
+                AppleVersion::parse(b).unwrap(),
+            );
+            assert_eq!(Cmp::from(a.cmp(&b)), *cmp, "Comparing {} to {}", a, b);
+            assert_eq!(Cmp::from(b.cmp(&a)), cmp.flip(), "Comparing {} to {}", b, a);
+        }
+    }
+
+    #[test]
+    fn hash_eq() {
+        for (a, b, cmp) in COMPARE {
+            if *cmp == Cmp::Eq {
+                let (a, b) = (
+                    AppleVersion::parse(a).unwrap(),
+                    AppleVersion::parse(b).unwrap(),
+                );
+                assert_eq!(hash_of(&a), hash_of(&b), "Hashing {} and {}", a, b);
+            }
+        }
+    }
+}
@@ -0,0 +1,524 @@
+//! Arch Linux version scheme, which provides the `ArchVersion` struct.
+//!
+//! Arch Linux package versions have the form `[epoch:]pkgver[-pkgrel]`, and are ordered like
+//! pacman's `vercmp`, using the `rpmvercmp` variant from libalpm.
+
+use std::cmp::Ordering;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+
+use crate::Error;
+
+/// Arch Linux package version, ordered like pacman's `vercmp`.
+///
+/// The epoch is compared first, then the pkgver and pkgrel. Alphabetic segments are older than
+/// numeric ones, so `1.0rc1` is less than `1.0`.
+///
+/// `vercmp` only compares the pkgrel if both versions have one, so `1.0` equals both `1.0-1` and
+/// `1.0-2`. To keep the order transitive, a missing pkgrel is less than any pkgrel instead, use
+/// `vercmp` for pacman's behavior.
+///
+/// The order is only total if segments are separated by single characters. Like pacman, longer
+/// runs of separators win over shorter ones, except against the end of a version, so `2.` <
+/// `2+2p` < `2__r` but `2.` > `2__r`.
+///
+/// # Examples
+///
+/// ```
+/// use version_compare::ArchVersion;
+///
+/// let a = ArchVersion::parse("1:2.30-1.1").unwrap();
+/// let b = ArchVersion::parse("2.31rc1-1").unwrap();
+///
+/// assert_eq!(a.epoch(), 1);
+/// assert_eq!(a.pkgver(), "2.30");
+/// assert_eq!(a.pkgrel(), Some("1.1"));
+/// assert!(a > b);
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct ArchVersion<'a> {
+    version: &'a str,
+    epoch: u32,
+    pkgver: &'a str,
+    pkgrel: Option<&'a str>,
+}
+
+impl<'a> ArchVersion<'a> {
+    /// Parse an Arch Linux version string in `[epoch:]pkgver[-pkgrel]` form.
+    ///
+    /// The epoch is split off at a colon following only digits, and the pkgrel at the last
+    /// hyphen, as libalpm does. An error is returned for what makepkg rejects: a pkgver that is
+    /// empty or contains a colon, hyphen, slash or whitespace, and a pkgrel that isn't an integer
+    /// with an optional fractional part.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use version_compare::{ArchVersion, Error};
+    ///
+    /// assert_eq!(ArchVersion::parse("0:1.0-1").unwrap().pkgver(), "1.0");
+    /// assert_eq!(ArchVersion::parse("1.0-1a").unwrap_err(), Error::UnexpectedChar { at: 5 });
+    /// assert_eq!(ArchVersion::parse("1:-1").unwrap_err(), Error::EmptyPart { at: 2 });
+    /// ```
+    pub fn parse(version: &'a str) -> Result<Self, Error> {
+        // The epoch ends at a colon, following nothing but digits
+        let digits = version.bytes().take_while(u8::is_ascii_digit).count();
+        let (epoch, pkgver_at) = if version[digits..].starts_with(':') {
+            match &version[..digits] {
+                "" => (0, digits + 1),
+                epoch => match epoch.parse() {
+                    Ok(epoch) => (epoch, digits + 1),
+                    Err(_) => return Err(Error::NumberOverflow { at: 0 }),
+                },
+            }
+        } else {
+            (0, 0)
+        };
+
+        // The pkgrel starts after the last hyphen
+        let rest = &version[pkgver_at..];
+        let (pkgver, pkgrel) = match rest.rfind('-') {
+            Some(hyphen) => (&rest[..hyphen], Some(&rest[hyphen + 1..])),
+            None => (rest, None),
+        };
+        if pkgver.is_empty() {
+            return Err(Error::EmptyPart { at: pkgver_at });
+        }
+        if let Some(at) = pkgver.find(|c: char| c.is_whitespace() || ":-/".contains(c)) {
+            return Err(Error::UnexpectedChar { at: pkgver_at + at });
+        }
+        if let Some(pkgrel) = pkgrel {
+            validate_pkgrel(pkgrel, pkgver_at + pkgver.len() + 1)?;
+        }
+
+        Ok(ArchVersion {
+            version,
+            epoch,
+            pkgver,
+            pkgrel,
+        })
+    }
+
+    /// Get the epoch, `0` if the version has none.
+    pub fn epoch(&self) -> u32 {
+        self.epoch
+    }
+
+    /// Get the pkgver, without epoch and pkgrel.
+    pub fn pkgver(&self) -> &'a str {
+        self.pkgver
+    }
+
+    /// Get the pkgrel, if any.
+    pub fn pkgrel(&self) -> Option<&'a str> {
+        self.pkgrel
+    }
+
+    /// Get the full version string.
+    pub fn as_str(&self) -> &'a str {
+        self.version
+    }
+
+    /// Compare to another version exactly like pacman's `vercmp`.
+    ///
+    /// Unlike `Ord`, the pkgrel is ignored if either version has none. This isn't a total order,
+    /// so don't use it for sorting.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::cmp::Ordering;
+    /// use version_compare::ArchVersion;
+    ///
+    /// let a = ArchVersion::parse("1.0").unwrap();
+    /// let b = ArchVersion::parse("1.0-2").unwrap();
+    ///
+    /// assert_eq!(a.vercmp(&b), Ordering::Equal);
+    /// assert!(a < b);
+    /// ```
+    pub fn vercmp(&self, other: &ArchVersion) -> Ordering {
+        self.epoch
+            .cmp(&other.epoch)
+            .then_with(|| alpm_vercmp(self.pkgver, other.pkgver))
+            .then_with(|| match (self.pkgrel, other.pkgrel) {
+                (Some(a), Some(b)) => alpm_vercmp(a, b),
+                _ => Ordering::Equal,
+            })
+    }
+}
+
+/// Order like `vercmp`, except for a missing pkgrel, total only for single-character separators.
+impl<'a> Ord for ArchVersion<'a> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.epoch
+            .cmp(&other.epoch)
+            .then_with(|| alpm_vercmp(self.pkgver, other.pkgver))
+            .then_with(|| match (self.pkgrel, other.pkgrel) {
+                (Some(a), Some(b)) => alpm_vercmp(a, b),
+                (a, b) => a.is_some().cmp(&b.is_some()),
+            })
+    }
+}
+
+impl<'a> PartialOrd for ArchVersion<'a> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<'a> PartialEq for ArchVersion<'a> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl<'a> Eq for ArchVersion<'a> {}
+
+/// A missing pkgrel hashes unlike any pkgrel, matching `Ord` rather than `vercmp`.
+impl<'a> Hash for ArchVersion<'a> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.epoch.hash(state);
+        hash_segments(self.pkgver, state);
+        self.pkgrel.is_some().hash(state);
+        if let Some(pkgrel) = self.pkgrel {
+            hash_segments(pkgrel, state);
+        }
+    }
+}
+
+impl<'a> fmt::Display for ArchVersion<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.version)
+    }
+}
+
+/// Validate a pkgrel at the given offset, an integer with an optional fractional part.
+fn validate_pkgrel(pkgrel: &str, at: usize) -> Result<(), Error> {
+    let integer = pkgrel.bytes().take_while(u8::is_ascii_digit).count();
+    if integer == 0 {
+        if pkgrel.is_empty() {
+            return Err(Error::EmptyPart { at });
+        }
+        return Err(Error::UnexpectedChar { at });
+    }
+    let rest = &pkgrel[integer..];
+    if rest.is_empty() {
+        return Ok(());
+    }
+    if !rest.starts_with('.') {
+        return Err(Error::UnexpectedChar { at: at + integer });
+    }
+    let fraction = rest[1..].bytes().take_while(u8::is_ascii_digit).count();
+    if fraction == 0 && rest.len() == 1 {
+        return Err(Error::EmptyPart {
+            at: at + integer + 1,
+        });
+    }
+    if fraction + 1 < rest.len() {
+        return Err(Error::UnexpectedChar {
+            at: at + integer + 1 + fraction,
+        });
+    }
+    Ok(())
+}
+
+/// Get the alphabetic or numeric segment at the start of the given string.
+fn segment(s: &[u8], numeric: bool) -> &[u8] {
+    let len = s
+        .iter()
+        .take_while(|c| {
+            if numeric {
+                c.is_ascii_digit()
+            } else {
+                c.is_ascii_alphabetic()
+            }
+        })
+        .count();
+    &s[..len]
+}
+
+/// Trim leading zeros from a numeric segment.
+fn trim_zeros(digits: &[u8]) -> &[u8] {
+    let zeros = digits.iter().take_while(|&&c| c == b'0').count();
+    &digits[zeros..]
+}
+
+/// Compare a pkgver or pkgrel, as libalpm's `rpmvercmp` does.
+///
+/// Unlike rpm, there is no special handling of `~` and `^`, and the lengths of separators
+/// between segments are compared.
+fn alpm_vercmp(a: &str, b: &str) -> Ordering {
+    if a == b {
+        return Ordering::Equal;
+    }
+
+    let (a, b) = (a.as_bytes(), b.as_bytes());
+    let (mut i, mut j) = (0, 0);
+    while i < a.len() && j < b.len() {
+        let (start_i, start_j) = (i, j);
+        while i < a.len() && !a[i].is_ascii_alphanumeric() {
+            i += 1;
+        }
+        while j < b.len() && !b[j].is_ascii_alphanumeric() {
+            j += 1;
+        }
+        if i == a.len() || j == b.len() {
+            break;
+        }
+
+        // Separators of different lengths, the longer one wins
+        if i - start_i != j - start_j {
+            return (i - start_i).cmp(&(j - start_j));
+        }
+
+        let numeric = a[i].is_ascii_digit();
+        let (one, two) = (segment(&a[i..], numeric), segment(&b[j..], numeric));
+        i += one.len();
+        j += two.len();
+
+        // Segments of different types, numeric segments are newer than alphabetic ones
+        if two.is_empty() {
+            return if numeric {
+                Ordering::Greater
+            } else {
+                Ordering::Less
+            };
+        }
+
+        let ord = if numeric {
+            let (one, two) = (trim_zeros(one), trim_zeros(two));
+            one.len().cmp(&two.len()).then_with(|| one.cmp(two))
+        } else {
+            one.cmp(two)
+        };
+        if ord != Ordering::Equal {
+            return ord;
+        }
+    }
+
+    // A remaining alphabetic segment never beats the end of a version
+    match (a.get(i), b.get(j)) {
+        (None, None) => Ordering::Equal,
+        (None, Some(c)) if !c.is_ascii_alphabetic() => Ordering::Less,
+        (Some(c), _) if c.is_ascii_alphabetic() => Ordering::Less,
+        _ => Ordering::Greater,
+    }
+}
+
+/// Hash a pkgver, consistent with `alpm_vercmp`.
+///
+/// Hashes each segment with the length of the separator before it, skipping leading zeros, and
+/// whether there are trailing separators, as their length doesn't matter.
+fn hash_segments<H: Hasher>(version: &str, state: &mut H) {
+    let mut rest = version.as_bytes();
+    loop {
+        let separator = rest
+            .iter()
+            .take_while(|c| !c.is_ascii_alphanumeric())
+            .count();
+        rest = &rest[separator..];
+        let c = match rest.first() {
+            Some(&c) => c,
+            None => {
+                (separator > 0).hash(state);
+                break;
+            }
+        };
+        let numeric = c.is_ascii_digit();
+        let token = segment(rest, numeric);
+        rest = &rest[token.len()..];
+
+        separator.hash(state);
+        numeric.hash(state);
+        if numeric {
+            trim_zeros(token).hash(state);
+        } else {
+            token.hash(state);
+        }
+    }
+}
+
+#[cfg_attr(tarpaulin, skip)]
+#[cfg(test)]
+mod tests {
+    use std::cmp::Ordering;
+    use std::collections::BTreeSet;
+
+    use crate::test::hash_of;
+    use crate::{Cmp, Error};
+
+    use super::ArchVersion;
+
+    /// Version comparisons, as pacman's `vercmp` reports them.
+    const VERCMP: &[(&str, &str, Cmp)] = &[
+        // Similar length, no pkgrel
+        ("1.5.0", "1.5.0", Cmp::Eq),
+        ("1.5.1", "1.5.0", Cmp::Gt),
+        ("1.5.1", "1.5", Cmp::Gt),
+        // With pkgrel
+        ("1.5.0-1", "1.5.0-1", Cmp::Eq),
+        ("1.5.0-1", "1.5.0-2", Cmp::Lt),
+        ("1.5.0-1", "1.5.1-1", Cmp::Lt),
+        ("1.5.0-2", "1.5.1-1", Cmp::Lt),
+        ("1.5-1", "1.5.1-1", Cmp::Lt),
+        ("1.5-2", "1.5.1-1", Cmp::Lt),
+        ("1.5-2", "1.5.1-2", Cmp::Lt),
+        // Mixed pkgrel inclusion
+        ("1.0-1", "1.1", Cmp::Lt),
+        ("1.1-1", "1.0", Cmp::Gt),
+        // Fractional pkgrel
+        ("1.0-1", "1.0-1.1", Cmp::Lt),
+        ("1.0-1.1", "1.0-2", Cmp::Lt),
+        ("1.0-1.10", "1.0-1.9", Cmp::Gt),
+        ("1.0-01", "1.0-1", Cmp::Eq),
+        // Alphanumeric versions
+        ("1.5b-1", "1.5-1", Cmp::Lt),
+        ("1.5b", "1.5", Cmp::Lt),
+        ("1.5b-1", "1.5", Cmp::Lt),
+        ("1.5b", "1.5.1", Cmp::Lt),
+        ("1.0a", "1.0alpha", Cmp::Lt),
+        ("1.0alpha", "1.0b", Cmp::Lt),
+        ("1.0b", "1.0beta", Cmp::Lt),
+        ("1.0beta", "1.0rc", Cmp::Lt),
+        ("1.0rc", "1.0", Cmp::Lt),
+        // Alphabetic segments after dots
+        ("1.5.a", "1.5", Cmp::Gt),
+        ("1.5.b", "1.5.a", Cmp::Gt),
+        ("1.5.1", "1.5.b", Cmp::Gt),
+        ("1.5-1", "1.5.b", Cmp::Lt),
+        // Differing separators
+        ("2.0", "2_0", Cmp::Eq),
+        ("2.0_a", "2_0.a", Cmp::Eq),
+        ("2.0a", "2.0.a", Cmp::Lt),
+        ("2___a", "2_a", Cmp::Gt),
+        ("1.0.", "1.0_", Cmp::Eq),
+        ("1.0.", "1.0", Cmp::Gt),
+        // Epochs
+        ("0:1.0", "0:1.0", Cmp::Eq),
+        ("0:1.0", "0:1.1", Cmp::Lt),
+        ("1:1.0", "0:1.0", Cmp::Gt),
+        ("1:1.0", "0:1.1", Cmp::Gt),
+        ("1:1.0", "2:1.1", Cmp::Lt),
+        ("1:1.0", "0:1.0-1", Cmp::Gt),
+        ("1:1.0-1", "0:1.1-1", Cmp::Gt),
+        ("0:1.0", "1.0", Cmp::Eq),
+        ("0:1.0", "1.1", Cmp::Lt),
+        ("0:1.1", "1.0", Cmp::Gt),
+        ("1:1.0", "1.0", Cmp::Gt),
+        ("1:1.0", "1.1", Cmp::Gt),
+        ("1:1.1", "1.1", Cmp::Gt),
+    ];
+
+    /// Versions without and with pkgrel, which `vercmp` reports equal.
+    const MISSING_PKGREL: &[(&str, &str)] = &[
+        ("1.5", "1.5-1"),
+        ("1.1", "1.1-1"),
+        ("1.5.b", "1.5.b-1"),
+        ("1:1.0", "1:1.0-0"),
+    ];
+
+    #[test]
+    fn parse() {
+        let version = ArchVersion::parse("2:1.0rc1+r12.g1a2b3c-3.1").unwrap();
+        assert_eq!(version.epoch(), 2);
+        assert_eq!(version.pkgver(), "1.0rc1+r12.g1a2b3c");
+        assert_eq!(version.pkgrel(), Some("3.1"));
+        assert_eq!(version.as_str(), "2:1.0rc1+r12.g1a2b3c-3.1");
+
+        let version = ArchVersion::parse(":20240101").unwrap();
+        assert_eq!(version.epoch(), 0);
+        assert_eq!(version.pkgver(), "20240101");
+        assert_eq!(version.pkgrel(), None);
+    }
+
+    #[test]
+    fn parse_error() {
+        let errors = [
+            ("", Error::EmptyPart { at: 0 }),
+            ("1:", Error::EmptyPart { at: 2 }),
+            ("-1", Error::EmptyPart { at: 0 }),
+            ("1.0-", Error::EmptyPart { at: 4 }),
+            ("1.0-1.", Error::EmptyPart { at: 6 }),
+            ("1.0-a", Error::UnexpectedChar { at: 4 }),
+            ("1.0-1.1.1", Error::UnexpectedChar { at: 7 }),
+            ("1.0-1-1", Error::UnexpectedChar { at: 3 }),
+            ("1:2:3", Error::UnexpectedChar { at: 3 }),
+            ("1.0/2", Error::UnexpectedChar { at: 3 }),
+            ("1.0 beta", Error::UnexpectedChar { at: 3 }),
+            ("4294967296:1.0", Error::NumberOverflow { at: 0 }),
+        ];
+        for (version, err) in &errors {
+            assert_eq!(
+                ArchVersion::parse(version).unwrap_err(),
+                *err,
+                "Parsing {:?}",
+                version,
+            );
+        }
+    }
+
+    #[test]
+    fn compare() {
+        for (a, b, cmp) in VERCMP {
+            let (a, b) = (
+                ArchVersion::parse(a).unwrap(),
+                ArchVersion::parse(b).unwrap(),
+            );
+            assert_eq!(Cmp::from(a.cmp(&b)), *cmp, "Comparing {} to {}", a, b);
+            assert_eq!(Cmp::from(b.cmp(&a)), cmp.flip(), "Comparing {} to {}", b, a);
+        }
+    }
+
+    #[test]
+    fn vercmp() {
+        for (a, b, cmp) in VERCMP {
+            let (a, b) = (
+                ArchVersion::parse(a).unwrap(),
+                ArchVersion::parse(b).unwrap(),
+            );
+            assert_eq!(Cmp::from(a.vercmp(&b)), *cmp, "Comparing {} to {}", a, b);
+        }
+        for (a, b) in MISSING_PKGREL {
+            let (a, b) = (
+                ArchVersion::parse(a).unwrap(),
+                ArchVersion::parse(b).unwrap(),
+            );
+            assert_eq!(a.vercmp(&b), Ordering::Equal, "Comparing {} to {}", a, b);
+            assert_eq!(b.vercmp(&a), Ordering::Equal, "Comparing {} to {}", b, a);
+            assert!(a < b, "Comparing {} to {}", a, b);
+            assert_ne!(hash_of(&a), hash_of(&b), "Hashing {} and {}", a, b);
+        }
+    }
+
+    #[test]
+    fn transitive() {
+        let a = ArchVersion::parse("1.0").unwrap();
+        let b = ArchVersion::parse("1.0-1").unwrap();
+        let c = ArchVersion::parse("1.0-2").unwrap();
+        assert!(a < b && b < c && a < c);
+
+        let set: BTreeSet<_> = [c, a, b].iter().copied().collect();
+        let set: Vec<_> = set.iter().map(|v| v.as_str()).collect();
+        assert_eq!(set, ["1.0", "1.0-1", "1.0-2"]);
+
+        // Runs of separators aren't transitive, like in pacman
+        let a = ArchVersion::parse("2.").unwrap();
+        let b = ArchVersion::parse("2+2p").unwrap();
+        let c = ArchVersion::parse("2__r").unwrap();
+        assert!(a < b && b < c && a > c);
+        assert_eq!(a.vercmp(&c), Ordering::Greater);
+    }
+
+    #[test]
+    fn hash_eq() {
+        for (a, b, cmp) in VERCMP {
+            if *cmp == Cmp::Eq {
+                let (a, b) = (
+                    ArchVersion::parse(a).unwrap(),
+                    ArchVersion::parse(b).unwrap(),
+                );
+                assert_eq!(hash_of(&a), hash_of(&b), "Hashing {} and {}", a, b);
+            }
+        }
+    }
+}
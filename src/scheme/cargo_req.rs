@@ -0,0 +1,608 @@
+//! Cargo version requirement module, which provides the `CargoReq` struct.
+//!
+//! Cargo requirements are comma separated comparators, such as `^1.2.3`, `~1.2`, `1.*` or
+//! `>=1.2, <1.5`. A bare version such as `1.2` is a caret requirement.
+
+use std::cmp::Ordering;
+use std::fmt;
+use std::str::FromStr;
+
+use super::semver::{for_identifiers, numeric};
+use crate::{Cmp, Error, Manifest, Scheme, SemverVersion, Version, VersionBuf, VersionReq};
+
+/// Cargo version requirement, as used for dependencies in `Cargo.toml`.
+///
+/// A requirement is a comma separated list of comparators, and matches a version if all of them
+/// do. Comparators are versions with an operator: `=`, `>`, `>=`, `<`, `<=`, `~` or `^`, or with
+/// a `*` wildcard as the minor or patch version. A version without operator or wildcard is a
+/// caret requirement, and minor and patch versions may be omitted.
+///
+/// Like Cargo, a pre-release version only matches if a comparator has a pre-release on the same
+/// `major.minor.patch`.
+///
+/// # Examples
+///
+/// ```
+/// use version_compare::{CargoReq, Version};
+///
+/// let req = CargoReq::parse("1.2").unwrap();
+///
+/// assert!(req.matches(&Version::from("1.2.0").unwrap()));
+/// assert!(req.matches(&Version::from("1.9.3").unwrap()));
+/// assert!(!req.matches(&Version::from("2.0.0").unwrap()));
+/// assert!(!req.matches(&Version::from("1.3.0-beta").unwrap()));
+/// assert_eq!(req.to_string(), "^1.2");
+/// assert_eq!(req.bounds().to_string(), ">=1.2.0, <2.0.0");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CargoReq {
+    comparators: Vec<Comparator>,
+}
+
+/// A single requirement comparator, with missing components as `None`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Comparator {
+    operator: Operator,
+    major: u64,
+    minor: Option<u64>,
+    patch: Option<u64>,
+    pre: Option<String>,
+}
+
+/// Requirement comparator operator.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Operator {
+    /// Plain comparison operator.
+    Cmp(Cmp),
+
+    /// Tilde operator `~`, allowing patch level changes.
+    Tilde,
+
+    /// Caret operator `^`, allowing changes that keep the left-most non-zero component.
+    Caret,
+
+    /// Wildcard in the minor or patch version, such as `1.2.*`.
+    Wildcard,
+}
+
+/// Operator signs, longest first.
+const OPERATORS: &[&str] = &[">=", "<=", ">", "<", "=", "~", "^"];
+
+impl CargoReq {
+    /// Parse a Cargo version requirement, made of comma separated comparators.
+    ///
+    /// A single `*` matches any version. An error is returned for an empty requirement or
+    /// comparator, an unknown operator, a leading zero or an invalid pre-release, like Cargo.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use version_compare::{CargoReq, Error};
+    ///
+    /// assert!(CargoReq::parse(">=1.2, <1.5").is_ok());
+    /// assert!(CargoReq::parse("~1.2.3-beta.2").is_ok());
+    /// assert_eq!(CargoReq::parse("^1.02").unwrap_err(), Error::LeadingZero { at: 3 });
+    /// assert_eq!(CargoReq::parse("1.2, ").unwrap_err(), Error::EmptyClause { at: 3 });
+    /// assert_eq!(CargoReq::parse("~> 1.2").unwrap_err(), Error::UnexpectedChar { at: 1 });
+    /// ```
+    pub fn parse(req: &str) -> Result<Self, Error> {
+        if req.trim().is_empty() {
+            return Err(Error::MissingVersion { at: req.len() });
+        }
+
+        let mut comparators = Vec::new();
+        let mut at = 0;
+        for clause in req.split(',') {
+            // Point to the comma ending an empty clause, or the one before a trailing one
+            if clause.trim().is_empty() {
+                let end = at + clause.len();
+                let at = if end < req.len() { end } else { at - 1 };
+                return Err(Error::EmptyClause { at });
+            }
+
+            // A wildcard major version must be the only comparator
+            let start = at + clause.len() - clause.trim_start().len();
+            if matches!(clause.trim(), "*" | "x" | "X") {
+                if req.trim() != clause.trim() {
+                    return Err(Error::UnexpectedChar { at: start });
+                }
+                return Ok(CargoReq {
+                    comparators: Vec::new(),
+                });
+            }
+
+            comparators.push(Comparator::parse(clause.trim(), start)?);
+            at += clause.len() + 1;
+        }
+
+        Ok(CargoReq { comparators })
+    }
+
+    /// Get the equivalent requirement with explicit bounds only, such as `>=1.2.0, <2.0.0` for
+    /// `^1.2`.
+    ///
+    /// Missing components are filled with zeros, and exact versions use `==`. The bounds don't
+    /// include the pre-release rule of Cargo, so they're meant for display rather than matching.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use version_compare::CargoReq;
+    ///
+    /// let bounds = |req| CargoReq::parse(req).unwrap().bounds().to_string();
+    ///
+    /// assert_eq!(bounds("^0.2.3"), ">=0.2.3, <0.3.0");
+    /// assert_eq!(bounds("^0.0.3"), ">=0.0.3, <0.0.4");
+    /// assert_eq!(bounds("~1.2.3"), ">=1.2.3, <1.3.0");
+    /// assert_eq!(bounds("1.*, <=1.4"), ">=1.0.0, <2.0.0, <1.5.0");
+    /// assert_eq!(bounds("=1.2.3-rc.1"), "==1.2.3-rc.1");
+    /// assert_eq!(bounds("*"), "");
+    /// ```
+    pub fn bounds(&self) -> VersionReq {
+        let manifest = Manifest {
+            scheme: Scheme::Semver,
+            ..Manifest::default()
+        };
+        let clauses = self
+            .comparators
+            .iter()
+            .flat_map(Comparator::bounds)
+            .map(|(operator, version)| {
+                let version = VersionBuf::from_manifest(version, &manifest)
+                    .expect("bound must be a valid semantic version");
+                (operator, version)
+            })
+            .collect();
+        VersionReq::from_clauses(clauses)
+    }
+
+    /// Check whether the given `version` matches all comparators of this requirement.
+    ///
+    /// A version that isn't a valid semantic version never matches.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use version_compare::{CargoReq, Version};
+    ///
+    /// let req = CargoReq::parse("^0.2.3").unwrap();
+    /// assert!(req.matches(&Version::from("0.2.5").unwrap()));
+    /// assert!(!req.matches(&Version::from("0.3.0").unwrap()));
+    ///
+    /// let req = CargoReq::parse(">=1.0.0-rc.1, <2").unwrap();
+    /// assert!(req.matches(&Version::from("1.0.0-rc.2").unwrap()));
+    /// assert!(!req.matches(&Version::from("1.5.0-rc.1").unwrap()));
+    /// ```
+    pub fn matches(&self, version: &Version) -> bool {
+        let version = match SemverVersion::parse(version.as_str()) {
+            Ok(version) => version,
+            Err(_) => return false,
+        };
+        let (major, minor, patch) = match (
+            version.major().as_u64(),
+            version.minor().as_u64(),
+            version.patch().as_u64(),
+        ) {
+            (Some(major), Some(minor), Some(patch)) => (major, minor, patch),
+            _ => return false,
+        };
+
+        let matches = self
+            .comparators
+            .iter()
+            .all(|comparator| comparator.matches(&version, major, minor, patch));
+        if !matches || !version.is_prerelease() {
+            return matches;
+        }
+
+        // Pre-releases only match if a comparator has one on the same version
+        self.comparators.iter().any(|comparator| {
+            comparator.pre.is_some()
+                && comparator.major == major
+                && comparator.minor == Some(minor)
+                && comparator.patch == Some(patch)
+        })
+    }
+}
+
+impl FromStr for CargoReq {
+    type Err = Error;
+
+    fn from_str(req: &str) -> Result<Self, Self::Err> {
+        CargoReq::parse(req)
+    }
+}
+
+/// Format the requirement like Cargo does, with an explicit `^` for bare versions.
+impl fmt::Display for CargoReq {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.comparators.is_empty() {
+            return write!(f, "*");
+        }
+
+        for (i, comparator) in self.comparators.iter().enumerate() {
+            if i > 0 {
+                write!(f, ", ")?;
+            }
+            match comparator.operator {
+                Operator::Cmp(Cmp::Eq) => write!(f, "=")?,
+                Operator::Cmp(cmp) => write!(f, "{}", cmp.sign())?,
+                Operator::Tilde => write!(f, "~")?,
+                Operator::Caret => write!(f, "^")?,
+                Operator::Wildcard => {}
+            }
+            write!(f, "{}", comparator.major)?;
+            match (comparator.minor, comparator.operator) {
+                (Some(minor), _) => write!(f, ".{}", minor)?,
+                (None, Operator::Wildcard) => write!(f, ".*")?,
+                (None, _) => {}
+            }
+            match (comparator.minor, comparator.patch, comparator.operator) {
+                (_, Some(patch), _) => write!(f, ".{}", patch)?,
+                (Some(_), None, Operator::Wildcard) => write!(f, ".*")?,
+                _ => {}
+            }
+            if let Some(pre) = &comparator.pre {
+                write!(f, "-{}", pre)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Comparator {
+    /// Parse a single trimmed comparator, located at byte offset `at` in the requirement.
+    fn parse(comparator: &str, at: usize) -> Result<Self, Error> {
+        let sign = OPERATORS
+            .iter()
+            .find(|sign| comparator.starts_with(*sign))
+            .map_or("", |sign| *sign);
+        let rest = &comparator[sign.len()..];
+        let version = rest.trim_start();
+        let at = at + comparator.len() - version.len();
+        if version.is_empty() {
+            return Err(Error::MissingVersion { at });
+        }
+
+        let mut operator = match sign {
+            "~" => Operator::Tilde,
+            "^" | "" => Operator::Caret,
+            sign => Operator::Cmp(Cmp::from_sign(sign)?),
+        };
+
+        // Split off build metadata, which is ignored, then the pre-release
+        let (rest, build) = match version.find('+') {
+            Some(plus) => (&version[..plus], Some(plus + 1)),
+            None => (version, None),
+        };
+        let (core, pre) = match rest.find('-') {
+            Some(hyphen) => (&rest[..hyphen], Some(hyphen + 1)),
+            None => (rest, None),
+        };
+
+        // Components are numbers, and minor or patch may be wildcards or missing
+        let mut numbers = Vec::with_capacity(3);
+        let mut wildcard = false;
+        let mut offset = 0;
+        for (i, number) in core.split('.').enumerate() {
+            let number_at = at + offset;
+            offset += number.len() + 1;
+            if i == 3 {
+                return Err(Error::UnexpectedChar { at: number_at - 1 });
+            }
+            if i > 0 && matches!(number, "*" | "x" | "X") {
+                if sign.is_empty() {
+                    operator = Operator::Wildcard;
+                }
+                wildcard = true;
+                continue;
+            }
+            if wildcard {
+                return Err(Error::UnexpectedChar { at: number_at });
+            }
+
+            let parsed = numeric(number, number_at)?.ok_or_else(|| Error::UnexpectedChar {
+                at: number_at + number.find(|c: char| !c.is_ascii_digit()).unwrap_or(0),
+            })?;
+            numbers.push(
+                parsed
+                    .as_u64()
+                    .ok_or(Error::NumberOverflow { at: number_at })?,
+            );
+        }
+
+        // Pre-release and build metadata may only follow a patch version
+        if let Some(suffix) = pre.or(build) {
+            if numbers.len() < 3 {
+                return Err(Error::UnexpectedChar {
+                    at: at + suffix - 1,
+                });
+            }
+        }
+        if let Some(pre_at) = pre {
+            let pre = &rest[pre_at..];
+            for_identifiers(pre, at + pre_at, |identifier, at| {
+                numeric(identifier, at).map(|_| ())
+            })?;
+        }
+        if let Some(build_at) = build {
+            for_identifiers(&version[build_at..], at + build_at, |_, _| Ok(()))?;
+        }
+
+        Ok(Comparator {
+            operator,
+            major: numbers[0],
+            minor: numbers.get(1).cloned(),
+            patch: numbers.get(2).cloned(),
+            pre: pre.map(|pre_at| rest[pre_at..].to_string()),
+        })
+    }
+
+    /// Get the version of this comparator, with missing components as zero.
+    fn version(&self) -> String {
+        let mut version = format!(
+            "{}.{}.{}",
+            self.major,
+            self.minor.unwrap_or(0),
+            self.patch.unwrap_or(0)
+        );
+        if let Some(pre) = &self.pre {
+            version.push('-');
+            version.push_str(pre);
+        }
+        version
+    }
+
+    /// Get the explicit bounds of this comparator.
+    fn bounds(&self) -> Vec<(Cmp, String)> {
+        let (major, minor) = (u128::from(self.major), self.minor.map(u128::from));
+        let next_major = format!("{}.0.0", major + 1);
+        let next_minor = |minor: u128| format!("{}.{}.0", major, minor + 1);
+
+        // The version after a partial version, such as `1.3.0` for `1.2`
+        let next = match minor {
+            None => next_major.clone(),
+            Some(minor) => next_minor(minor),
+        };
+        let partial = self.patch.is_none();
+
+        match self.operator {
+            Operator::Cmp(Cmp::Eq) if partial => vec![(Cmp::Ge, self.version()), (Cmp::Lt, next)],
+            Operator::Cmp(Cmp::Gt) if partial => vec![(Cmp::Ge, next)],
+            Operator::Cmp(Cmp::Le) if partial => vec![(Cmp::Lt, next)],
+            Operator::Cmp(cmp) => vec![(cmp, self.version())],
+            Operator::Tilde | Operator::Wildcard => {
+                vec![(Cmp::Ge, self.version()), (Cmp::Lt, next)]
+            }
+            Operator::Caret => {
+                let upper = match (major, minor, self.patch) {
+                    (0, Some(0), Some(patch)) => format!("0.0.{}", u128::from(patch) + 1),
+                    (0, Some(minor), _) => next_minor(minor),
+                    _ => next_major,
+                };
+                vec![(Cmp::Ge, self.version()), (Cmp::Lt, upper)]
+            }
+        }
+    }
+
+    /// Check whether the given `version` with the given numbers matches this comparator, like
+    /// the `semver` crate does.
+    fn matches(&self, version: &SemverVersion, major: u64, minor: u64, patch: u64) -> bool {
+        // Compare the pre-release if all numbers are equal, a release is the greatest
+        let pre = || {
+            let own = self.version();
+            version.cmp(&SemverVersion::parse(&own).expect("comparator must be valid"))
+        };
+        let numbers = (major, minor, patch);
+        let exact = || {
+            major == self.major
+                && self.minor.map_or(true, |m| m == minor)
+                && self.patch.map_or(true, |p| p == patch)
+                && version.pre() == self.pre.as_deref()
+        };
+
+        match self.operator {
+            Operator::Cmp(Cmp::Eq) | Operator::Wildcard => exact(),
+            Operator::Cmp(Cmp::Gt) => self.order(numbers, pre) == Some(Ordering::Greater),
+            Operator::Cmp(Cmp::Ge) => {
+                exact() || self.order(numbers, pre) == Some(Ordering::Greater)
+            }
+            Operator::Cmp(Cmp::Lt) => self.order(numbers, pre) == Some(Ordering::Less),
+            Operator::Cmp(Cmp::Le) => exact() || self.order(numbers, pre) == Some(Ordering::Less),
+            Operator::Cmp(Cmp::Ne) => unreachable!(),
+            Operator::Tilde => {
+                major == self.major
+                    && self.minor.map_or(true, |m| m == minor)
+                    && match self.patch {
+                        Some(p) if p != patch => patch > p,
+                        _ => pre() != Ordering::Less,
+                    }
+            }
+            Operator::Caret => {
+                let (own_minor, own_patch) = match (self.minor, self.patch) {
+                    _ if major != self.major => return false,
+                    (None, _) => return true,
+                    (Some(m), None) if self.major > 0 => return minor >= m,
+                    (Some(m), None) => return minor == m,
+                    (Some(m), Some(p)) => (m, p),
+                };
+                if self.major > 0 {
+                    if minor != own_minor {
+                        return minor > own_minor;
+                    }
+                } else if minor != own_minor || (own_minor == 0 && patch != own_patch) {
+                    return false;
+                }
+                if patch != own_patch {
+                    return patch > own_patch;
+                }
+                pre() != Ordering::Less
+            }
+        }
+    }
+
+    /// Order the given version numbers against this comparator, by its present components.
+    ///
+    /// Returns `None` if they're equal up to a missing component, and calls `pre` to order the
+    /// pre-release if all components are equal.
+    fn order<F>(&self, (major, minor, patch): (u64, u64, u64), pre: F) -> Option<Ordering>
+    where
+        F: Fn() -> Ordering,
+    {
+        if major != self.major {
+            return Some(major.cmp(&self.major));
+        }
+        match self.minor {
+            None => return None,
+            Some(m) if m != minor => return Some(minor.cmp(&m)),
+            Some(_) => {}
+        }
+        match self.patch {
+            None => None,
+            Some(p) if p != patch => Some(patch.cmp(&p)),
+            Some(_) => Some(pre()),
+        }
+    }
+}
+
+#[cfg_attr(tarpaulin, skip)]
+#[cfg(test)]
+mod tests {
+    use crate::{Error, Version};
+
+    use super::CargoReq;
+
+    /// Requirements, with versions they match and don't match, checked against the `semver`
+    /// crate.
+    const MATCHES: &[(&str, &[&str], &[&str])] = &[
+        (
+            "1.2",
+            &["1.2.0", "1.9.9"],
+            &["1.1.0", "2.0.0", "1.2.3-alpha"],
+        ),
+        (
+            "^1.2.3",
+            &["1.2.3", "1.5.0", "1.2.3+build"],
+            &["1.2.2", "2.0.0-alpha"],
+        ),
+        ("^0.2.3", &["0.2.3", "0.2.9"], &["0.3.0", "0.2.2"]),
+        ("^0.0.3", &["0.0.3"], &["0.0.4", "0.0.2", "0.1.0"]),
+        ("^0.0", &["0.0.0", "0.0.4"], &["0.1.0"]),
+        ("^0", &["0.0.0", "0.9.9"], &["1.0.0"]),
+        ("~1.2.3", &["1.2.3", "1.2.4"], &["1.2.2", "1.3.0"]),
+        ("~1.2", &["1.2.0", "1.2.4"], &["1.1.0", "1.3.0"]),
+        ("~1", &["1.0.0", "1.9.9"], &["0.9.9", "2.0.0"]),
+        (
+            "1.*",
+            &["1.0.0", "1.9.9"],
+            &["0.9.9", "2.0.0", "1.2.3-alpha"],
+        ),
+        ("1.2.*", &["1.2.0", "1.2.4"], &["1.3.0", "1.1.0"]),
+        ("*", &["0.0.0", "3.0.0"], &["1.2.3-alpha"]),
+        ("=1.2", &["1.2.0", "1.2.4"], &["1.3.0"]),
+        (">1.2", &["1.3.0"], &["1.2.4"]),
+        (">=1.2", &["1.2.0"], &["1.1.0"]),
+        ("<1.2", &["1.1.0"], &["1.2.0"]),
+        ("<=1.2", &["1.2.4"], &["1.3.0"]),
+        (
+            ">= 1.2, < 1.5",
+            &["1.2.0", "1.4.9"],
+            &["1.5.0", "1.5.0-rc.1"],
+        ),
+        (
+            "^1.2.3-alpha.1",
+            &["1.2.3-alpha.2", "1.2.3", "1.2.4"],
+            &["1.2.3-alpha", "1.2.4-alpha"],
+        ),
+        (
+            "~1.2.3-beta",
+            &["1.2.3-beta", "1.2.3-rc.1"],
+            &["1.2.3-alpha.2"],
+        ),
+        (
+            ">=1.0.0-rc.1, <2",
+            &["1.0.0-rc.2", "1.0.0"],
+            &["1.5.0-rc.1"],
+        ),
+        ("^0.0.1-pre", &["0.0.1-pre", "0.0.1"], &["0.0.2"]),
+        ("=1.2.3, =1.2.3-alpha", &[], &["1.2.3", "1.2.3-alpha"]),
+    ];
+
+    #[test]
+    fn matches() {
+        for (req, matching, other) in MATCHES {
+            let parsed = CargoReq::parse(req).unwrap();
+            for version in matching.iter() {
+                let version = Version::from(version).unwrap();
+                assert!(parsed.matches(&version), "{} matches {}", req, version);
+            }
+            for version in other.iter() {
+                let version = Version::from(version).unwrap();
+                assert!(
+                    !parsed.matches(&version),
+                    "{} doesn't match {}",
+                    req,
+                    version
+                );
+            }
+        }
+
+        // Versions that aren't semantic versions never match
+        let req = CargoReq::parse("*").unwrap();
+        assert!(!req.matches(&Version::from("1.2").unwrap()));
+        assert!(!req.matches(&Version::from("v1.2.3").unwrap()));
+    }
+
+    #[test]
+    fn parse_error() {
+        let errors = [
+            ("", Error::MissingVersion { at: 0 }),
+            (">=", Error::MissingVersion { at: 2 }),
+            ("1.2.3.4", Error::UnexpectedChar { at: 5 }),
+            ("1.02", Error::LeadingZero { at: 2 }),
+            ("1.2-beta", Error::UnexpectedChar { at: 3 }),
+            ("1.2.", Error::EmptyPart { at: 4 }),
+            ("1.2,", Error::EmptyClause { at: 3 }),
+            ("1.2,,1.3", Error::EmptyClause { at: 4 }),
+            ("*, 1.2", Error::UnexpectedChar { at: 0 }),
+            ("~>1.2", Error::UnexpectedChar { at: 1 }),
+            ("^*", Error::UnexpectedChar { at: 1 }),
+            ("1.*.3", Error::UnexpectedChar { at: 4 }),
+            ("1.2.3-01", Error::LeadingZero { at: 6 }),
+            ("1.2.3-a..b", Error::EmptyPart { at: 8 }),
+            ("v1.2", Error::UnexpectedChar { at: 0 }),
+            ("18446744073709551616", Error::NumberOverflow { at: 0 }),
+        ];
+        for (req, err) in &errors {
+            assert_eq!(CargoReq::parse(req).unwrap_err(), *err, "Parsing {:?}", req);
+        }
+    }
+
+    #[test]
+    fn display() {
+        let reqs = [
+            ("1.2", "^1.2", ">=1.2.0, <2.0.0"),
+            ("~1", "~1", ">=1.0.0, <2.0.0"),
+            ("1.*.*", "1.*", ">=1.0.0, <2.0.0"),
+            ("1.2.X", "1.2.*", ">=1.2.0, <1.3.0"),
+            ("x", "*", ""),
+            ("=1", "=1", ">=1.0.0, <2.0.0"),
+            (">1", ">1", ">=2.0.0"),
+            ("<=1.2", "<=1.2", "<1.3.0"),
+            (">=1.*", ">=1", ">=1.0.0"),
+            ("1.2.3-alpha+build", "^1.2.3-alpha", ">=1.2.3-alpha, <2.0.0"),
+            (" >= 1.2 ,<1.5 ", ">=1.2, <1.5", ">=1.2.0, <1.5.0"),
+            (
+                "^18446744073709551615",
+                "^18446744073709551615",
+                ">=18446744073709551615.0.0, <18446744073709551616.0.0",
+            ),
+        ];
+        for (req, display, bounds) in &reqs {
+            let parsed = CargoReq::parse(req).unwrap();
+            assert_eq!(parsed.to_string(), *display, "Formatting {}", req);
+            assert_eq!(parsed.bounds().to_string(), *bounds, "Bounds of {}", req);
+            assert_eq!(CargoReq::parse(display).unwrap(), parsed);
+        }
+    }
+}
@@ -0,0 +1,726 @@
+//! Composer constraint module, which provides the `ComposerConstraint` struct.
+//!
+//! Composer constraints are `||` separated alternatives of comma or space separated clauses,
+//! such as `^1.2 || ~2.0.3` or `>=1.0 <1.5@beta`, as found in `composer.json` files.
+
+use std::cmp::Ordering;
+use std::fmt;
+use std::str::FromStr;
+
+use crate::{Cmp, Error, PhpVersion, Version};
+
+/// Composer package stability, from least to most stable.
+///
+/// A constraint only matches versions at least as stable as its minimum stability.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum ComposerStability {
+    /// Development versions, such as `1.0-dev`.
+    Dev,
+
+    /// Alpha versions, such as `1.0-alpha2`.
+    Alpha,
+
+    /// Beta versions, such as `1.0-beta2`.
+    Beta,
+
+    /// Release candidates, such as `1.0-RC2`.
+    Rc,
+
+    /// Stable versions, including patch versions such as `1.0-patch2`.
+    Stable,
+}
+
+/// Stability flag names, as used after `@`.
+const STABILITIES: &[(&str, ComposerStability)] = &[
+    ("dev", ComposerStability::Dev),
+    ("alpha", ComposerStability::Alpha),
+    ("beta", ComposerStability::Beta),
+    ("rc", ComposerStability::Rc),
+    ("stable", ComposerStability::Stable),
+];
+
+/// Version modifiers, longest first, with their expanded normalized form.
+const MODIFIERS: &[(&str, &str)] = &[
+    ("stable", "stable"),
+    ("alpha", "alpha"),
+    ("patch", "patch"),
+    ("beta", "beta"),
+    ("pl", "patch"),
+    ("rc", "RC"),
+    ("a", "alpha"),
+    ("b", "beta"),
+    ("p", "patch"),
+];
+
+/// Operator signs, longest first.
+const OPERATORS: &[&str] = &["<>", "!=", "<=", ">=", "==", "<", ">", "="];
+
+/// Composer version constraint, compatible with Composer's `VersionParser`.
+///
+/// Supports the operators `=`, `==`, `!=`, `<>`, `<`, `<=`, `>`, `>=`, the caret `^1.2.3`
+/// meaning `>=1.2.3 <2.0.0`, the tilde `~1.2` meaning `>=1.2 <2.0`, wildcards such as `1.2.*`,
+/// hyphen ranges such as `1.0 - 2.0`, and `*`. Like Composer, versions are normalized to four
+/// numbers, and lower bounds include development versions, so `^1.2` matches `1.2.0-beta`.
+///
+/// Clauses separated by `,` or spaces must all match, and alternatives separated by `||` or `|`
+/// match if any does. A stability flag such as `@dev` sets the minimum stability of matched
+/// versions, which otherwise is the lowest stability of versions in the constraint, or stable.
+///
+/// # Examples
+///
+/// ```
+/// use version_compare::{ComposerConstraint, Version};
+///
+/// let constraint = ComposerConstraint::parse("^1.2 || ~2.0.3").unwrap();
+///
+/// assert!(constraint.matches(&Version::from("1.9.0").unwrap()));
+/// assert!(constraint.matches(&Version::from("v2.0.5").unwrap()));
+/// assert!(!constraint.matches(&Version::from("2.1.0").unwrap()));
+/// assert!(!constraint.matches(&Version::from("1.3.0-beta").unwrap()));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ComposerConstraint {
+    alternatives: Vec<Vec<(Cmp, String)>>,
+    stability: ComposerStability,
+}
+
+/// Parsed clause: its bounds, its stability flag, and the lowest stability of its versions.
+type Clause = (
+    Vec<(Cmp, String)>,
+    Option<ComposerStability>,
+    ComposerStability,
+);
+
+/// Version parsed like Composer's `VersionParser::normalize`.
+struct Normalized {
+    /// Numbers as written, one to four.
+    numbers: Vec<u64>,
+
+    /// Whether a stability modifier or `dev` suffix was written.
+    modifier: bool,
+
+    /// Stability of the version.
+    stability: ComposerStability,
+
+    /// Normalized version string, such as `1.2.0.0-beta1`.
+    version: String,
+}
+
+impl ComposerConstraint {
+    /// Parse a Composer constraint.
+    ///
+    /// An error is returned for malformed constraints, such as an empty alternative or clause,
+    /// an unknown stability flag, or a version that Composer can't normalize. Branch names such
+    /// as `dev-main` aren't supported.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use version_compare::{ComposerConstraint, Error};
+    ///
+    /// assert!(ComposerConstraint::parse(">=1.0 <1.5, !=1.2.3 || ^2.0@dev").is_ok());
+    /// assert_eq!(ComposerConstraint::parse("^1.0 ||").unwrap_err(), Error::EmptyClause { at: 5 });
+    /// assert_eq!(ComposerConstraint::parse("1.0@foo").unwrap_err(), Error::UnexpectedChar { at: 4 });
+    /// ```
+    pub fn parse(constraint: &str) -> Result<Self, Error> {
+        let mut alternatives = Vec::new();
+        let mut explicit: Option<ComposerStability> = None;
+        let mut inferred = ComposerStability::Stable;
+
+        let (mut at, mut separator) = (0, None);
+        loop {
+            // Alternatives are separated by `||`, or a single `|`
+            let end = constraint[at..]
+                .find('|')
+                .map_or(constraint.len(), |i| i + at);
+            let alternative = &constraint[at..end];
+            if alternative.trim().is_empty() {
+                let at = if end < constraint.len() {
+                    end
+                } else {
+                    separator.unwrap_or(end)
+                };
+                return Err(Error::EmptyClause { at });
+            }
+
+            let mut clauses = Vec::new();
+            for (clause, clause_at) in split_clauses(alternative, at)? {
+                let (bounds, flag, stability) = parse_clause(&clause, clause_at)?;
+                if let Some(flag) = flag {
+                    explicit = Some(explicit.map_or(flag, |explicit| explicit.min(flag)));
+                }
+                inferred = inferred.min(stability);
+                clauses.extend(bounds);
+            }
+            alternatives.push(clauses);
+
+            if end == constraint.len() {
+                break;
+            }
+            separator = Some(end);
+            at = end + 1;
+            if constraint[at..].starts_with('|') {
+                at += 1;
+            }
+        }
+
+        Ok(ComposerConstraint {
+            alternatives,
+            stability: explicit.unwrap_or(inferred),
+        })
+    }
+
+    /// Get the minimum stability of versions this constraint matches.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use version_compare::{ComposerConstraint, ComposerStability};
+    ///
+    /// let stability = |constraint| ComposerConstraint::parse(constraint).unwrap().stability();
+    /// assert_eq!(stability("^1.0"), ComposerStability::Stable);
+    /// assert_eq!(stability("^1.0@beta"), ComposerStability::Beta);
+    /// assert_eq!(stability(">=1.0-RC2"), ComposerStability::Rc);
+    /// ```
+    pub fn stability(&self) -> ComposerStability {
+        self.stability
+    }
+
+    /// Check whether the given `version` matches this constraint.
+    ///
+    /// The version is normalized like Composer does, and must be at least as stable as the
+    /// minimum stability of this constraint. A version Composer can't normalize never matches.
+    /// Versions are compared with the PHP scheme, like `PhpVersion`.
+    pub fn matches(&self, version: &Version) -> bool {
+        let normalized = match normalize(version.as_str(), 0) {
+            Ok(normalized) => normalized,
+            Err(_) => return false,
+        };
+        if normalized.stability < self.stability {
+            return false;
+        }
+
+        let version = PhpVersion::parse(&normalized.version).expect("normalized version is valid");
+        self.alternatives.iter().any(|clauses| {
+            clauses.iter().all(|(cmp, bound)| {
+                let bound = PhpVersion::parse(bound).expect("normalized bound is valid");
+                match version.cmp(&bound) {
+                    Ordering::Less => matches!(cmp, Cmp::Ne | Cmp::Lt | Cmp::Le),
+                    Ordering::Equal => matches!(cmp, Cmp::Eq | Cmp::Le | Cmp::Ge),
+                    Ordering::Greater => matches!(cmp, Cmp::Ne | Cmp::Gt | Cmp::Ge),
+                }
+            })
+        })
+    }
+}
+
+impl FromStr for ComposerConstraint {
+    type Err = Error;
+
+    fn from_str(constraint: &str) -> Result<Self, Self::Err> {
+        ComposerConstraint::parse(constraint)
+    }
+}
+
+/// Format the constraint with normalized bounds, such as `>= 1.2.0.0-dev, < 2.0.0.0-dev`, like
+/// Composer's pretty constraint. A constraint without bounds is formatted as `*`, and the
+/// stability flag isn't included.
+impl fmt::Display for ComposerConstraint {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (i, clauses) in self.alternatives.iter().enumerate() {
+            if i > 0 {
+                write!(f, " || ")?;
+            }
+            if clauses.is_empty() {
+                write!(f, "*")?;
+            }
+            for (j, (cmp, version)) in clauses.iter().enumerate() {
+                if j > 0 {
+                    write!(f, ", ")?;
+                }
+                write!(f, "{} {}", cmp.sign(), version)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Split an alternative at byte offset `at` into clauses, each with its byte offset.
+///
+/// Clauses are separated by commas or spaces, except that an operator may be separated from its
+/// version, and a hyphen range `1.0 - 2.0` is a single clause.
+fn split_clauses(alternative: &str, at: usize) -> Result<Vec<(String, usize)>, Error> {
+    let mut clauses: Vec<(String, usize)> = Vec::new();
+    let mut offset = at;
+    for part in alternative.split(',') {
+        if part.trim().is_empty() {
+            let end = offset + part.len();
+            let at = if end < at + alternative.len() || offset == at {
+                end
+            } else {
+                offset - 1
+            };
+            return Err(Error::EmptyClause { at });
+        }
+
+        // Words with their offsets, merged into clauses
+        let mut words: Vec<(&str, usize)> = Vec::new();
+        let mut rest = part;
+        while let Some(start) = rest.find(|c: char| !c.is_whitespace()) {
+            let len = rest[start..]
+                .find(char::is_whitespace)
+                .unwrap_or(rest.len() - start);
+            words.push((
+                &rest[start..start + len],
+                offset + part.len() - rest.len() + start,
+            ));
+            rest = &rest[start + len..];
+        }
+
+        let mut i = 0;
+        while i < words.len() {
+            let (word, word_at) = words[i];
+            if words.get(i + 1).map(|(word, _)| *word) == Some("-") && i + 2 < words.len() {
+                clauses.push((format!("{} - {}", word, words[i + 2].0), word_at));
+                i += 3;
+            } else if OPERATORS.contains(&word) || word == "^" || word == "~" || word == "~>" {
+                let (version, _) = words.get(i + 1).ok_or(Error::MissingVersion {
+                    at: word_at + word.len(),
+                })?;
+                clauses.push((format!("{}{}", word, version), word_at));
+                i += 2;
+            } else {
+                clauses.push((word.to_owned(), word_at));
+                i += 1;
+            }
+        }
+        offset += part.len() + 1;
+    }
+    Ok(clauses)
+}
+
+/// Parse a clause at byte offset `at` into bounds, its stability flag, and the lowest stability
+/// of its versions.
+///
+/// Offsets within the clause are exact, unless an operator or hyphen range was separated by
+/// whitespace.
+fn parse_clause(clause: &str, at: usize) -> Result<Clause, Error> {
+    // Strip the stability flag
+    let (clause, flag) = match clause.find('@') {
+        Some(i) => {
+            let name = clause[i + 1..].to_ascii_lowercase();
+            let flag = STABILITIES
+                .iter()
+                .find(|(stability, _)| *stability == name)
+                .map(|(_, flag)| *flag)
+                .ok_or(Error::UnexpectedChar { at: at + i + 1 })?;
+            (&clause[..i], Some(flag))
+        }
+        None => (clause, None),
+    };
+
+    if clause.is_empty() || matches!(clause, "*" | "x" | "X") {
+        return Ok((Vec::new(), flag, ComposerStability::Stable));
+    }
+
+    // Hyphen ranges, with an exact upper bound if it's complete
+    if let Some(hyphen) = clause.find(" - ") {
+        let from = normalize(&clause[..hyphen], at)?;
+        let to = normalize(&clause[hyphen + 3..], at + hyphen + 3)?;
+        let upper = if to.numbers.len() >= 3 || to.modifier {
+            (Cmp::Le, to.version)
+        } else {
+            let position = if to.numbers.len() == 1 { 1 } else { 2 };
+            (Cmp::Lt, bump(&to.numbers, position, 1))
+        };
+        let stability = from.stability.min(to.stability);
+        return Ok((vec![(Cmp::Ge, lower(from)), upper], flag, stability));
+    }
+
+    // Caret and tilde ranges, bumping the first non-zero or the next to last number
+    let caret = clause.starts_with('^');
+    if caret || clause.starts_with('~') {
+        let sign = if clause.starts_with("~>") { 2 } else { 1 };
+        let version = normalize(&clause[sign..], at + sign)?;
+        let numbers = &version.numbers;
+        let position = if !caret {
+            (numbers.len() - 1).max(1)
+        } else if numbers[0] != 0 || numbers.len() == 1 {
+            1
+        } else if numbers[1] != 0 || numbers.len() == 2 {
+            2
+        } else {
+            3
+        };
+        let upper = (Cmp::Lt, bump(numbers, position, 1));
+        let stability = version.stability;
+        return Ok((vec![(Cmp::Ge, lower(version)), upper], flag, stability));
+    }
+
+    // Wildcards, such as `1.2.*` or `1.x`
+    let trimmed = clause.trim_end_matches(|c| matches!(c, '.' | '*' | 'x' | 'X'));
+    let wildcard = &clause[trimmed.len()..];
+    if wildcard.starts_with('.') && wildcard.contains(|c| matches!(c, '*' | 'x' | 'X')) {
+        let version = normalize(trimmed, at)?;
+        if version.modifier || version.numbers.len() == 4 {
+            return Err(Error::UnexpectedChar {
+                at: at + trimmed.len(),
+            });
+        }
+        let position = version.numbers.len();
+        let lower = bump(&version.numbers, position, 0);
+        let upper = (Cmp::Lt, bump(&version.numbers, position, 1));
+        let bounds = if lower == "0.0.0.0-dev" {
+            vec![upper]
+        } else {
+            vec![(Cmp::Ge, lower), upper]
+        };
+        return Ok((bounds, flag, ComposerStability::Stable));
+    }
+
+    // Plain comparison, where a lower bound includes development versions
+    let sign = OPERATORS
+        .iter()
+        .find(|sign| clause.starts_with(*sign))
+        .map_or("", |sign| *sign);
+    let cmp = match sign {
+        "" | "=" => Cmp::Eq,
+        "<>" => Cmp::Ne,
+        sign => Cmp::from_sign(sign)?,
+    };
+    if clause.len() == sign.len() {
+        return Err(Error::MissingVersion {
+            at: at + clause.len(),
+        });
+    }
+    let mut version = normalize(&clause[sign.len()..], at + sign.len())?;
+    let stability = version.stability;
+    let modifier = STABILITIES
+        .iter()
+        .find(|(_, stability)| Some(*stability) == flag && *stability != ComposerStability::Stable);
+    if let (Some((name, _)), true) = (
+        modifier,
+        cmp != Cmp::Eq && stability == ComposerStability::Stable,
+    ) {
+        version.version.push('-');
+        version.version.push_str(name);
+    } else if matches!(cmp, Cmp::Lt | Cmp::Ge) && !version.modifier {
+        version.version.push_str("-dev");
+    }
+    Ok((vec![(cmp, version.version)], flag, stability))
+}
+
+/// Get the normalized lower bound of a caret, tilde or hyphen range, including development
+/// versions if no modifier was written.
+fn lower(version: Normalized) -> String {
+    if version.modifier {
+        version.version
+    } else {
+        format!("{}-dev", version.version)
+    }
+}
+
+/// Increment the number at 1-based `position` by `increment`, zero the numbers after it, and
+/// format as a normalized development version, like Composer's `manipulateVersionString`.
+fn bump(numbers: &[u64], position: usize, increment: u64) -> String {
+    let mut bumped = [0; 4];
+    bumped[..position].copy_from_slice(&numbers[..position]);
+    bumped[position - 1] += increment;
+    format!(
+        "{}.{}.{}.{}-dev",
+        bumped[0], bumped[1], bumped[2], bumped[3]
+    )
+}
+
+/// Normalize a version at byte offset `at` like Composer's `VersionParser::normalize`.
+///
+/// Accepts an optional `v`, one to four numbers, an optional stability modifier with numbers such
+/// as `-beta.2`, an optional `-dev` suffix, and build metadata, which is dropped.
+fn normalize(version: &str, at: usize) -> Result<Normalized, Error> {
+    let unexpected = |i: usize| Error::UnexpectedChar { at: at + i };
+    let digits = |s: &str| s.find(|c: char| !c.is_ascii_digit()).unwrap_or(s.len());
+
+    let mut i = if version.starts_with(|c| c == 'v' || c == 'V') {
+        1
+    } else {
+        0
+    };
+
+    // One to four numbers
+    let mut numbers = Vec::new();
+    loop {
+        let len = digits(&version[i..]);
+        if len == 0 {
+            return Err(if i == version.len() {
+                Error::MissingVersion { at: at + i }
+            } else {
+                unexpected(i)
+            });
+        }
+        numbers.push(
+            version[i..i + len]
+                .parse()
+                .map_err(|_| Error::NumberOverflow { at: at + i })?,
+        );
+        i += len;
+        if numbers.len() == 4 || !version[i..].starts_with('.') || digits(&version[i + 1..]) == 0 {
+            break;
+        }
+        i += 1;
+    }
+    let mut normalized = format!(
+        "{}.{}.{}.{}",
+        numbers[0],
+        numbers.get(1).unwrap_or(&0),
+        numbers.get(2).unwrap_or(&0),
+        numbers.get(3).unwrap_or(&0)
+    );
+
+    // Stability modifier with numbers, such as `-beta.2`
+    let lowercase = version.to_ascii_lowercase();
+    let separator = |i: usize, separators: &[char]| {
+        version[i..]
+            .chars()
+            .next()
+            .map_or(0, |c| separators.contains(&c) as usize)
+    };
+    let mut stability = ComposerStability::Stable;
+    let mut expanded = None;
+    let start = i + separator(i, &['.', '_', '-']);
+    if let Some((name, expansion)) = MODIFIERS
+        .iter()
+        .find(|(name, _)| lowercase[start..].starts_with(name))
+    {
+        i = start + name.len();
+        let from = i;
+        loop {
+            let sep = separator(i, &['.', '-']);
+            let len = digits(&version[i + sep..]);
+            if len == 0 {
+                break;
+            }
+            i += sep + len;
+        }
+        stability = match *expansion {
+            "alpha" => ComposerStability::Alpha,
+            "beta" => ComposerStability::Beta,
+            "RC" => ComposerStability::Rc,
+            _ => ComposerStability::Stable,
+        };
+        if *expansion != "stable" {
+            normalized.push('-');
+            normalized.push_str(expansion);
+            normalized.push_str(version[from..i].trim_start_matches(|c| c == '.' || c == '-'));
+        }
+        expanded = Some(*expansion);
+    }
+
+    // Development suffix, ignored for stable versions, then build metadata
+    let separators: &[char] = if expanded.is_some() {
+        &['.', '-']
+    } else {
+        &['.', '_', '-']
+    };
+    let dev = i + separator(i, separators);
+    let modifier = if lowercase[dev..].starts_with("dev") {
+        i = dev + 3;
+        if expanded != Some("stable") {
+            stability = ComposerStability::Dev;
+            normalized.push_str("-dev");
+        }
+        true
+    } else {
+        expanded.is_some()
+    };
+    if version[i..].starts_with('+') && i + 1 < version.len() {
+        i = version.len();
+    }
+    if i < version.len() {
+        return Err(unexpected(i));
+    }
+
+    Ok(Normalized {
+        numbers,
+        modifier,
+        stability,
+        version: normalized,
+    })
+}
+
+#[cfg_attr(tarpaulin, skip)]
+#[cfg(test)]
+mod tests {
+    use crate::{ComposerStability, Error, Version};
+
+    use super::{normalize, ComposerConstraint};
+
+    #[test]
+    fn normalize_versions() {
+        let versions = [
+            ("1", "1.0.0.0"),
+            ("v1.2.3", "1.2.3.0"),
+            ("1.2.3.4", "1.2.3.4"),
+            ("01.02", "1.2.0.0"),
+            ("1.0.0-beta", "1.0.0.0-beta"),
+            ("1.0.0-beta.2", "1.0.0.0-beta2"),
+            ("1.0.0b2", "1.0.0.0-beta2"),
+            ("1.0.0-RC1", "1.0.0.0-RC1"),
+            ("1.0.0rc1", "1.0.0.0-RC1"),
+            ("1.0.0-alpha3.1", "1.0.0.0-alpha3.1"),
+            ("1.0.0-pl1", "1.0.0.0-patch1"),
+            ("1.0.0-stable", "1.0.0.0"),
+            ("1.0-dev", "1.0.0.0-dev"),
+            ("1.0.0-beta.5-dev", "1.0.0.0-beta5-dev"),
+            ("1.0.0+build.1", "1.0.0.0"),
+        ];
+        for (version, normalized) in &versions {
+            assert_eq!(
+                normalize(version, 0).map(|n| n.version).unwrap(),
+                *normalized,
+                "Normalizing {:?}",
+                version,
+            );
+        }
+        assert_eq!(
+            normalize("1.0.0-foo", 0).err(),
+            Some(Error::UnexpectedChar { at: 5 })
+        );
+        assert_eq!(
+            normalize("dev-main", 0).err(),
+            Some(Error::UnexpectedChar { at: 0 })
+        );
+    }
+
+    #[test]
+    fn matches() {
+        let cases: &[(&str, &[&str], &[&str])] = &[
+            ("1.2.3", &["1.2.3", "v1.2.3.0"], &["1.2.4", "1.2.3-beta"]),
+            ("==1.2", &["1.2.0"], &["1.2.1"]),
+            ("!=1.2", &["1.3"], &["1.2"]),
+            ("<>1.2", &["1.3"], &["1.2"]),
+            (">=1.2", &["1.2", "2.0"], &["1.1", "1.2-beta"]),
+            (">1.2", &["1.2.1"], &["1.2"]),
+            ("<1.2", &["1.1"], &["1.2", "1.2.0-dev"]),
+            ("<=1.2", &["1.2"], &["1.2.1"]),
+            (">=1.0 <1.5", &["1.0", "1.4.9"], &["1.5", "0.9"]),
+            (">=1.0,<1.5", &["1.4"], &["1.5"]),
+            (">= 1.0, < 1.5", &["1.4"], &["1.5"]),
+            ("^1.2.3", &["1.2.3", "1.9"], &["1.2.2", "2.0", "2.0-beta"]),
+            ("^0.3", &["0.3.0", "0.3.9"], &["0.4.0", "0.2"]),
+            ("^0.0.3", &["0.0.3"], &["0.0.4"]),
+            ("^0", &["0.9"], &["1.0"]),
+            ("~1.2", &["1.2", "1.9"], &["2.0", "1.1"]),
+            ("~1.2.3", &["1.2.3", "1.2.9"], &["1.3.0", "1.2.2"]),
+            ("~1", &["1.0", "1.9"], &["2.0"]),
+            ("~>1.2", &["1.9"], &["2.0"]),
+            ("1.2.*", &["1.2.0", "1.2.99"], &["1.3.0", "1.1.9"]),
+            ("1.x", &["1.0", "1.99"], &["2.0", "0.9"]),
+            ("0.*", &["0.0.1"], &["1.0"]),
+            ("*", &["0.1", "99.0"], &["1.0-beta"]),
+            ("1.0 - 2.0", &["1.0", "2.0.5"], &["0.9", "2.1"]),
+            ("1.0.0 - 2.1.0", &["2.1.0"], &["2.1.1"]),
+            ("^1.0 || ^3.0", &["1.5", "3.1"], &["2.0", "4.0"]),
+            ("^1.0 | ^3.0", &["1.5", "3.1"], &["2.0"]),
+            ("^1.0@dev", &["1.1-dev", "1.1-alpha", "1.1"], &["2.0-dev"]),
+            ("^1.0@beta", &["1.1-beta1", "1.1-RC1"], &["1.1-alpha1"]),
+            ("@dev", &["1.0-dev", "0.1"], &[]),
+            (
+                ">=1.0-beta",
+                &["1.0-beta2", "1.0-RC", "2.0"],
+                &["1.0-alpha"],
+            ),
+            ("1.0.0-patch1", &["1.0.0-pl1", "1.0.0p1"], &["1.0.0"]),
+            (">1.0@dev", &["1.0", "1.0.1-dev"], &["1.0-dev"]),
+        ];
+        for (constraint, included, excluded) in cases {
+            let parsed = ComposerConstraint::parse(constraint).unwrap();
+            for version in *included {
+                let version = Version::from(version).unwrap();
+                assert!(
+                    parsed.matches(&version),
+                    "{} should match {}",
+                    constraint,
+                    version
+                );
+            }
+            for version in *excluded {
+                let version = Version::from(version).unwrap();
+                assert!(
+                    !parsed.matches(&version),
+                    "{} should not match {}",
+                    constraint,
+                    version
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn stability() {
+        let constraints = [
+            ("^1.0", ComposerStability::Stable),
+            ("^1.0@dev", ComposerStability::Dev),
+            ("^1.0@RC", ComposerStability::Rc),
+            ("^1.0@stable", ComposerStability::Stable),
+            ("^1.0@beta || ^2.0@alpha", ComposerStability::Alpha),
+            ("1.0-dev", ComposerStability::Dev),
+            (">=1.0-beta2 <2.0-alpha", ComposerStability::Alpha),
+            ("^1.0-beta@rc", ComposerStability::Rc),
+        ];
+        for (constraint, stability) in &constraints {
+            assert_eq!(
+                ComposerConstraint::parse(constraint).unwrap().stability(),
+                *stability,
+                "Parsing {:?}",
+                constraint
+            );
+        }
+    }
+
+    #[test]
+    fn parse_error() {
+        let errors = [
+            ("", Error::EmptyClause { at: 0 }),
+            ("^1.0 ||", Error::EmptyClause { at: 5 }),
+            ("|| ^1.0", Error::EmptyClause { at: 0 }),
+            ("^1.0,", Error::EmptyClause { at: 4 }),
+            ("^1.0,,^2.0", Error::EmptyClause { at: 5 }),
+            (">=", Error::MissingVersion { at: 2 }),
+            (">= ", Error::MissingVersion { at: 2 }),
+            ("^", Error::MissingVersion { at: 1 }),
+            ("1.0@foo", Error::UnexpectedChar { at: 4 }),
+            ("=>1.0", Error::UnexpectedChar { at: 1 }),
+            ("^1.0 foo", Error::UnexpectedChar { at: 5 }),
+            ("1.2.3.4.5", Error::UnexpectedChar { at: 7 }),
+            ("1.0-beta.*", Error::UnexpectedChar { at: 8 }),
+            ("dev-main", Error::UnexpectedChar { at: 0 }),
+        ];
+        for (constraint, err) in &errors {
+            assert_eq!(
+                ComposerConstraint::parse(constraint).unwrap_err(),
+                *err,
+                "Parsing {:?}",
+                constraint,
+            );
+        }
+    }
+
+    #[test]
+    fn display() {
+        let constraints = [
+            ("^1.2.3", ">= 1.2.3.0-dev, < 2.0.0.0-dev"),
+            (
+                "~1.2 || 3.0.*",
+                ">= 1.2.0.0-dev, < 2.0.0.0-dev || >= 3.0.0.0-dev, < 3.1.0.0-dev",
+            ),
+            ("1.0", "== 1.0.0.0"),
+            ("*", "*"),
+            ("<>1.2, >1.0", "!= 1.2.0.0, > 1.0.0.0"),
+        ];
+        for (constraint, display) in &constraints {
+            let parsed = ComposerConstraint::parse(constraint).unwrap();
+            assert_eq!(parsed.to_string(), *display, "Parsing {}", constraint);
+            assert_eq!(constraint.parse::<ComposerConstraint>().unwrap(), parsed);
+        }
+    }
+}
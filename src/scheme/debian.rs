@@ -0,0 +1,392 @@
+//! Debian version scheme, which provides the `DebianVersion` struct.
+//!
+//! Debian package versions have the form `[epoch:]upstream[-revision]`, and are ordered like
+//! `dpkg --compare-versions`, using the `verrevcmp` algorithm from dpkg.
+
+use std::cmp::Ordering;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+
+use crate::Error;
+
+/// Debian package version, ordered like `dpkg --compare-versions`.
+///
+/// The epoch is compared first, then the upstream version and revision. A `~` sorts before
+/// anything, even the end of a version, so `1.0~rc1` is less than `1.0`.
+///
+/// # Examples
+///
+/// ```
+/// use version_compare::DebianVersion;
+///
+/// let a = DebianVersion::parse("1:2.30-1ubuntu1").unwrap();
+/// let b = DebianVersion::parse("2.31~rc1-1").unwrap();
+///
+/// assert_eq!(a.epoch(), 1);
+/// assert_eq!(a.upstream(), "2.30");
+/// assert_eq!(a.revision(), Some("1ubuntu1"));
+/// assert!(a > b);
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct DebianVersion<'a> {
+    version: &'a str,
+    epoch: u32,
+    upstream: &'a str,
+    revision: Option<&'a str>,
+}
+
+impl<'a> DebianVersion<'a> {
+    /// Parse a Debian version string.
+    ///
+    /// Surrounding whitespace is ignored. An error is returned for the same versions dpkg rejects:
+    /// empty versions, whitespace within the version, an empty or non-numeric epoch, and an empty
+    /// upstream version or revision. Like dpkg, unusual characters are accepted.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use version_compare::{DebianVersion, Error};
+    ///
+    /// assert!(DebianVersion::parse("7:1-a:b-5").is_ok());
+    /// assert_eq!(DebianVersion::parse("1.0-").unwrap_err(), Error::EmptyPart { at: 4 });
+    /// assert_eq!(DebianVersion::parse("x:1.0").unwrap_err(), Error::UnexpectedChar { at: 0 });
+    /// ```
+    pub fn parse(version: &'a str) -> Result<Self, Error> {
+        let start = version.len() - version.trim_start().len();
+        let version = version.trim();
+        if version.is_empty() {
+            return Err(Error::EmptyPart { at: start });
+        }
+        if let Some(at) = version.find(char::is_whitespace) {
+            return Err(Error::UnexpectedChar { at: start + at });
+        }
+
+        // The epoch ends at the first colon
+        let (epoch, rest, rest_at) = match version.find(':') {
+            Some(colon) => {
+                let epoch = &version[..colon];
+                if epoch.is_empty() {
+                    return Err(Error::EmptyPart { at: start });
+                }
+                if let Some(at) = epoch.find(|c: char| !c.is_ascii_digit()) {
+                    return Err(Error::UnexpectedChar { at: start + at });
+                }
+                let epoch = match epoch.parse::<u32>() {
+                    Ok(epoch) if epoch <= i32::MAX as u32 => epoch,
+                    _ => return Err(Error::NumberOverflow { at: start }),
+                };
+                if colon + 1 == version.len() {
+                    return Err(Error::EmptyPart {
+                        at: start + colon + 1,
+                    });
+                }
+                (epoch, &version[colon + 1..], colon + 1)
+            }
+            None => (0, version, 0),
+        };
+
+        // The revision starts after the last hyphen
+        let (upstream, revision) = match rest.rfind('-') {
+            Some(hyphen) => {
+                if hyphen + 1 == rest.len() {
+                    return Err(Error::EmptyPart {
+                        at: start + rest_at + hyphen + 1,
+                    });
+                }
+                (&rest[..hyphen], Some(&rest[hyphen + 1..]))
+            }
+            None => (rest, None),
+        };
+        if upstream.is_empty() {
+            return Err(Error::EmptyPart {
+                at: start + rest_at,
+            });
+        }
+
+        Ok(DebianVersion {
+            version,
+            epoch,
+            upstream,
+            revision,
+        })
+    }
+
+    /// Get the epoch, `0` if the version has none.
+    pub fn epoch(&self) -> u32 {
+        self.epoch
+    }
+
+    /// Get the upstream version.
+    pub fn upstream(&self) -> &'a str {
+        self.upstream
+    }
+
+    /// Get the Debian revision, if any.
+    ///
+    /// A version without revision is equal to the same version with revision `0`.
+    pub fn revision(&self) -> Option<&'a str> {
+        self.revision
+    }
+
+    /// Get the version string, without surrounding whitespace.
+    pub fn as_str(&self) -> &'a str {
+        self.version
+    }
+}
+
+impl<'a> Ord for DebianVersion<'a> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.epoch
+            .cmp(&other.epoch)
+            .then_with(|| verrevcmp(self.upstream, other.upstream))
+            .then_with(|| verrevcmp(self.revision.unwrap_or(""), other.revision.unwrap_or("")))
+    }
+}
+
+impl<'a> PartialOrd for DebianVersion<'a> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<'a> PartialEq for DebianVersion<'a> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl<'a> Eq for DebianVersion<'a> {}
+
+/// Upstream version and revision are hashed the way `verrevcmp` compares them.
+impl<'a> Hash for DebianVersion<'a> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.epoch.hash(state);
+        hash_verrev(self.upstream, state);
+        hash_verrev(self.revision.unwrap_or(""), state);
+    }
+}
+
+impl<'a> fmt::Display for DebianVersion<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.version)
+    }
+}
+
+/// Get the sort weight of a character in the non-digit part of a version, as dpkg does.
+///
+/// Digits and the end of the string weigh nothing, letters sort before other characters, and `~`
+/// sorts before anything.
+fn order(c: Option<u8>) -> i32 {
+    match c {
+        None => 0,
+        Some(c) if c.is_ascii_digit() => 0,
+        Some(c) if c.is_ascii_alphabetic() => i32::from(c),
+        Some(b'~') => -1,
+        // dpkg reads non-ASCII bytes as negative signed chars, placing them between letters and
+        // other ASCII characters
+        Some(c) if !c.is_ascii() => i32::from(c),
+        Some(c) => i32::from(c) + 256,
+    }
+}
+
+/// Compare an upstream version or revision, as dpkg's `verrevcmp` does.
+///
+/// The strings are compared as alternating non-digit and digit runs. Non-digit runs are compared
+/// character by character using `order`, digit runs are compared numerically.
+fn verrevcmp(a: &str, b: &str) -> Ordering {
+    let (a, b) = (a.as_bytes(), b.as_bytes());
+    let is_digit = |s: &[u8], i: usize| s.get(i).map_or(false, u8::is_ascii_digit);
+    let (mut i, mut j) = (0, 0);
+
+    while i < a.len() || j < b.len() {
+        while (i < a.len() && !is_digit(a, i)) || (j < b.len() && !is_digit(b, j)) {
+            let (ac, bc) = (order(a.get(i).copied()), order(b.get(j).copied()));
+            if ac != bc {
+                return ac.cmp(&bc);
+            }
+            i += 1;
+            j += 1;
+        }
+
+        // Compare numbers without leading zeros, a longer number is greater
+        while a.get(i) == Some(&b'0') {
+            i += 1;
+        }
+        while b.get(j) == Some(&b'0') {
+            j += 1;
+        }
+        let mut first_diff = Ordering::Equal;
+        while is_digit(a, i) && is_digit(b, j) {
+            if first_diff == Ordering::Equal {
+                first_diff = a[i].cmp(&b[j]);
+            }
+            i += 1;
+            j += 1;
+        }
+        if is_digit(a, i) {
+            return Ordering::Greater;
+        }
+        if is_digit(b, j) {
+            return Ordering::Less;
+        }
+        if first_diff != Ordering::Equal {
+            return first_diff;
+        }
+    }
+
+    Ordering::Equal
+}
+
+/// Hash an upstream version or revision, consistent with `verrevcmp`.
+///
+/// Hashes each non-digit run with the following digit run without leading zeros. Trailing runs
+/// that are both empty are skipped, because a missing number equals zero.
+fn hash_verrev<H: Hasher>(version: &str, state: &mut H) {
+    let mut rest = version.as_bytes();
+    let mut empty = 0;
+    while !rest.is_empty() {
+        let text_len = rest.iter().take_while(|c| !c.is_ascii_digit()).count();
+        let (text, tail) = rest.split_at(text_len);
+        let digits_len = tail.iter().take_while(|c| c.is_ascii_digit()).count();
+        let (digits, tail) = tail.split_at(digits_len);
+        let zeros = digits.iter().take_while(|&&c| c == b'0').count();
+        let digits = &digits[zeros..];
+        rest = tail;
+
+        if text.is_empty() && digits.is_empty() {
+            empty += 1;
+            continue;
+        }
+        for _ in 0..empty {
+            true.hash(state);
+            (&[] as &[u8]).hash(state);
+            (&[] as &[u8]).hash(state);
+        }
+        empty = 0;
+        true.hash(state);
+        text.hash(state);
+        digits.hash(state);
+    }
+    false.hash(state);
+}
+
+#[cfg_attr(tarpaulin, skip)]
+#[cfg(test)]
+mod tests {
+
+    use crate::test::hash_of;
+    use crate::{Cmp, Error};
+
+    use super::DebianVersion;
+
+    /// Version comparisons, checked against `dpkg --compare-versions`, many taken from dpkg's own
+    /// test suite.
+    const COMBIS: &[(&str, &str, Cmp)] = &[
+        ("1.0", "1.0", Cmp::Eq),
+        ("1.0", "2.0", Cmp::Lt),
+        ("2.0", "1.0", Cmp::Gt),
+        ("2.0.0", "2.0", Cmp::Gt),
+        ("2.0", "2.0.0", Cmp::Lt),
+        ("2.0.0", "2.0.0", Cmp::Eq),
+        ("1.2.3", "1.2.3", Cmp::Eq),
+        ("4.4.3-2", "4.4.3-2", Cmp::Eq),
+        ("1:2ab:5", "1:2ab:5", Cmp::Eq),
+        ("7:1-a:b-5", "7:1-a:b-5", Cmp::Eq),
+        ("57:1.2.3abYZ+~-4-5", "57:1.2.3abYZ+~-4-5", Cmp::Eq),
+        ("1.2.3", "0:1.2.3", Cmp::Eq),
+        ("1.2.3", "1.2.3-0", Cmp::Eq),
+        ("009", "9", Cmp::Eq),
+        ("99:3", "99:3", Cmp::Eq),
+        ("1.2.3", "1:1.2.3", Cmp::Lt),
+        ("1.2.3", "1.2.4", Cmp::Lt),
+        ("1.2.4", "1.2.3", Cmp::Gt),
+        ("0002", "1", Cmp::Gt),
+        ("1.2.3", "1.2.3-1", Cmp::Lt),
+        ("1.2.3~rc1", "1.2.3", Cmp::Lt),
+        ("1.2.3~rc1", "1.2.3~rc2", Cmp::Lt),
+        ("1.2.3~rc1", "1.2.3~~", Cmp::Gt),
+        ("1.2.3~", "1.2.3~~", Cmp::Gt),
+        ("1.0~", "1.0", Cmp::Lt),
+        ("1.0+dfsg", "1.0", Cmp::Gt),
+        ("1.0a", "1.0", Cmp::Gt),
+        ("1.0a", "1.0.", Cmp::Lt),
+        ("1.0a", "1.0+", Cmp::Lt),
+        ("1.0-1", "1.0-1.1", Cmp::Lt),
+        ("1.0-1ubuntu1", "1.0-1", Cmp::Gt),
+        ("1.0-1~bpo1", "1.0-1", Cmp::Lt),
+        ("1:0.4", "10.3", Cmp::Gt),
+        ("1.2.3-1", "1.2.3-1.0", Cmp::Lt),
+        ("2.7.4-0ubuntu1", "2.7.4-0ubuntu1.6", Cmp::Lt),
+        ("0.9.8", "0.9.10", Cmp::Lt),
+        ("1.18.36-0.17.35", "1.18.36-19", Cmp::Lt),
+        ("1:1.2.13-3", "1.2.13-5", Cmp::Gt),
+        ("a", "0a", Cmp::Gt),
+    ];
+
+    #[test]
+    fn parse() {
+        let version = DebianVersion::parse(" 7:1-a:b-5\n").unwrap();
+        assert_eq!(version.epoch(), 7);
+        assert_eq!(version.upstream(), "1-a:b");
+        assert_eq!(version.revision(), Some("5"));
+        assert_eq!(version.as_str(), "7:1-a:b-5");
+
+        let version = DebianVersion::parse("1.0").unwrap();
+        assert_eq!(version.epoch(), 0);
+        assert_eq!(version.upstream(), "1.0");
+        assert_eq!(version.revision(), None);
+    }
+
+    #[test]
+    fn parse_error() {
+        let errors = [
+            ("", Error::EmptyPart { at: 0 }),
+            ("  ", Error::EmptyPart { at: 2 }),
+            ("1.0 2", Error::UnexpectedChar { at: 3 }),
+            (":1.0", Error::EmptyPart { at: 0 }),
+            ("1:", Error::EmptyPart { at: 2 }),
+            ("a:1.0", Error::UnexpectedChar { at: 0 }),
+            ("-1:1.0", Error::UnexpectedChar { at: 0 }),
+            ("2147483648:1.0", Error::NumberOverflow { at: 0 }),
+            ("1.0-", Error::EmptyPart { at: 4 }),
+            ("1:-1", Error::EmptyPart { at: 2 }),
+        ];
+        for (version, err) in &errors {
+            assert_eq!(
+                DebianVersion::parse(version).as_ref(),
+                Err(err),
+                "Parsing {:?}",
+                version,
+            );
+        }
+        assert!(DebianVersion::parse("2147483647:1.0").is_ok());
+    }
+
+    #[test]
+    fn compare() {
+        for (a, b, cmp) in COMBIS {
+            let (a, b) = (
+                DebianVersion::parse(a).unwrap(),
+                DebianVersion::parse(b).unwrap(),
+            );
+            assert_eq!(Cmp::from(a.cmp(&b)), *cmp, "Comparing {} to {}", a, b);
+            assert_eq!(Cmp::from(b.cmp(&a)), cmp.flip(), "Comparing {} to {}", b, a);
+            if *cmp == Cmp::Eq {
+                assert_eq!(hash_of(&a), hash_of(&b), "Hashing {} and {}", a, b);
+            }
+        }
+    }
+
+    #[test]
+    fn hash_eq() {
+        let equal = [("1.02", "1.2-0"), ("0:1.0", "1.0-00"), ("1.0a0", "1.0a")];
+        for (a, b) in &equal {
+            let (a, b) = (
+                DebianVersion::parse(a).unwrap(),
+                DebianVersion::parse(b).unwrap(),
+            );
+            assert_eq!(a, b);
+            assert_eq!(hash_of(&a), hash_of(&b), "Hashing {} and {}", a, b);
+        }
+    }
+}
@@ -0,0 +1,295 @@
+//! RubyGems requirement module, which provides the `GemRequirement` struct.
+//!
+//! A requirement is a list of comma separated clauses, such as `~> 7.0, >= 7.0.4`, as found in
+//! `Gemfile.lock` files. A version matches the requirement if it matches all clauses.
+
+use std::cmp::Ordering;
+use std::fmt;
+use std::str::FromStr;
+
+use crate::{Cmp, Error, GemVersion, Part, Version};
+
+/// RubyGems version requirement, compatible with `Gem::Requirement`.
+///
+/// Supports the operators `=`, `!=`, `<`, `<=`, `>`, `>=` and the pessimistic operator `~>`. A
+/// clause without operator means `=`. `~> 2.2` matches `2.2` up to but excluding `3`, and
+/// `~> 2.2.0` matches `2.2.0` up to but excluding `2.3`.
+///
+/// Versions are always compared with the RubyGems scheme, like `GemVersion`.
+///
+/// # Examples
+///
+/// ```
+/// use version_compare::{GemRequirement, Version};
+///
+/// let req = GemRequirement::parse("~> 7.0, >= 7.0.4").unwrap();
+///
+/// assert!(req.matches(&Version::from("7.1.2").unwrap()));
+/// assert!(!req.matches(&Version::from("7.0.3").unwrap()));
+/// assert!(!req.matches(&Version::from("8.0").unwrap()));
+/// ```
+#[derive(Debug, Clone)]
+pub struct GemRequirement {
+    clauses: Vec<(Operator, String)>,
+}
+
+/// Requirement clause operator.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Operator {
+    /// Plain comparison operator.
+    Cmp(Cmp),
+
+    /// Pessimistic operator `~>`.
+    Pessimistic,
+}
+
+/// Operator signs, longest first.
+const OPERATORS: &[&str] = &["~>", "!=", "<=", ">=", "=", "<", ">"];
+
+impl GemRequirement {
+    /// Parse a requirement, made of comma separated clauses.
+    ///
+    /// An error is returned for malformed clauses, such as an empty clause, an unknown operator,
+    /// or an invalid version.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use version_compare::{Error, GemRequirement};
+    ///
+    /// assert!(GemRequirement::parse("1.2.3").is_ok());
+    /// assert_eq!(GemRequirement::parse("> blah").unwrap_err(), Error::UnexpectedChar { at: 2 });
+    /// assert_eq!(GemRequirement::parse(">= 1.0,").unwrap_err(), Error::EmptyClause { at: 6 });
+    /// ```
+    pub fn parse(req: &str) -> Result<Self, Error> {
+        let mut clauses = Vec::new();
+        let mut at = 0;
+        for clause in req.split(',') {
+            // Point to the comma ending an empty clause, or the one before a trailing one
+            if clause.trim().is_empty() {
+                let end = at + clause.len();
+                let at = if end < req.len() || at == 0 {
+                    end
+                } else {
+                    at - 1
+                };
+                return Err(Error::EmptyClause { at });
+            }
+
+            let start = clause.len() - clause.trim_start().len();
+            let trimmed = clause.trim_start();
+            let (operator, sign) = match OPERATORS.iter().find(|sign| trimmed.starts_with(*sign)) {
+                Some(&"~>") => (Operator::Pessimistic, "~>"),
+                Some(sign) => (Operator::Cmp(Cmp::from_sign(sign)?), *sign),
+                None => (Operator::Cmp(Cmp::Eq), ""),
+            };
+            let version_at = at + start + sign.len();
+            let version = &trimmed[sign.len()..];
+            if version.trim().is_empty() {
+                return Err(Error::MissingVersion {
+                    at: version_at + version.len(),
+                });
+            }
+            let version = GemVersion::parse(version).map_err(|err| err.offset(version_at))?;
+
+            clauses.push((operator, version.as_str().to_string()));
+            at += clause.len() + 1;
+        }
+
+        Ok(GemRequirement { clauses })
+    }
+
+    /// Check whether the given `version` matches all clauses of this requirement.
+    ///
+    /// A version that isn't a valid RubyGems version never matches.
+    pub fn matches(&self, version: &Version) -> bool {
+        let version = match GemVersion::parse(version.as_str()) {
+            Ok(version) => version,
+            Err(_) => return false,
+        };
+        self.clauses.iter().all(|(operator, req)| {
+            // Versions are validated when parsing
+            let req = match GemVersion::parse(req) {
+                Ok(req) => req,
+                Err(_) => unreachable!(),
+            };
+            matches_clause(*operator, &version, &req)
+        })
+    }
+}
+
+impl FromStr for GemRequirement {
+    type Err = Error;
+
+    fn from_str(req: &str) -> Result<Self, Self::Err> {
+        GemRequirement::parse(req)
+    }
+}
+
+impl fmt::Display for GemRequirement {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (i, (operator, version)) in self.clauses.iter().enumerate() {
+            if i > 0 {
+                write!(f, ", ")?;
+            }
+            let sign = match operator {
+                Operator::Cmp(Cmp::Eq) => "=",
+                Operator::Cmp(cmp) => cmp.sign(),
+                Operator::Pessimistic => "~>",
+            };
+            write!(f, "{} {}", sign, version)?;
+        }
+        Ok(())
+    }
+}
+
+/// Check whether `version` matches a clause with the given `operator` and version `req`.
+fn matches_clause(operator: Operator, version: &GemVersion, req: &GemVersion) -> bool {
+    match operator {
+        Operator::Cmp(cmp) => match version.cmp(req) {
+            Ordering::Less => matches!(cmp, Cmp::Ne | Cmp::Lt | Cmp::Le),
+            Ordering::Equal => matches!(cmp, Cmp::Eq | Cmp::Le | Cmp::Ge),
+            Ordering::Greater => matches!(cmp, Cmp::Ne | Cmp::Gt | Cmp::Ge),
+        },
+        Operator::Pessimistic => version >= req && below_bump(version, req),
+    }
+}
+
+/// Check whether the release of `version` is less than the bumped release of `req`, like
+/// `version.release < req.bump` in RubyGems.
+///
+/// Bumping drops the alphabetic segments and the last number if there's more than one, and
+/// increments the new last number. So a release is less if all numbers before the last number of
+/// the bumped version are equal, and the last one is at most the unbumped number.
+fn below_bump(version: &GemVersion, req: &GemVersion) -> bool {
+    let release = req.release_segments();
+    let bumped = &release[..release.len().max(2) - 1];
+    let zero = Part::Number(0.into());
+    for (i, segment) in bumped.iter().enumerate() {
+        let own = version.release_segments().get(i).unwrap_or(&zero);
+        let ord = match (own, segment) {
+            (Part::Number(a), Part::Number(b)) => a.cmp(b),
+            _ => unreachable!("release segments are numeric"),
+        };
+        if i + 1 == bumped.len() {
+            return ord != Ordering::Greater;
+        }
+        if ord != Ordering::Equal {
+            return ord == Ordering::Less;
+        }
+    }
+    unreachable!("release has a number")
+}
+
+#[cfg_attr(tarpaulin, skip)]
+#[cfg(test)]
+mod tests {
+    use crate::{Error, Version};
+
+    use super::GemRequirement;
+
+    #[test]
+    fn matches() {
+        let cases: &[(&str, &[&str], &[&str])] = &[
+            ("1.2", &["1.2", "1.2.0"], &["1.1", "1.3"]),
+            ("= 1.2", &["1.2"], &["1.1", "1.3"]),
+            ("!= 1.2", &["1.3"], &["1.2"]),
+            ("> 1.2", &["1.3"], &["1.1", "1.2"]),
+            (">= 1.2", &["1.2", "1.3"], &["1.1"]),
+            ("< 1.2", &["1.1"], &["1.2", "1.3"]),
+            ("<= 1.2", &["1.1", "1.2"], &["1.3"]),
+            ("> 1.1, < 1.3", &["1.2"], &["1.1", "1.3"]),
+            (
+                ">= 1.4, <= 1.6, != 1.5",
+                &["1.4", "1.6"],
+                &["1.3", "1.5", "1.7", "2.0"],
+            ),
+            ("~> 1.2", &["1.2", "1.3"], &["1.1", "2.0"]),
+            ("~> 0.0.1", &["0.0.1", "0.0.2"], &["0.1.1"]),
+            ("~> 1.0", &["1.0", "1.1", "1.9.9"], &["2.0", "2.0.a"]),
+            ("~> 2.0", &["2.0.1"], &[]),
+            ("~> 1.4", &["1.4", "1.5"], &["1.3", "2.0"]),
+            (
+                "~> 1.4.4",
+                &["1.4.4", "1.4.5"],
+                &["1.3", "1.4", "1.5", "2.0"],
+            ),
+            ("~> 1.0.0", &["1.0.1.a"], &["1.1.pre"]),
+            ("~> 1.1", &[], &["1.1.pre"]),
+            ("~> 1.9.a", &["1.9", "1.9.b"], &["2.0"]),
+            ("~> 1", &["1.0", "1.9"], &["2.0"]),
+            ("= 1.0", &["1.0", "1.0.0", "1.0.0.0"], &["1.0.0.1"]),
+            ("> 0.0.0", &["0.2", "0.0.0.0.0.2"], &[]),
+            ("> 0.0.0.1", &["0.0.1.0"], &[]),
+            ("> 1.111", &["1.112"], &[]),
+            (">= 9.3.2", &[" 9.3.2", "9.3.2 "], &[]),
+            ("= 0", &[""], &[]),
+            (" <  0.1", &["", "  "], &[]),
+        ];
+        for (req, included, excluded) in cases {
+            let parsed = GemRequirement::parse(req).unwrap();
+            for version in *included {
+                let version = Version::from(version).unwrap();
+                assert!(parsed.matches(&version), "{} should match {}", req, version);
+            }
+            for version in *excluded {
+                let version = Version::from(version).unwrap();
+                assert!(
+                    !parsed.matches(&version),
+                    "{} should not match {}",
+                    req,
+                    version
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn parse_error() {
+        let errors = [
+            ("", Error::EmptyClause { at: 0 }),
+            (">= 1.0,", Error::EmptyClause { at: 6 }),
+            (", >= 1.0", Error::EmptyClause { at: 0 }),
+            (">=", Error::MissingVersion { at: 2 }),
+            ("~> ", Error::MissingVersion { at: 3 }),
+            (">>> 1.3.5", Error::UnexpectedChar { at: 1 }),
+            ("> blah", Error::UnexpectedChar { at: 2 }),
+            ("=> 1.0", Error::UnexpectedChar { at: 1 }),
+            ("==1.2", Error::UnexpectedChar { at: 1 }),
+            ("~> 1.0, ~> 1.0 beta", Error::UnexpectedChar { at: 14 }),
+        ];
+        for (req, err) in &errors {
+            assert_eq!(
+                GemRequirement::parse(req).unwrap_err(),
+                *err,
+                "Parsing {:?}",
+                req,
+            );
+        }
+    }
+
+    #[test]
+    fn display() {
+        let reqs = [
+            ("~> 7.0, >= 7.0.4", "~> 7.0, >= 7.0.4"),
+            ("1.2", "= 1.2"),
+            ("!=1.2,<2", "!= 1.2, < 2"),
+            (" ~>1.0-beta ", "~> 1.0-beta"),
+        ];
+        for (req, display) in &reqs {
+            assert_eq!(GemRequirement::parse(req).unwrap().to_string(), *display);
+            assert_eq!(
+                display.parse::<GemRequirement>().unwrap().to_string(),
+                *display
+            );
+        }
+    }
+
+    #[test]
+    fn matches_invalid() {
+        let req = GemRequirement::parse(">= 1.0").unwrap();
+        assert!(req.matches(&Version::from("1.1-beta.1").unwrap()));
+        assert!(!req.matches(&Version::from("1.0 beta").unwrap()));
+        assert!(!req.matches(&Version::from("1.0_1").unwrap()));
+    }
+}
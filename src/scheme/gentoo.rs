@@ -0,0 +1,412 @@
+//! Gentoo version scheme, which provides the `GentooVersion` struct.
+//!
+//! Gentoo package versions have the form `{number}{.number}...{letter}{_suffix{number}}...{-rN}`,
+//! and are ordered using the version comparison algorithm of the Package Manager Specification.
+
+use std::cmp::Ordering;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+
+use crate::{Error, Number};
+
+/// Gentoo package version, ordered like the Package Manager Specification.
+///
+/// The first number is compared numerically. Later numbers with a leading zero are compared as
+/// fractions, so `1.01` is less than `1.1`, and `1.010` equals `1.01`. The suffixes `_alpha`,
+/// `_beta`, `_pre` and `_rc` sort before a release, and `_p` after it. The revision `-rN` is
+/// compared last, a missing revision being equal to `-r0`.
+///
+/// # Examples
+///
+/// ```
+/// use version_compare::GentooVersion;
+///
+/// let a = GentooVersion::parse("1.2b_rc1-r3").unwrap();
+/// let b = GentooVersion::parse("1.2b").unwrap();
+/// let c = GentooVersion::parse("1.2b_p1").unwrap();
+///
+/// assert_eq!(a.numbers(), &["1", "2"]);
+/// assert_eq!(a.letter(), Some('b'));
+/// assert_eq!(a.revision(), Some(3.into()));
+/// assert!(a < b);
+/// assert!(b < c);
+/// ```
+#[derive(Debug, Clone)]
+pub struct GentooVersion<'a> {
+    version: &'a str,
+    numbers: Vec<&'a str>,
+    letter: Option<char>,
+    suffixes: Vec<(Suffix, &'a str)>,
+    revision: Option<&'a str>,
+}
+
+/// Version suffix, in sort order.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+enum Suffix {
+    Alpha,
+    Beta,
+    Pre,
+    Rc,
+    P,
+}
+
+impl<'a> GentooVersion<'a> {
+    /// Parse a Gentoo version string.
+    ///
+    /// An error is returned for versions the Package Manager Specification doesn't allow, such as
+    /// an empty number, an unknown suffix, more than one letter, or a revision without `-r`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use version_compare::{Error, GentooVersion};
+    ///
+    /// assert!(GentooVersion::parse("1.0_alpha1_p20240101-r2").is_ok());
+    /// assert_eq!(GentooVersion::parse("1.0_git").unwrap_err(), Error::UnexpectedChar { at: 4 });
+    /// assert_eq!(GentooVersion::parse("1.0-1").unwrap_err(), Error::UnexpectedChar { at: 4 });
+    /// assert_eq!(GentooVersion::parse("1..0").unwrap_err(), Error::EmptyPart { at: 2 });
+    /// ```
+    pub fn parse(version: &'a str) -> Result<Self, Error> {
+        let bytes = version.as_bytes();
+        let digits = |at: usize| {
+            let len = bytes[at..]
+                .iter()
+                .take_while(|c| c.is_ascii_digit())
+                .count();
+            &version[at..at + len]
+        };
+
+        // Dot separated numbers
+        let mut numbers = Vec::new();
+        let mut at = 0;
+        loop {
+            let number = digits(at);
+            if number.is_empty() {
+                return Err(Error::EmptyPart { at });
+            }
+            numbers.push(number);
+            at += number.len();
+            if bytes.get(at) != Some(&b'.') {
+                break;
+            }
+            at += 1;
+        }
+
+        // Optional single letter
+        let letter = match bytes.get(at) {
+            Some(c) if c.is_ascii_lowercase() => {
+                at += 1;
+                Some(*c as char)
+            }
+            _ => None,
+        };
+
+        // Any number of suffixes, each with an optional number
+        let mut suffixes = Vec::new();
+        while bytes.get(at) == Some(&b'_') {
+            at += 1;
+            let len = bytes[at..]
+                .iter()
+                .take_while(|c| c.is_ascii_lowercase())
+                .count();
+            let suffix = match &version[at..at + len] {
+                "alpha" => Suffix::Alpha,
+                "beta" => Suffix::Beta,
+                "pre" => Suffix::Pre,
+                "rc" => Suffix::Rc,
+                "p" => Suffix::P,
+                "" => return Err(Error::EmptyPart { at }),
+                _ => return Err(Error::UnexpectedChar { at }),
+            };
+            at += len;
+            let number = digits(at);
+            suffixes.push((suffix, number));
+            at += number.len();
+        }
+
+        // Optional revision
+        let revision = match bytes.get(at) {
+            Some(b'-') if bytes.get(at + 1) == Some(&b'r') => {
+                at += 2;
+                let revision = digits(at);
+                if revision.is_empty() {
+                    return Err(Error::EmptyPart { at });
+                }
+                at += revision.len();
+                Some(revision)
+            }
+            Some(b'-') => return Err(Error::UnexpectedChar { at: at + 1 }),
+            _ => None,
+        };
+
+        if at < bytes.len() {
+            return Err(Error::UnexpectedChar { at });
+        }
+
+        Ok(GentooVersion {
+            version,
+            numbers,
+            letter,
+            suffixes,
+            revision,
+        })
+    }
+
+    /// Get the dot separated numbers, as written.
+    pub fn numbers(&self) -> &[&'a str] {
+        &self.numbers
+    }
+
+    /// Get the letter following the numbers, if any.
+    pub fn letter(&self) -> Option<char> {
+        self.letter
+    }
+
+    /// Get the version without the revision.
+    pub fn version(&self) -> &'a str {
+        match self.revision {
+            Some(revision) => &self.version[..self.version.len() - revision.len() - 2],
+            None => self.version,
+        }
+    }
+
+    /// Get the revision, the number after `-r`, if any.
+    pub fn revision(&self) -> Option<Number<'a>> {
+        self.revision.and_then(Number::parse)
+    }
+
+    /// Get the full version string.
+    pub fn as_str(&self) -> &'a str {
+        self.version
+    }
+}
+
+impl<'a> Ord for GentooVersion<'a> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        // The first number is compared numerically, the others like fractions
+        let numbers = cmp_numbers(self.numbers[0], other.numbers[0]).then_with(|| {
+            self.numbers[1..]
+                .iter()
+                .zip(&other.numbers[1..])
+                .map(|(a, b)| cmp_fractions(a, b))
+                .find(|ord| *ord != Ordering::Equal)
+                .unwrap_or_else(|| self.numbers.len().cmp(&other.numbers.len()))
+        });
+
+        numbers
+            .then_with(|| self.letter.cmp(&other.letter))
+            .then_with(|| cmp_suffixes(&self.suffixes, &other.suffixes))
+            .then_with(|| cmp_numbers(self.revision.unwrap_or("0"), other.revision.unwrap_or("0")))
+    }
+}
+
+impl<'a> PartialOrd for GentooVersion<'a> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<'a> PartialEq for GentooVersion<'a> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl<'a> Eq for GentooVersion<'a> {}
+
+/// Numbers are hashed like PMS compares them: the first without leading zeros, and others with
+/// a leading zero without trailing zeros.
+impl<'a> Hash for GentooVersion<'a> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.numbers.len().hash(state);
+        self.numbers[0].trim_start_matches('0').hash(state);
+        for number in &self.numbers[1..] {
+            if number.starts_with('0') {
+                number.trim_end_matches('0').hash(state);
+            } else {
+                number.hash(state);
+            }
+        }
+        self.letter.hash(state);
+        self.suffixes.len().hash(state);
+        for (suffix, number) in &self.suffixes {
+            suffix.hash(state);
+            number.trim_start_matches('0').hash(state);
+        }
+        self.revision
+            .unwrap_or("")
+            .trim_start_matches('0')
+            .hash(state);
+    }
+}
+
+impl<'a> fmt::Display for GentooVersion<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.version)
+    }
+}
+
+/// Compare two strings of digits numerically, an empty string being zero.
+fn cmp_numbers(a: &str, b: &str) -> Ordering {
+    let (a, b) = (a.trim_start_matches('0'), b.trim_start_matches('0'));
+    a.len().cmp(&b.len()).then_with(|| a.cmp(b))
+}
+
+/// Compare two numbers following a dot.
+///
+/// If any has a leading zero, both are compared as strings with trailing zeros stripped.
+fn cmp_fractions(a: &str, b: &str) -> Ordering {
+    if a.starts_with('0') || b.starts_with('0') {
+        a.trim_end_matches('0').cmp(b.trim_end_matches('0'))
+    } else {
+        cmp_numbers(a, b)
+    }
+}
+
+/// Compare two lists of suffixes.
+///
+/// If one list is a prefix of the other, the longer one is greater if its next suffix is `_p`.
+fn cmp_suffixes(a: &[(Suffix, &str)], b: &[(Suffix, &str)]) -> Ordering {
+    for ((a, an), (b, bn)) in a.iter().zip(b) {
+        let ord = a.cmp(b).then_with(|| cmp_numbers(an, bn));
+        if ord != Ordering::Equal {
+            return ord;
+        }
+    }
+    match (a.get(b.len()), b.get(a.len())) {
+        (Some((Suffix::P, _)), _) => Ordering::Greater,
+        (Some(_), _) => Ordering::Less,
+        (_, Some((Suffix::P, _))) => Ordering::Less,
+        (_, Some(_)) => Ordering::Greater,
+        _ => Ordering::Equal,
+    }
+}
+
+#[cfg_attr(tarpaulin, skip)]
+#[cfg(test)]
+mod tests {
+
+    use crate::test::hash_of;
+    use crate::{Cmp, Error};
+
+    use super::GentooVersion;
+
+    /// Version comparisons, as Portage's `vercmp` reports them.
+    const VERCMP: &[(&str, &str, Cmp)] = &[
+        ("4.0", "4.0", Cmp::Eq),
+        ("6.0", "5.0", Cmp::Gt),
+        ("5.0", "5", Cmp::Gt),
+        ("1.0.0", "1.0", Cmp::Gt),
+        ("01.0", "1.0", Cmp::Eq),
+        (
+            "999999999999999999999999999999",
+            "999999999999999999999999999998",
+            Cmp::Gt,
+        ),
+        ("1.01", "1.1", Cmp::Lt),
+        ("1.05", "1.5", Cmp::Lt),
+        ("1.10", "1.9", Cmp::Gt),
+        ("1.10", "1.010", Cmp::Gt),
+        ("1.010", "1.01", Cmp::Eq),
+        ("1.0", "1.00", Cmp::Eq),
+        (
+            "1.001000000000000000001",
+            "1.001000000000000000002",
+            Cmp::Lt,
+        ),
+        ("1.00100000000", "1.0010000000000000001", Cmp::Lt),
+        ("1b", "1", Cmp::Gt),
+        ("1.1b", "1.1", Cmp::Gt),
+        ("1.0b", "1.0.0", Cmp::Lt),
+        ("12.2b", "12.2.5", Cmp::Lt),
+        ("1.2a", "1.2b", Cmp::Lt),
+        ("1b_p1", "1_p1", Cmp::Gt),
+        ("1.0_alpha", "1.0", Cmp::Lt),
+        ("1.0_alpha1", "1.0_beta1", Cmp::Lt),
+        ("1.0_beta3", "1.0_rc3", Cmp::Lt),
+        ("1.0_pre2", "1.0_p2", Cmp::Lt),
+        ("1.0_alpha2", "1.0_p2", Cmp::Lt),
+        ("1.0_rc1", "1.0_rc2", Cmp::Lt),
+        ("1.0_rc10", "1.0_rc9", Cmp::Gt),
+        ("1.0_p1", "1.0", Cmp::Gt),
+        ("1.0_p", "1.0_p0", Cmp::Eq),
+        ("1.0_alpha_p1", "1.0_alpha", Cmp::Gt),
+        ("1.0_alpha_beta", "1.0_alpha", Cmp::Lt),
+        ("1.0_p1", "1.0.1", Cmp::Lt),
+        ("1.0-r1", "1.0-r0", Cmp::Gt),
+        ("1.0-r1", "1.0", Cmp::Gt),
+        ("1.0-r0", "1.0", Cmp::Eq),
+        ("1.0-r10", "1.0-r9", Cmp::Gt),
+        ("1.0_rc1-r5", "1.0", Cmp::Lt),
+        ("1.0_p1-r0", "1.0-r5", Cmp::Gt),
+    ];
+
+    #[test]
+    fn parse() {
+        let version = GentooVersion::parse("1.02.3z_alpha_p20240101-r10").unwrap();
+        assert_eq!(version.numbers(), &["1", "02", "3"]);
+        assert_eq!(version.letter(), Some('z'));
+        assert_eq!(version.version(), "1.02.3z_alpha_p20240101");
+        assert_eq!(version.revision(), Some(10.into()));
+        assert_eq!(version.as_str(), "1.02.3z_alpha_p20240101-r10");
+
+        let version = GentooVersion::parse("2024").unwrap();
+        assert_eq!(version.numbers(), &["2024"]);
+        assert_eq!(version.letter(), None);
+        assert_eq!(version.revision(), None);
+    }
+
+    #[test]
+    fn parse_error() {
+        let errors = [
+            ("", Error::EmptyPart { at: 0 }),
+            ("a1", Error::EmptyPart { at: 0 }),
+            ("1.", Error::EmptyPart { at: 2 }),
+            ("1..0", Error::EmptyPart { at: 2 }),
+            ("1.0ab", Error::UnexpectedChar { at: 4 }),
+            ("1.0a1", Error::UnexpectedChar { at: 4 }),
+            ("1.0A", Error::UnexpectedChar { at: 3 }),
+            ("1.0_", Error::EmptyPart { at: 4 }),
+            ("1.0_git", Error::UnexpectedChar { at: 4 }),
+            ("1.0_alpha.1", Error::UnexpectedChar { at: 9 }),
+            ("1.0-1", Error::UnexpectedChar { at: 4 }),
+            ("1.0-r", Error::EmptyPart { at: 5 }),
+            ("1.0-r1-r1", Error::UnexpectedChar { at: 6 }),
+            ("1.0-r1_p1", Error::UnexpectedChar { at: 6 }),
+            ("1.0 ", Error::UnexpectedChar { at: 3 }),
+        ];
+        for (version, err) in &errors {
+            assert_eq!(
+                GentooVersion::parse(version).unwrap_err(),
+                *err,
+                "Parsing {:?}",
+                version,
+            );
+        }
+    }
+
+    #[test]
+    fn compare() {
+        for (a, b, cmp) in VERCMP {
+            let (a, b) = (
+                GentooVersion::parse(a).unwrap(),
+                GentooVersion::parse(b).unwrap(),
+            );
+            assert_eq!(Cmp::from(a.cmp(&b)), *cmp, "Comparing {} to {}", a, b);
+            assert_eq!(Cmp::from(b.cmp(&a)), cmp.flip(), "Comparing {} to {}", b, a);
+        }
+    }
+
+    #[test]
+    fn hash_eq() {
+        for (a, b, cmp) in VERCMP {
+            if *cmp == Cmp::Eq {
+                let (a, b) = (
+                    GentooVersion::parse(a).unwrap(),
+                    GentooVersion::parse(b).unwrap(),
+                );
+                assert_eq!(hash_of(&a), hash_of(&b), "Hashing {} and {}", a, b);
+            }
+        }
+    }
+}
@@ -0,0 +1,422 @@
+//! Go module version scheme, which provides the `GoVersion` struct.
+//!
+//! Go module versions are semantic versions with a `v` prefix, such as `v1.2.3`, optionally with
+//! a `+incompatible` suffix. Pseudo-versions, such as `v0.0.0-20191109021931-daa7c04131f5`,
+//! refer to a specific commit, and embed a UTC timestamp and a revision in their pre-release.
+
+use std::cmp::Ordering;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+
+use crate::{Error, Number};
+
+use super::SemverVersion;
+
+/// Go module version, ordered like the `go` command.
+///
+/// Versions are ordered as semantic versions, ignoring the `+incompatible` suffix. The timestamp
+/// in pseudo-versions has a fixed length, so pseudo-versions for the same base are ordered by
+/// commit time, after their base version and before the next release.
+///
+/// # Examples
+///
+/// ```
+/// use version_compare::GoVersion;
+///
+/// let a = GoVersion::parse("v1.2.3").unwrap();
+/// let b = GoVersion::parse("v1.2.4-0.20191109021931-daa7c04131f5").unwrap();
+/// let c = GoVersion::parse("v1.2.4").unwrap();
+///
+/// assert!(b.is_pseudo());
+/// assert_eq!(b.pseudo_base(), Some("v1.2.3".into()));
+/// assert_eq!(b.pseudo_timestamp(), Some("20191109021931"));
+/// assert_eq!(b.pseudo_revision(), Some("daa7c04131f5"));
+/// assert!(a < b);
+/// assert!(b < c);
+/// ```
+#[derive(Debug, Clone)]
+pub struct GoVersion<'a> {
+    version: &'a str,
+    semver: SemverVersion<'a>,
+    pseudo: Option<Pseudo<'a>>,
+}
+
+/// The parts of a pseudo-version.
+#[derive(Debug, Clone, Copy)]
+struct Pseudo<'a> {
+    base: Base<'a>,
+    timestamp: &'a str,
+    revision: &'a str,
+}
+
+/// The kind of version a pseudo-version is based on.
+#[derive(Debug, Clone, Copy)]
+enum Base<'a> {
+    /// `vX.0.0-yyyymmddhhmmss-abcdef123456`, without base version.
+    Missing,
+
+    /// `vX.Y.(Z+1)-0.yyyymmddhhmmss-abcdef123456`, based on release `vX.Y.Z`.
+    Release,
+
+    /// `vX.Y.Z-pre.0.yyyymmddhhmmss-abcdef123456`, based on pre-release `vX.Y.Z-pre`.
+    Pre(&'a str),
+}
+
+impl<'a> GoVersion<'a> {
+    /// Parse a Go module version string.
+    ///
+    /// An error is returned for anything that isn't a canonical module version: a missing `v`
+    /// prefix, an invalid semantic version, or build metadata other than `+incompatible`, which
+    /// is only allowed from major version 2.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use version_compare::{Error, GoVersion};
+    ///
+    /// assert!(GoVersion::parse("v2.0.0+incompatible").unwrap().is_incompatible());
+    /// assert_eq!(GoVersion::parse("1.2.3").unwrap_err(), Error::UnexpectedChar { at: 0 });
+    /// assert_eq!(GoVersion::parse("v1.2").unwrap_err(), Error::EmptyPart { at: 4 });
+    /// assert_eq!(GoVersion::parse("v1.2.3+meta").unwrap_err(), Error::UnexpectedChar { at: 6 });
+    /// ```
+    pub fn parse(version: &'a str) -> Result<Self, Error> {
+        if version.is_empty() {
+            return Err(Error::EmptyPart { at: 0 });
+        }
+        if !version.starts_with('v') {
+            return Err(Error::UnexpectedChar { at: 0 });
+        }
+
+        let semver = SemverVersion::parse(&version[1..]).map_err(|err| err.offset(1))?;
+        if let Some(build) = semver.build() {
+            if build != "incompatible" || semver.major() < 2.into() {
+                return Err(Error::UnexpectedChar {
+                    at: version.len() - build.len() - 1,
+                });
+            }
+        }
+
+        let pseudo = parse_pseudo(&semver);
+        Ok(GoVersion {
+            version,
+            semver,
+            pseudo,
+        })
+    }
+
+    /// Get the version as semantic version, without `v` prefix.
+    pub fn semver(&self) -> &SemverVersion<'a> {
+        &self.semver
+    }
+
+    /// Check whether this version has the `+incompatible` suffix.
+    pub fn is_incompatible(&self) -> bool {
+        self.semver.build().is_some()
+    }
+
+    /// Check whether this is a pseudo-version.
+    pub fn is_pseudo(&self) -> bool {
+        self.pseudo.is_some()
+    }
+
+    /// Get the version a pseudo-version is based on.
+    ///
+    /// `None` is returned if this isn't a pseudo-version, or if the pseudo-version has no base
+    /// version, such as `v0.0.0-20191109021931-daa7c04131f5`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use version_compare::GoVersion;
+    ///
+    /// let version = GoVersion::parse("v2.3.0-pre.0.20060102150405-hash+incompatible").unwrap();
+    /// assert_eq!(version.pseudo_base(), Some("v2.3.0-pre+incompatible".into()));
+    ///
+    /// let version = GoVersion::parse("v0.0.0-20060102150405-hash").unwrap();
+    /// assert_eq!(version.pseudo_base(), None);
+    /// ```
+    pub fn pseudo_base(&self) -> Option<String> {
+        let semver = &self.semver;
+        let core = match self.pseudo?.base {
+            Base::Missing => return None,
+            Base::Release => format!(
+                "v{}.{}.{}",
+                semver.major(),
+                semver.minor(),
+                decrement(semver.patch())?,
+            ),
+            Base::Pre(pre) => format!(
+                "v{}.{}.{}-{}",
+                semver.major(),
+                semver.minor(),
+                semver.patch(),
+                pre,
+            ),
+        };
+        Some(match semver.build() {
+            Some(build) => format!("{}+{}", core, build),
+            None => core,
+        })
+    }
+
+    /// Get the UTC commit timestamp of a pseudo-version, in `yyyymmddhhmmss` form.
+    pub fn pseudo_timestamp(&self) -> Option<&'a str> {
+        self.pseudo.map(|pseudo| pseudo.timestamp)
+    }
+
+    /// Get the commit revision of a pseudo-version, usually a 12 character commit hash prefix.
+    pub fn pseudo_revision(&self) -> Option<&'a str> {
+        self.pseudo.map(|pseudo| pseudo.revision)
+    }
+
+    /// Get the full version string.
+    pub fn as_str(&self) -> &'a str {
+        self.version
+    }
+}
+
+impl<'a> Ord for GoVersion<'a> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.semver.cmp(&other.semver)
+    }
+}
+
+impl<'a> PartialOrd for GoVersion<'a> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// Equality ignoring the `+incompatible` suffix.
+impl<'a> PartialEq for GoVersion<'a> {
+    fn eq(&self, other: &Self) -> bool {
+        self.semver == other.semver
+    }
+}
+
+impl<'a> Eq for GoVersion<'a> {}
+
+/// Only the semantic version is hashed, `+incompatible` doesn't affect the order.
+impl<'a> Hash for GoVersion<'a> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.semver.hash(state);
+    }
+}
+
+impl<'a> fmt::Display for GoVersion<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.version)
+    }
+}
+
+/// Parse the pseudo-version parts of a version, if it is one.
+///
+/// Matches the forms recognized by `module.IsPseudoVersion` of `golang.org/x/mod`.
+fn parse_pseudo<'a>(semver: &SemverVersion<'a>) -> Option<Pseudo<'a>> {
+    let pre = semver.pre()?;
+    let (rest, last) = match pre.rfind('.') {
+        Some(dot) => (Some(&pre[..dot]), &pre[dot + 1..]),
+        None => (None, pre),
+    };
+
+    // The last identifier is the timestamp and revision
+    let hyphen = last.find('-')?;
+    let (timestamp, revision) = (&last[..hyphen], &last[hyphen + 1..]);
+    if timestamp.len() != 14 || !timestamp.bytes().all(|c| c.is_ascii_digit()) {
+        return None;
+    }
+    if revision.is_empty() || !revision.bytes().all(|c| c.is_ascii_alphanumeric()) {
+        return None;
+    }
+
+    let base = match rest {
+        None if semver.minor().is_zero() && semver.patch().is_zero() => Base::Missing,
+        Some("0") => Base::Release,
+        Some(rest) if rest.ends_with(".0") => Base::Pre(&rest[..rest.len() - 2]),
+        _ => return None,
+    };
+    Some(Pseudo {
+        base,
+        timestamp,
+        revision,
+    })
+}
+
+/// Decrement a number by one, `None` if it is zero.
+fn decrement(number: Number) -> Option<String> {
+    if number.is_zero() {
+        return None;
+    }
+
+    let mut digits = number.to_string().into_bytes();
+    for digit in digits.iter_mut().rev() {
+        if *digit == b'0' {
+            *digit = b'9';
+        } else {
+            *digit -= 1;
+            break;
+        }
+    }
+    if digits.len() > 1 && digits[0] == b'0' {
+        digits.remove(0);
+    }
+    String::from_utf8(digits).ok()
+}
+
+#[cfg_attr(tarpaulin, skip)]
+#[cfg(test)]
+mod tests {
+
+    use crate::test::hash_of;
+    use crate::{Cmp, Error};
+
+    use super::GoVersion;
+
+    /// Version comparisons, as `semver.Compare` of `golang.org/x/mod` reports them.
+    const COMPARE: &[(&str, &str, Cmp)] = &[
+        ("v1.0.0", "v1.0.0", Cmp::Eq),
+        ("v1.0.0", "v1.0.1", Cmp::Lt),
+        ("v1.10.0", "v1.9.0", Cmp::Gt),
+        ("v1.0.0-alpha", "v1.0.0-alpha.1", Cmp::Lt),
+        ("v1.0.0-alpha.beta", "v1.0.0-beta", Cmp::Lt),
+        ("v1.0.0-rc.1", "v1.0.0", Cmp::Lt),
+        ("v2.0.0+incompatible", "v2.0.0", Cmp::Eq),
+        ("v2.0.0+incompatible", "v2.1.0+incompatible", Cmp::Lt),
+        (
+            "v0.0.0-20191109021931-daa7c04131f5",
+            "v0.0.0-20200101000000-0123456789ab",
+            Cmp::Lt,
+        ),
+        ("v0.0.0-20191109021931-daa7c04131f5", "v0.0.1", Cmp::Lt),
+        ("v1.2.4-0.20191109021931-daa7c04131f5", "v1.2.3", Cmp::Gt),
+        ("v1.2.4-0.20191109021931-daa7c04131f5", "v1.2.4", Cmp::Lt),
+        (
+            "v1.2.4-0.20191109021931-daa7c04131f5",
+            "v1.2.4-0.20191109021932-000000000000",
+            Cmp::Lt,
+        ),
+        (
+            "v1.2.3-pre.0.20191109021931-daa7c04131f5",
+            "v1.2.3-pre",
+            Cmp::Gt,
+        ),
+        (
+            "v1.2.3-pre.0.20191109021931-daa7c04131f5",
+            "v1.2.3-pre.1",
+            Cmp::Lt,
+        ),
+        (
+            "v1.2.3-pre.0.20191109021931-daa7c04131f5",
+            "v1.2.3",
+            Cmp::Lt,
+        ),
+    ];
+
+    /// Pseudo-versions and their base, from the `golang.org/x/mod` tests.
+    const PSEUDO_BASE: &[(&str, Option<&str>)] = &[
+        ("v0.0.0-20060102150405-hash", None),
+        ("v1.0.0-20060102150405-hash", None),
+        ("v2.0.0-20060102150405-hash", None),
+        ("v0.0.1-0.20060102150405-hash", Some("v0.0.0")),
+        ("v1.2.4-0.20060102150405-hash", Some("v1.2.3")),
+        (
+            "v1.2.100000000000000000-0.20060102150405-hash",
+            Some("v1.2.99999999999999999"),
+        ),
+        ("v1.2.3-pre.0.20060102150405-hash", Some("v1.2.3-pre")),
+        ("v1.3.0-pre.0.20060102150405-hash", Some("v1.3.0-pre")),
+        ("v0.0.0--.0.20060102150405-hash", Some("v0.0.0--")),
+        (
+            "v2.0.1-0.20060102150405-hash+incompatible",
+            Some("v2.0.0+incompatible"),
+        ),
+        (
+            "v2.3.0-pre.0.20060102150405-hash+incompatible",
+            Some("v2.3.0-pre+incompatible"),
+        ),
+    ];
+
+    #[test]
+    fn parse() {
+        let version = GoVersion::parse("v2.1.0-rc.1+incompatible").unwrap();
+        assert_eq!(version.semver().as_str(), "2.1.0-rc.1+incompatible");
+        assert_eq!(version.semver().pre(), Some("rc.1"));
+        assert!(version.is_incompatible());
+        assert!(!version.is_pseudo());
+        assert_eq!(version.pseudo_timestamp(), None);
+        assert_eq!(version.as_str(), "v2.1.0-rc.1+incompatible");
+    }
+
+    #[test]
+    fn parse_error() {
+        let errors = [
+            ("", Error::EmptyPart { at: 0 }),
+            ("1.0.0", Error::UnexpectedChar { at: 0 }),
+            ("V1.0.0", Error::UnexpectedChar { at: 0 }),
+            ("v1", Error::EmptyPart { at: 2 }),
+            ("v1.02.0", Error::LeadingZero { at: 3 }),
+            ("v1.0.0-", Error::EmptyPart { at: 7 }),
+            ("v1.0.0+build", Error::UnexpectedChar { at: 6 }),
+            ("v1.0.0+incompatible", Error::UnexpectedChar { at: 6 }),
+        ];
+        for (version, err) in &errors {
+            assert_eq!(
+                GoVersion::parse(version).unwrap_err(),
+                *err,
+                "Parsing {:?}",
+                version,
+            );
+        }
+    }
+
+    #[test]
+    fn pseudo() {
+        for (version, base) in PSEUDO_BASE {
+            let version = GoVersion::parse(version).unwrap();
+            assert!(version.is_pseudo(), "Parsing {}", version);
+            assert_eq!(version.pseudo_timestamp(), Some("20060102150405"));
+            assert_eq!(version.pseudo_revision(), Some("hash"));
+            assert_eq!(
+                version.pseudo_base().as_deref(),
+                *base,
+                "Parsing {}",
+                version
+            );
+        }
+
+        let not_pseudo = [
+            "v1.2.3",
+            "v1.2.3-pre",
+            "v1.2.3-20060102150405-hash",
+            "v1.2.3-1.20060102150405-hash",
+            "v1.2.3-0.2006010215040-hash",
+            "v1.2.3-0.20060102150405-",
+        ];
+        for version in &not_pseudo {
+            assert!(
+                !GoVersion::parse(version).unwrap().is_pseudo(),
+                "Parsing {}",
+                version
+            );
+        }
+    }
+
+    #[test]
+    fn compare() {
+        for (a, b, cmp) in COMPARE {
+            let (a, b) = (GoVersion::parse(a).unwrap(), GoVersion::parse(b).unwrap());
+            assert_eq!(Cmp::from(a.cmp(&b)), *cmp, "Comparing {} to {}", a, b);
+            assert_eq!(Cmp::from(b.cmp(&a)), cmp.flip(), "Comparing {} to {}", b, a);
+        }
+    }
+
+    #[test]
+    fn hash_eq() {
+        for (a, b, cmp) in COMPARE {
+            if *cmp == Cmp::Eq {
+                let (a, b) = (GoVersion::parse(a).unwrap(), GoVersion::parse(b).unwrap());
+                assert_eq!(hash_of(&a), hash_of(&b), "Hashing {} and {}", a, b);
+            }
+        }
+    }
+}
@@ -0,0 +1,277 @@
+//! Gradle version scheme, which provides the `GradleVersion` struct.
+//!
+//! Gradle versions have no required format, and are ordered like Gradle's
+//! `StaticVersionComparator`, which splits versions into a flat list of numbers and strings.
+
+use std::cmp::Ordering;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+
+use crate::Number;
+
+/// Strings with a special meaning, in ascending order, other strings rank between `dev` and `rc`.
+const SPECIAL: &[&str] = &["dev", "", "rc", "snapshot", "final", "ga", "release", "sp"];
+
+/// Gradle version, ordered like Gradle's `StaticVersionComparator`.
+///
+/// A version is split into parts at `.`, `-`, `_` and `+`, and at transitions between digits and
+/// other characters. Separators are insignificant, so `1.0-rc1` equals `1.0.rc.1`. Unlike Maven,
+/// nothing is trimmed, so `1.0` is older than `1.0.0`.
+///
+/// Numbers are newer than strings. Strings with a special meaning are ordered `dev` < other <
+/// `rc` < `snapshot` < `final` < `ga` < `release` < `sp`, case-insensitively, while other strings
+/// are ordered lexically and case-sensitively. A version with an extra number is newer, and a
+/// version with an extra string is older, so `1.0-sp1` is older than `1.0`.
+///
+/// # Examples
+///
+/// ```
+/// use version_compare::GradleVersion;
+///
+/// let a = GradleVersion::parse("1.0-dev");
+/// let b = GradleVersion::parse("1.0-alpha");
+/// let c = GradleVersion::parse("1.0-RC1");
+/// let d = GradleVersion::parse("1.0");
+///
+/// assert!(a < b);
+/// assert!(b < c);
+/// assert!(c < d);
+/// assert!(d < GradleVersion::parse("1.0.0"));
+/// assert_eq!(c, GradleVersion::parse("1.0.rc.1"));
+/// ```
+#[derive(Debug, Clone)]
+pub struct GradleVersion<'a> {
+    version: &'a str,
+    items: Vec<Item<'a>>,
+}
+
+/// Part of a parsed Gradle version.
+#[derive(Debug, Clone, Copy)]
+enum Item<'a> {
+    /// Number, of digits only.
+    Number(Number<'a>),
+
+    /// String, which may be empty.
+    Text(&'a str),
+}
+
+impl<'a> GradleVersion<'a> {
+    /// Parse a Gradle version string.
+    ///
+    /// Like Gradle, any string is accepted, so parsing never fails.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use version_compare::GradleVersion;
+    ///
+    /// assert_eq!(GradleVersion::parse("1.0.0-SNAPSHOT").as_str(), "1.0.0-SNAPSHOT");
+    /// ```
+    pub fn parse(version: &'a str) -> Self {
+        let mut items = Vec::new();
+        let mut is_digit = false;
+        let mut start = 0;
+
+        for (i, c) in version.char_indices() {
+            match c {
+                '.' | '-' | '_' | '+' => {
+                    items.push(Item::parse(&version[start..i]));
+                    start = i + 1;
+                    is_digit = false;
+                }
+                c if c.is_ascii_digit() => {
+                    if !is_digit && i > start {
+                        items.push(Item::parse(&version[start..i]));
+                        start = i;
+                    }
+                    is_digit = true;
+                }
+                _ => {
+                    if is_digit {
+                        items.push(Item::parse(&version[start..i]));
+                        start = i;
+                    }
+                    is_digit = false;
+                }
+            }
+        }
+        if version.len() > start {
+            items.push(Item::parse(&version[start..]));
+        }
+
+        GradleVersion { version, items }
+    }
+
+    /// Get the version string.
+    pub fn as_str(&self) -> &'a str {
+        self.version
+    }
+}
+
+impl<'a> Item<'a> {
+    /// Parse an item from a run of digits or other characters.
+    fn parse(item: &'a str) -> Self {
+        match Number::parse(item) {
+            Some(number) => Item::Number(number),
+            None => Item::Text(item),
+        }
+    }
+
+    /// Get the class of this item, strings being older than a missing item, and numbers newer.
+    fn class(item: Option<&Self>) -> u8 {
+        match item {
+            Some(Item::Text(_)) => 0,
+            None => 1,
+            Some(Item::Number(_)) => 2,
+        }
+    }
+}
+
+impl<'a> Ord for GradleVersion<'a> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        for i in 0..self.items.len().max(other.items.len()) {
+            let (a, b) = (self.items.get(i), other.items.get(i));
+            let ord = match (a, b) {
+                (Some(Item::Number(a)), Some(Item::Number(b))) => a.cmp(b),
+                (Some(Item::Text(a)), Some(Item::Text(b))) => rank(a).cmp(&rank(b)),
+                _ => Item::class(a).cmp(&Item::class(b)),
+            };
+            if ord != Ordering::Equal {
+                return ord;
+            }
+        }
+        Ordering::Equal
+    }
+}
+
+impl<'a> PartialOrd for GradleVersion<'a> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<'a> PartialEq for GradleVersion<'a> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl<'a> Eq for GradleVersion<'a> {}
+
+/// Strings are hashed by rank, as special strings compare case-insensitively.
+impl<'a> Hash for GradleVersion<'a> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.items.len().hash(state);
+        for item in &self.items {
+            match item {
+                Item::Number(n) => n.hash(state),
+                Item::Text(text) => rank(text).hash(state),
+            }
+        }
+    }
+}
+
+impl<'a> fmt::Display for GradleVersion<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.version)
+    }
+}
+
+/// Get the rank of a string, strings without special meaning rank as the empty string.
+fn rank(text: &str) -> (usize, &str) {
+    match SPECIAL
+        .iter()
+        .position(|s| !s.is_empty() && s.eq_ignore_ascii_case(text))
+    {
+        Some(index) => (index, ""),
+        None => (1, text),
+    }
+}
+
+#[cfg_attr(tarpaulin, skip)]
+#[cfg(test)]
+mod tests {
+
+    use crate::test::hash_of;
+
+    use super::GradleVersion;
+
+    /// Versions in ascending order.
+    const ORDERED: &[&str] = &[
+        "1.0-dev",
+        "1.0-dev-1",
+        "1.0-dev-2",
+        "1.0-ALPHA-1",
+        "1.0.a",
+        "1.0-alpha-1",
+        "1.0-beta-1",
+        "1.0-zzz",
+        "1.0-rc-1",
+        "1.0-RC-2",
+        "1.0-SNAPSHOT",
+        "1.0-final",
+        "1.0-ga",
+        "1.0-release",
+        "1.0-sp-1",
+        "1.0",
+        "1.0.0",
+        "1.0.1",
+        "1.1-dev",
+        "1.1",
+        "1.10",
+        "2.0a",
+        "2.0",
+        "10",
+    ];
+
+    /// Equal versions.
+    const EQUAL: &[(&str, &str)] = &[
+        ("1.0", "1-0"),
+        ("1.0", "1_0"),
+        ("1.0", "1+0"),
+        ("1.01", "1.1"),
+        ("1.0a", "1.0.a"),
+        ("1.0-RC-1", "1.0.rc1"),
+        ("1.0-SNAPSHOT", "1.0-snapshot"),
+        ("1.0-Final", "1.0-FINAL"),
+        ("1..0", "1.-0"),
+    ];
+
+    /// Versions in ascending order pairwise, many of which Maven orders differently.
+    const LESS: &[(&str, &str)] = &[
+        ("1.0", "1.0.0"),
+        ("1.0-sp", "1.0"),
+        ("1.0-ga", "1.0"),
+        ("1.0.a", "1.0"),
+        ("1.0-dev", "1.0-alpha"),
+        ("1.0-beta", "1.0-milestone"),
+        ("1.0-milestone", "1.0-rc"),
+        ("1.A", "1.a"),
+        ("1.0-rc-1", "1.0.RC.2"),
+        ("1..2", "1.0.2"),
+    ];
+
+    #[test]
+    fn ord() {
+        let versions: Vec<_> = ORDERED.iter().map(|v| GradleVersion::parse(v)).collect();
+        for (i, a) in versions.iter().enumerate() {
+            for (j, b) in versions.iter().enumerate() {
+                assert_eq!(a.cmp(b), i.cmp(&j), "Comparing {} to {}", a, b);
+            }
+        }
+        for (a, b) in LESS {
+            let (a, b) = (GradleVersion::parse(a), GradleVersion::parse(b));
+            assert!(a < b, "Comparing {} to {}", a, b);
+            assert!(b > a, "Comparing {} to {}", b, a);
+        }
+    }
+
+    #[test]
+    fn eq() {
+        for (a, b) in EQUAL {
+            let (a, b) = (GradleVersion::parse(a), GradleVersion::parse(b));
+            assert_eq!(a, b, "Comparing {} to {}", a, b);
+            assert_eq!(hash_of(&a), hash_of(&b), "Hashing {} and {}", a, b);
+        }
+    }
+}
@@ -0,0 +1,617 @@
+//! Gradle version constraint module, which provides the `GradleConstraint` struct.
+//!
+//! A Gradle dependency version is either a single selector, such as `1.2`, `1.+`,
+//! `latest.release` or `[1.0,2.0)`, or a rich version constraint combining `strictly`, `require`,
+//! `prefer` and `reject`, written like Gradle's dependency reports as
+//! `{strictly [1.0,2.0); prefer 1.5; reject 1.3 & 1.4}`.
+
+use std::cmp::Ordering;
+use std::fmt;
+use std::ops::Bound;
+use std::str::FromStr;
+
+use crate::{Error, GradleVersion, Version};
+
+/// Gradle version constraint, as declared for a dependency.
+///
+/// A selector is an exact version such as `1.2`, a prefix such as `1.+` or `+`, the latest
+/// version of a status such as `latest.release`, or a range such as `[1.0,2.0)`, where `]1.0,2.0[`
+/// is an exclusive range as well. Like Gradle, prefixes are matched against the version string,
+/// so `1+` matches `10.0`. Like Gradle for Maven repositories, only versions ending with
+/// `-SNAPSHOT` are integration versions, the others being releases.
+///
+/// A plain selector is a required version, `1.5!!` is a strict version, and `[1.0,2.0)!!1.5` a
+/// strict range preferring `1.5`. Other combinations are written as a rich constraint between
+/// braces, such as `{require 1.+; prefer 1.4; reject 1.3 & 1.5}`, where each of `strictly`,
+/// `require` and `prefer` may occur once. The preferred version must be an exact version.
+///
+/// Versions are always compared with the Gradle scheme, like `GradleVersion`.
+///
+/// # Examples
+///
+/// ```
+/// use version_compare::{GradleConstraint, Version};
+///
+/// let constraint = GradleConstraint::parse("{require 1.+; reject 1.3}").unwrap();
+/// let versions: Vec<_> = ["1.2", "1.3", "2.0"]
+///     .iter()
+///     .map(|v| Version::from(v).unwrap())
+///     .collect();
+///
+/// assert!(constraint.matches(&versions[0]));
+/// assert!(!constraint.matches(&versions[1]));
+/// assert_eq!(constraint.resolve(&versions).unwrap().as_str(), "1.2");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GradleConstraint {
+    strictly: Option<Selector>,
+    require: Option<Selector>,
+    prefer: Option<String>,
+    reject: Vec<Selector>,
+}
+
+/// Version selector, like Gradle's `VersionSelector`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Selector {
+    /// Exact version, such as `1.2`.
+    Exact(String),
+
+    /// Version string prefix, such as `1.` for `1.+`.
+    Prefix(String),
+
+    /// Latest version with at least the given status, such as `latest.release`.
+    Latest(Status),
+
+    /// Range of versions, such as `[1.0,2.0)`.
+    Range(Bound<String>, Bound<String>),
+}
+
+/// Module status, in ascending order, like Gradle's default status scheme.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Status {
+    /// Any version, `latest.integration`.
+    Integration,
+
+    /// Versions that aren't snapshots, `latest.milestone`.
+    Milestone,
+
+    /// Versions that aren't snapshots, `latest.release`.
+    Release,
+}
+
+impl GradleConstraint {
+    /// Parse a Gradle version constraint, a selector or a rich constraint.
+    ///
+    /// An error is returned for malformed selectors, such as an unclosed or reversed range, for
+    /// unknown statuses or keywords, and for keywords given twice.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use version_compare::{Error, GradleConstraint};
+    ///
+    /// assert!(GradleConstraint::parse("latest.release").is_ok());
+    /// assert!(GradleConstraint::parse("[1.0,2.0[!!1.5").unwrap().is_strict());
+    /// assert_eq!(GradleConstraint::parse("[1.0,2.0").unwrap_err(), Error::UnclosedBracket { at: 0 });
+    /// assert_eq!(GradleConstraint::parse("latest.stable").unwrap_err(), Error::UnexpectedChar { at: 7 });
+    /// ```
+    pub fn parse(constraint: &str) -> Result<Self, Error> {
+        let mut result = GradleConstraint {
+            strictly: None,
+            require: None,
+            prefer: None,
+            reject: Vec::new(),
+        };
+        let start = skip_whitespace(constraint, 0);
+        let end = constraint.trim_end().len().max(start);
+
+        // Plain selectors, possibly strict with a preferred version
+        if !constraint[start..].starts_with('{') {
+            match constraint[..end].find("!!") {
+                Some(bang) => {
+                    result.strictly = Some(parse_selector(constraint, start, bang)?);
+                    if !constraint[bang + 2..end].trim().is_empty() {
+                        result.prefer = Some(parse_version(constraint, bang + 2, end)?);
+                    }
+                }
+                None => result.require = Some(parse_selector(constraint, start, end)?),
+            }
+            return Ok(result);
+        }
+
+        // Rich constraints, of `;` separated keywords and their selectors
+        if end == start + 1 || !constraint[..end].ends_with('}') {
+            return Err(Error::UnclosedBracket { at: start });
+        }
+        let (inner, inner_end) = (start + 1, end - 1);
+        if constraint[inner..inner_end].trim().is_empty() {
+            return Err(Error::MissingVersion { at: inner });
+        }
+        let mut at = inner;
+        for entry in constraint[inner..inner_end].split(';') {
+            let entry_end = at + entry.len();
+
+            // Point to the semicolon ending an empty entry, or the one before a trailing one
+            if entry.trim().is_empty() {
+                let at = if entry_end < inner_end || at == inner {
+                    entry_end
+                } else {
+                    at - 1
+                };
+                return Err(Error::EmptyClause { at });
+            }
+
+            let key_at = skip_whitespace(constraint, at);
+            let key_len = constraint[key_at..entry_end]
+                .find(|c: char| !c.is_ascii_alphabetic())
+                .unwrap_or(entry_end - key_at);
+            let value_at = key_at + key_len;
+            let duplicate = match &constraint[key_at..value_at] {
+                "strictly" => {
+                    let selector = parse_selector(constraint, value_at, entry_end)?;
+                    result.strictly.replace(selector).is_some()
+                }
+                "require" => {
+                    let selector = parse_selector(constraint, value_at, entry_end)?;
+                    result.require.replace(selector).is_some()
+                }
+                "prefer" => {
+                    let version = parse_version(constraint, value_at, entry_end)?;
+                    result.prefer.replace(version).is_some()
+                }
+                "reject" => {
+                    let mut from = value_at;
+                    for selector in constraint[value_at..entry_end].split('&') {
+                        let to = from + selector.len();
+                        result.reject.push(parse_selector(constraint, from, to)?);
+                        from = to + 1;
+                    }
+                    false
+                }
+                _ => return Err(Error::UnexpectedChar { at: key_at }),
+            };
+            if duplicate {
+                return Err(Error::UnexpectedChar { at: key_at });
+            }
+            at = entry_end + 1;
+        }
+
+        Ok(result)
+    }
+
+    /// Check whether this constraint is strict, declared with `strictly` or `!!`.
+    pub fn is_strict(&self) -> bool {
+        self.strictly.is_some()
+    }
+
+    /// Get the preferred version, if any.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use version_compare::GradleConstraint;
+    ///
+    /// assert_eq!(GradleConstraint::parse("1.+!!1.4").unwrap().preferred(), Some("1.4"));
+    /// assert_eq!(GradleConstraint::parse("1.+").unwrap().preferred(), None);
+    /// ```
+    pub fn preferred(&self) -> Option<&str> {
+        self.prefer.as_deref()
+    }
+
+    /// Check whether the given `version` matches this constraint.
+    ///
+    /// A version matches if it's accepted by the strict version, or else by the required version,
+    /// or else equals the preferred version, and isn't rejected.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use version_compare::{GradleConstraint, Version};
+    ///
+    /// let constraint = GradleConstraint::parse("]1.0,2.0[").unwrap();
+    ///
+    /// assert!(constraint.matches(&Version::from("1.5").unwrap()));
+    /// assert!(!constraint.matches(&Version::from("1.0").unwrap()));
+    /// assert!(constraint.matches(&Version::from("2.0-rc1").unwrap()));
+    /// ```
+    pub fn matches(&self, version: &Version) -> bool {
+        let accepted = match self.strictly.as_ref().or(self.require.as_ref()) {
+            Some(selector) => selector.accepts(version),
+            None => self.prefer.as_ref().map_or(true, |prefer| {
+                compare(version.as_str(), prefer) == Ordering::Equal
+            }),
+        };
+        accepted && !self.reject.iter().any(|selector| selector.accepts(version))
+    }
+
+    /// Resolve this constraint against the given candidate versions, like Gradle.
+    ///
+    /// The preferred version is picked if it matches, otherwise the highest matching version.
+    /// Returns `None` if no version matches.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use version_compare::{GradleConstraint, Version};
+    ///
+    /// let versions: Vec<_> = ["1.0", "1.1", "1.2-SNAPSHOT"]
+    ///     .iter()
+    ///     .map(|v| Version::from(v).unwrap())
+    ///     .collect();
+    /// let resolve = |constraint| {
+    ///     let constraint = GradleConstraint::parse(constraint).unwrap();
+    ///     constraint.resolve(&versions).map(|v| v.as_str())
+    /// };
+    ///
+    /// assert_eq!(resolve("latest.integration"), Some("1.2-SNAPSHOT"));
+    /// assert_eq!(resolve("latest.release"), Some("1.1"));
+    /// assert_eq!(resolve("{strictly [1.0,1.2); prefer 1.0}"), Some("1.0"));
+    /// assert_eq!(resolve("2.+"), None);
+    /// ```
+    pub fn resolve<'a, 'b, I>(&self, versions: I) -> Option<&'a Version<'b>>
+    where
+        I: IntoIterator<Item = &'a Version<'b>>,
+    {
+        let mut best: Option<&'a Version<'b>> = None;
+        for version in versions {
+            if !self.matches(version) {
+                continue;
+            }
+            if let Some(prefer) = &self.prefer {
+                if compare(version.as_str(), prefer) == Ordering::Equal {
+                    return Some(version);
+                }
+            }
+            let better = match best {
+                Some(current) => compare(version.as_str(), current.as_str()) == Ordering::Greater,
+                None => true,
+            };
+            if better {
+                best = Some(version);
+            }
+        }
+        best
+    }
+}
+
+impl Selector {
+    /// Check whether this selector accepts the given `version`.
+    fn accepts(&self, version: &Version) -> bool {
+        match self {
+            Selector::Exact(exact) => compare(version.as_str(), exact) == Ordering::Equal,
+            Selector::Prefix(prefix) => version.as_str().starts_with(prefix.as_str()),
+            Selector::Latest(Status::Integration) => true,
+            Selector::Latest(_) => !version.as_str().ends_with("-SNAPSHOT"),
+            Selector::Range(lower, upper) => {
+                let above = match lower {
+                    Bound::Included(v) => compare(version.as_str(), v) != Ordering::Less,
+                    Bound::Excluded(v) => compare(version.as_str(), v) == Ordering::Greater,
+                    Bound::Unbounded => true,
+                };
+                let below = match upper {
+                    Bound::Included(v) => compare(version.as_str(), v) != Ordering::Greater,
+                    Bound::Excluded(v) => compare(version.as_str(), v) == Ordering::Less,
+                    Bound::Unbounded => true,
+                };
+                above && below
+            }
+        }
+    }
+}
+
+impl FromStr for GradleConstraint {
+    type Err = Error;
+
+    fn from_str(constraint: &str) -> Result<Self, Self::Err> {
+        GradleConstraint::parse(constraint)
+    }
+}
+
+/// Format the constraint as a plain required selector, or else as a rich constraint.
+impl fmt::Display for GradleConstraint {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if let (None, Some(require), None, true) = (
+            &self.strictly,
+            &self.require,
+            &self.prefer,
+            self.reject.is_empty(),
+        ) {
+            return write!(f, "{}", require);
+        }
+
+        let mut entries = Vec::new();
+        if let Some(strictly) = &self.strictly {
+            entries.push(format!("strictly {}", strictly));
+        }
+        if let Some(require) = &self.require {
+            entries.push(format!("require {}", require));
+        }
+        if let Some(prefer) = &self.prefer {
+            entries.push(format!("prefer {}", prefer));
+        }
+        if !self.reject.is_empty() {
+            let reject: Vec<String> = self.reject.iter().map(Selector::to_string).collect();
+            entries.push(format!("reject {}", reject.join(" & ")));
+        }
+        write!(f, "{{{}}}", entries.join("; "))
+    }
+}
+
+impl fmt::Display for Selector {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Selector::Exact(version) => write!(f, "{}", version),
+            Selector::Prefix(prefix) => write!(f, "{}+", prefix),
+            Selector::Latest(Status::Integration) => write!(f, "latest.integration"),
+            Selector::Latest(Status::Milestone) => write!(f, "latest.milestone"),
+            Selector::Latest(Status::Release) => write!(f, "latest.release"),
+            Selector::Range(lower, upper) => {
+                match lower {
+                    Bound::Included(v) => write!(f, "[{}", v)?,
+                    Bound::Excluded(v) => write!(f, "({}", v)?,
+                    Bound::Unbounded => write!(f, "(")?,
+                }
+                match upper {
+                    Bound::Included(v) => write!(f, ",{}]", v),
+                    Bound::Excluded(v) => write!(f, ",{})", v),
+                    Bound::Unbounded => write!(f, ",)"),
+                }
+            }
+        }
+    }
+}
+
+/// Compare two version strings with the Gradle scheme.
+fn compare(a: &str, b: &str) -> Ordering {
+    GradleVersion::parse(a).cmp(&GradleVersion::parse(b))
+}
+
+/// Parse the selector between byte offsets `from` and `to`, surrounding whitespace is trimmed.
+fn parse_selector(constraint: &str, from: usize, to: usize) -> Result<Selector, Error> {
+    let at = skip_whitespace(constraint, from).min(to);
+    let selector = constraint[at..to].trim_end();
+
+    if selector.starts_with(|c| matches!(c, '[' | ']' | '(')) {
+        return parse_range(constraint, at, at + selector.len());
+    }
+    if selector.starts_with("latest.") {
+        return match &selector["latest.".len()..] {
+            "integration" => Ok(Selector::Latest(Status::Integration)),
+            "milestone" => Ok(Selector::Latest(Status::Milestone)),
+            "release" => Ok(Selector::Latest(Status::Release)),
+            _ => Err(Error::UnexpectedChar {
+                at: at + "latest.".len(),
+            }),
+        };
+    }
+    if selector.ends_with('+') {
+        let to = at + selector.len() - 1;
+        if let Some(i) = constraint[at..to].find(is_reserved) {
+            return Err(Error::UnexpectedChar { at: at + i });
+        }
+        return Ok(Selector::Prefix(constraint[at..to].to_owned()));
+    }
+    parse_version(constraint, from, to).map(Selector::Exact)
+}
+
+/// Parse the range between byte offsets `start` and `end`, starting with a bracket.
+fn parse_range(constraint: &str, start: usize, end: usize) -> Result<Selector, Error> {
+    if end == start + 1 || !constraint[..end].ends_with(|c| matches!(c, ']' | '[' | ')')) {
+        return Err(Error::UnclosedBracket { at: start });
+    }
+    let lower_inclusive = constraint[start..].starts_with('[');
+    let upper_inclusive = constraint[..end].ends_with(']');
+    let end = end - 1;
+    let comma = match constraint[start + 1..end].find(',') {
+        Some(comma) => comma + start + 1,
+        None => return Err(Error::InvalidRange { at: start }),
+    };
+
+    let bound = |from: usize, to: usize, inclusive: bool| {
+        if constraint[from..to].trim().is_empty() {
+            return Ok(Bound::Unbounded);
+        }
+        let version = parse_version(constraint, from, to)?;
+        Ok(if inclusive {
+            Bound::Included(version)
+        } else {
+            Bound::Excluded(version)
+        })
+    };
+    let lower = bound(start + 1, comma, lower_inclusive)?;
+    let upper = bound(comma + 1, end, upper_inclusive)?;
+
+    // Bounds may not be reversed, and must differ unless both inclusive
+    let invalid = match (&lower, &upper) {
+        (Bound::Unbounded, Bound::Unbounded) => true,
+        (Bound::Included(l), Bound::Included(u)) => compare(u, l) == Ordering::Less,
+        (Bound::Included(l), Bound::Excluded(u))
+        | (Bound::Excluded(l), Bound::Included(u))
+        | (Bound::Excluded(l), Bound::Excluded(u)) => compare(u, l) != Ordering::Greater,
+        _ => false,
+    };
+    if invalid {
+        return Err(Error::InvalidRange { at: start });
+    }
+    Ok(Selector::Range(lower, upper))
+}
+
+/// Parse the exact version between byte offsets `from` and `to`, surrounding whitespace is
+/// trimmed.
+fn parse_version(constraint: &str, from: usize, to: usize) -> Result<String, Error> {
+    let at = skip_whitespace(constraint, from).min(to);
+    let version = constraint[at..to].trim_end();
+    if version.is_empty() {
+        return Err(Error::MissingVersion { at: to });
+    }
+    if let Some(i) = version.find(|c: char| is_reserved(c) || c.is_whitespace()) {
+        return Err(Error::UnexpectedChar { at: at + i });
+    }
+    Ok(version.to_owned())
+}
+
+/// Check whether the given character is reserved for selectors and constraints.
+fn is_reserved(c: char) -> bool {
+    matches!(
+        c,
+        '[' | ']' | '(' | ')' | '{' | '}' | ',' | ';' | '&' | '!' | '+'
+    )
+}
+
+/// Get the byte offset of the first non-whitespace character at or after `at`.
+fn skip_whitespace(constraint: &str, at: usize) -> usize {
+    constraint[at..]
+        .find(|c: char| !c.is_whitespace())
+        .map_or(constraint.len(), |i| i + at)
+}
+
+#[cfg_attr(tarpaulin, skip)]
+#[cfg(test)]
+mod tests {
+    use crate::{Error, Version};
+
+    use super::GradleConstraint;
+
+    /// Constraints, with versions they match and don't match.
+    const MATCHES: &[(&str, &[&str], &[&str])] = &[
+        ("1.2", &["1.2", "1-2"], &["1.2.0", "1.3"]),
+        ("1.+", &["1.0", "1.10.2", "1.0-SNAPSHOT"], &["2.0", "10.0"]),
+        ("1+", &["1.0", "10.0"], &["2.0"]),
+        ("+", &["0.1", "99"], &[]),
+        ("latest.release", &["1.0", "1.1-rc1"], &["1.1-SNAPSHOT"]),
+        ("latest.integration", &["1.0", "1.1-SNAPSHOT"], &[]),
+        ("[1.0,2.0)", &["1.0", "1.5", "2.0-rc1"], &["0.9", "2.0"]),
+        ("]1.0,2.0]", &["1.0.1", "2.0"], &["1.0", "2.0.1"]),
+        ("[1.0,2.0[", &["1.0"], &["2.0"]),
+        ("(,1.5]", &["0.1", "1.5"], &["1.5.1"]),
+        ("[1.5,)", &["1.5", "3.0"], &["1.4"]),
+        ("[1.0,1.0]", &["1.0"], &["1.0.0"]),
+        ("1.5!!", &["1.5"], &["1.4", "1.6"]),
+        ("[1.0,2.0)!!1.5", &["1.2", "1.5"], &["2.0"]),
+        (
+            "{strictly 1.+; reject 1.3 & 1.5}",
+            &["1.2", "1.4"],
+            &["1.3", "1.5"],
+        ),
+        (
+            "{require [1.0,2.0); reject [1.2,1.4]}",
+            &["1.1", "1.5"],
+            &["1.2", "1.3.5"],
+        ),
+        ("{prefer 1.4}", &["1.4"], &["1.3"]),
+        ("{reject 1.+}", &["2.0", "0.9"], &["1.0"]),
+    ];
+
+    /// Constraints, candidate versions, and the resolved version.
+    const RESOLVE: &[(&str, &[&str], Option<&str>)] = &[
+        ("1.2", &["1.1", "1.2", "1.3"], Some("1.2")),
+        ("1.+", &["1.1", "1.10", "1.9", "2.0"], Some("1.10")),
+        ("1.+", &["2.0"], None),
+        ("[1.0,2.0)", &["1.5", "2.0-rc1", "2.0"], Some("2.0-rc1")),
+        ("[1.0,2.0)!!1.5", &["1.2", "1.5", "1.9"], Some("1.5")),
+        ("[1.0,2.0)!!1.5", &["1.2", "1.9"], Some("1.9")),
+        ("{require 1.+; prefer 2.0}", &["1.2", "2.0"], Some("1.2")),
+        ("{prefer 1.4}", &["1.3", "1.4", "1.5"], Some("1.4")),
+        (
+            "{strictly latest.release; reject 2.0}",
+            &["1.0", "2.0", "2.1-SNAPSHOT"],
+            Some("1.0"),
+        ),
+        ("1.0", &["1.0-final", "1.0.0"], None),
+    ];
+
+    #[test]
+    fn matches() {
+        for (constraint, matched, unmatched) in MATCHES {
+            let parsed = GradleConstraint::parse(constraint).unwrap();
+            for version in matched.iter() {
+                let version = Version::from(version).unwrap();
+                assert!(
+                    parsed.matches(&version),
+                    "{} matches {}",
+                    constraint,
+                    version
+                );
+            }
+            for version in unmatched.iter() {
+                let version = Version::from(version).unwrap();
+                assert!(
+                    !parsed.matches(&version),
+                    "{} doesn't match {}",
+                    constraint,
+                    version
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn resolve() {
+        for (constraint, versions, resolved) in RESOLVE {
+            let versions: Vec<_> = versions.iter().map(|v| Version::from(v).unwrap()).collect();
+            let parsed = GradleConstraint::parse(constraint).unwrap();
+            assert_eq!(
+                parsed.resolve(&versions).map(|v| v.as_str()),
+                *resolved,
+                "Resolving {}",
+                constraint
+            );
+        }
+    }
+
+    #[test]
+    fn parse_error() {
+        let errors = [
+            ("", Error::MissingVersion { at: 0 }),
+            ("[1.0,2.0", Error::UnclosedBracket { at: 0 }),
+            ("[1.0]", Error::InvalidRange { at: 0 }),
+            ("[2.0,1.0]", Error::InvalidRange { at: 0 }),
+            ("[1.0,1.0)", Error::InvalidRange { at: 0 }),
+            ("(,)", Error::InvalidRange { at: 0 }),
+            ("[1.0,2.0,3.0]", Error::UnexpectedChar { at: 8 }),
+            ("latest.stable", Error::UnexpectedChar { at: 7 }),
+            ("1.0 2.0", Error::UnexpectedChar { at: 3 }),
+            ("1.+.+", Error::UnexpectedChar { at: 2 }),
+            ("!!1.5", Error::MissingVersion { at: 0 }),
+            ("1.+!!1.+", Error::UnexpectedChar { at: 7 }),
+            ("{strictly 1.0", Error::UnclosedBracket { at: 0 }),
+            ("{}", Error::MissingVersion { at: 1 }),
+            ("{require 1.0;}", Error::EmptyClause { at: 12 }),
+            ("{; require 1.0}", Error::EmptyClause { at: 1 }),
+            ("{require}", Error::MissingVersion { at: 8 }),
+            (
+                "{require 1.0; require 1.1}",
+                Error::UnexpectedChar { at: 14 },
+            ),
+            ("{requires 1.0}", Error::UnexpectedChar { at: 1 }),
+            ("{reject 1.0 & }", Error::MissingVersion { at: 14 }),
+        ];
+        for (constraint, err) in &errors {
+            assert_eq!(
+                GradleConstraint::parse(constraint).unwrap_err(),
+                *err,
+                "Parsing {:?}",
+                constraint
+            );
+        }
+    }
+
+    #[test]
+    fn display() {
+        let constraints = [
+            (" 1.+ ", "1.+"),
+            ("]1.0,2.0[", "(1.0,2.0)"),
+            ("[1.0,2.0)!!1.5", "{strictly [1.0,2.0); prefer 1.5}"),
+            ("1.5!!", "{strictly 1.5}"),
+            (
+                "{reject 1.3&1.4;require latest.release}",
+                "{require latest.release; reject 1.3 & 1.4}",
+            ),
+        ];
+        for (constraint, display) in &constraints {
+            let parsed = GradleConstraint::parse(constraint).unwrap();
+            assert_eq!(parsed.to_string(), *display, "Formatting {}", constraint);
+            assert_eq!(GradleConstraint::parse(display).unwrap(), parsed);
+        }
+    }
+}
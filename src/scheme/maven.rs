@@ -0,0 +1,520 @@
+//! Maven version scheme, which provides the `MavenVersion` struct.
+//!
+//! Maven versions have no required format, and are ordered like Maven's `ComparableVersion` as of
+//! Maven 3.9, which splits versions into nested lists of numbers and qualifiers.
+
+use std::cmp::Ordering;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+
+use crate::Number;
+
+/// Known qualifiers, in ascending order, the empty qualifier is a release.
+const QUALIFIERS: &[&str] = &["alpha", "beta", "milestone", "rc", "snapshot", "", "sp"];
+
+/// Maven version, ordered like Maven's `ComparableVersion` as of Maven 3.9.
+///
+/// A version is split into numbers and qualifiers at `.` and `-`, and at transitions between
+/// digits and other characters. A `-`, or such a transition, starts a nested sub-list. Trailing
+/// zeros and release qualifiers are trimmed, so `1.0.0`, `1-0` and `1-ga` all equal `1`.
+///
+/// Since Maven 3.9 (MNG-7644), a qualifier after a `.` also starts a sub-list, so `1.x` equals
+/// `1-x`. Earlier releases kept it in the same list, which made the order cyclic: `1.x` < `1-rc`
+/// < `1` < `1.x`.
+///
+/// Known qualifiers are ordered `alpha` < `beta` < `milestone` < `rc` = `cr` < `snapshot` <
+/// release = `ga` = `final` = `release` < `sp`, case-insensitively. `a`, `b` and `m` directly
+/// followed by a number are short for `alpha`, `beta` and `milestone`. Unknown qualifiers are
+/// newer than known ones, and ordered lexically. Numbers are newer than qualifiers.
+///
+/// # Examples
+///
+/// ```
+/// use version_compare::MavenVersion;
+///
+/// let a = MavenVersion::parse("1.0-alpha-1");
+/// let b = MavenVersion::parse("1.0.0-RC2");
+/// let c = MavenVersion::parse("1-final");
+///
+/// assert!(a < b);
+/// assert!(b < c);
+/// assert_eq!(c, MavenVersion::parse("1"));
+/// assert_eq!(MavenVersion::parse("1a1").canonical(), "1-alpha-1");
+/// ```
+#[derive(Debug, Clone)]
+pub struct MavenVersion<'a> {
+    version: &'a str,
+    items: Vec<Item<'a>>,
+}
+
+/// Item of a parsed Maven version.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+enum Item<'a> {
+    /// Number, without leading zeros.
+    Number(Number<'a>),
+
+    /// Lowercase qualifier, with aliases resolved.
+    Qualifier(String),
+
+    /// Nested sub-list.
+    List(Vec<Item<'a>>),
+}
+
+impl<'a> MavenVersion<'a> {
+    /// Parse a Maven version string.
+    ///
+    /// Like Maven, any string is accepted, so parsing never fails.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use version_compare::MavenVersion;
+    ///
+    /// assert_eq!(MavenVersion::parse("1.0.0-SNAPSHOT").as_str(), "1.0.0-SNAPSHOT");
+    /// ```
+    pub fn parse(version: &'a str) -> Self {
+        // Lists are only ever nested in the last item of their parent, keep a stack of them
+        let mut lists = vec![Vec::new()];
+        let mut is_digit = false;
+        let mut after_dot = false;
+        let mut start = 0;
+
+        for (i, c) in version.char_indices() {
+            let list = lists.last_mut().unwrap();
+            match c {
+                '.' | '-' => {
+                    if i == start {
+                        list.push(Item::Number(0.into()));
+                    } else {
+                        list.push(Item::parse(&version[start..i], is_digit, false));
+                    }
+                    start = i + 1;
+                    after_dot = c == '.';
+                    if c == '-' {
+                        lists.push(Vec::new());
+                    }
+                }
+                c if c.is_ascii_digit() => {
+                    if !is_digit && i > start {
+                        list.push(Item::parse(&version[start..i], false, true));
+                        start = i;
+                        lists.push(Vec::new());
+                    }
+                    is_digit = true;
+                }
+                _ => {
+                    // A qualifier after a dot starts a sub-list like after a dash
+                    if i == start && after_dot && !list.is_empty() {
+                        lists.push(Vec::new());
+                    } else if is_digit && i > start {
+                        list.push(Item::parse(&version[start..i], true, false));
+                        start = i;
+                        lists.push(Vec::new());
+                    }
+                    is_digit = false;
+                }
+            }
+        }
+        if version.len() > start {
+            let list = lists.last_mut().unwrap();
+            list.push(Item::parse(&version[start..], is_digit, false));
+        }
+
+        // Normalize the innermost list first, then nest each list in its parent
+        let mut items = lists.pop().unwrap();
+        normalize(&mut items);
+        while let Some(mut parent) = lists.pop() {
+            parent.push(Item::List(items));
+            normalize(&mut parent);
+            items = parent;
+        }
+
+        MavenVersion { version, items }
+    }
+
+    /// Get the canonical form of this version.
+    ///
+    /// Versions are equal if, and only if, their canonical forms are equal.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use version_compare::MavenVersion;
+    ///
+    /// assert_eq!(MavenVersion::parse("1.0.0-SNAPSHOT").canonical(), "1-snapshot");
+    /// assert_eq!(MavenVersion::parse("2.0.RC1").canonical(), "2-rc-1");
+    /// ```
+    pub fn canonical(&self) -> String {
+        let mut canonical = String::new();
+        write_list(&mut canonical, &self.items);
+        canonical
+    }
+
+    /// Get the version string.
+    pub fn as_str(&self) -> &'a str {
+        self.version
+    }
+}
+
+impl<'a> Item<'a> {
+    /// Parse an item from a run of digits or other characters.
+    ///
+    /// `followed_by_digit` expands the `a`, `b` and `m` shorthands.
+    fn parse(item: &'a str, is_digit: bool, followed_by_digit: bool) -> Self {
+        if is_digit {
+            return Item::Number(Number::parse(item).unwrap());
+        }
+
+        let qualifier = item.to_lowercase();
+        let qualifier = match qualifier.as_str() {
+            "a" if followed_by_digit => "alpha",
+            "b" if followed_by_digit => "beta",
+            "m" if followed_by_digit => "milestone",
+            "ga" | "final" | "release" => "",
+            "cr" => "rc",
+            _ => return Item::Qualifier(qualifier),
+        };
+        Item::Qualifier(qualifier.into())
+    }
+
+    /// Check whether this item equals a missing item, which is trimmed when normalizing.
+    fn is_null(&self) -> bool {
+        match self {
+            Item::Number(n) => n.is_zero(),
+            Item::Qualifier(q) => q.is_empty(),
+            Item::List(items) => items.is_empty(),
+        }
+    }
+
+    /// Compare this item to another item.
+    fn cmp(&self, other: &Self) -> Ordering {
+        match (self, other) {
+            (Item::Number(a), Item::Number(b)) => a.cmp(b),
+            (Item::Qualifier(a), Item::Qualifier(b)) => rank(a).cmp(&rank(b)),
+            (Item::List(a), Item::List(b)) => cmp_lists(a, b),
+            // Numbers are newer than lists, which are newer than qualifiers
+            (Item::Number(_), _) | (Item::List(_), Item::Qualifier(_)) => Ordering::Greater,
+            (Item::Qualifier(_), _) | (Item::List(_), Item::Number(_)) => Ordering::Less,
+        }
+    }
+
+    /// Compare this item to a missing item.
+    fn cmp_null(&self) -> Ordering {
+        match self {
+            Item::Number(n) if n.is_zero() => Ordering::Equal,
+            Item::Number(_) => Ordering::Greater,
+            Item::Qualifier(q) => rank(q).cmp(&rank("")),
+            Item::List(items) => items
+                .iter()
+                .map(Item::cmp_null)
+                .find(|ord| *ord != Ordering::Equal)
+                .unwrap_or(Ordering::Equal),
+        }
+    }
+}
+
+impl<'a> Ord for MavenVersion<'a> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        cmp_lists(&self.items, &other.items)
+    }
+}
+
+impl<'a> PartialOrd for MavenVersion<'a> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<'a> PartialEq for MavenVersion<'a> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl<'a> Eq for MavenVersion<'a> {}
+
+impl<'a> Hash for MavenVersion<'a> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        // Normalized items are equal if, and only if, versions are equal
+        self.items.hash(state);
+    }
+}
+
+impl<'a> fmt::Display for MavenVersion<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.version)
+    }
+}
+
+/// Get the rank of a qualifier, known qualifiers are older than unknown qualifiers.
+fn rank(qualifier: &str) -> (usize, &str) {
+    match QUALIFIERS.iter().position(|q| *q == qualifier) {
+        Some(index) => (index, ""),
+        None => (QUALIFIERS.len(), qualifier),
+    }
+}
+
+/// Trim trailing null items, skipping over nested lists.
+fn normalize(items: &mut Vec<Item>) {
+    for i in (0..items.len()).rev() {
+        if items[i].is_null() {
+            items.remove(i);
+        } else if !matches!(items[i], Item::List(_)) {
+            break;
+        }
+    }
+}
+
+/// Compare two lists of items, missing items are compared as null.
+fn cmp_lists(a: &[Item], b: &[Item]) -> Ordering {
+    for i in 0..a.len().max(b.len()) {
+        let ord = match (a.get(i), b.get(i)) {
+            (Some(a), Some(b)) => a.cmp(b),
+            (Some(a), None) => a.cmp_null(),
+            (None, Some(b)) => b.cmp_null().reverse(),
+            (None, None) => unreachable!(),
+        };
+        if ord != Ordering::Equal {
+            return ord;
+        }
+    }
+    Ordering::Equal
+}
+
+/// Write the canonical form of a list of items.
+fn write_list(buf: &mut String, items: &[Item]) {
+    for (i, item) in items.iter().enumerate() {
+        match item {
+            // A leading sub-list follows a trimmed zero, which is kept to parse back the same
+            Item::List(_) if i == 0 => buf.push_str("0-"),
+            Item::List(_) => buf.push('-'),
+            _ if i > 0 => buf.push('.'),
+            _ => {}
+        }
+        match item {
+            Item::Number(n) => buf.push_str(&n.to_string()),
+            Item::Qualifier(q) => buf.push_str(q),
+            Item::List(items) => write_list(buf, items),
+        }
+    }
+}
+
+#[cfg_attr(tarpaulin, skip)]
+#[cfg(test)]
+mod tests {
+
+    use crate::test::hash_of;
+
+    use super::MavenVersion;
+
+    /// Versions with qualifiers in ascending order, from Maven's test suite.
+    const QUALIFIER_ORDERED: &[&str] = &[
+        "1-alpha2snapshot",
+        "1-alpha2",
+        "1-alpha-123",
+        "1-beta-2",
+        "1-beta123",
+        "1-m2",
+        "1-m11",
+        "1-rc",
+        "1-cr2",
+        "1-rc123",
+        "1-SNAPSHOT",
+        "1",
+        "1-sp",
+        "1-sp2",
+        "1-sp123",
+        "1-abc",
+        "1-def",
+        "1-pom-1",
+        "1-1-snapshot",
+        "1-1",
+        "1-2",
+        "1-123",
+    ];
+
+    /// Versions with numbers in ascending order, from Maven's test suite.
+    const NUMBER_ORDERED: &[&str] = &[
+        "2.0", "2.0.a", "2-1", "2.0.2", "2.0.123", "2.1.0", "2.1-a", "2.1b", "2.1-c", "2.1-1",
+        "2.1.0.1", "2.2", "2.123", "11.a2", "11.a11", "11.b2", "11.b11", "11.m2", "11.m11", "11",
+        "11.a", "11b", "11c", "11m",
+    ];
+
+    /// Equal versions, from Maven's test suite.
+    const EQUAL: &[(&str, &str)] = &[
+        ("1", "1"),
+        ("1", "1.0"),
+        ("1", "1.0.0"),
+        ("1.0", "1.0.0"),
+        ("1", "1-0"),
+        ("1", "1.0-0"),
+        ("1.0", "1.0-0"),
+        ("1a", "1-a"),
+        ("1a", "1.0-a"),
+        ("1a", "1.0.0-a"),
+        ("1.0a", "1-a"),
+        ("1.0.0a", "1-a"),
+        ("1x", "1-x"),
+        ("1x", "1.0-x"),
+        ("1x", "1.0.0-x"),
+        ("1.0x", "1-x"),
+        ("1.0.0x", "1-x"),
+        ("1ga", "1"),
+        ("1release", "1"),
+        ("1final", "1"),
+        ("1cr", "1rc"),
+        ("1a1", "1-alpha-1"),
+        ("1b2", "1-beta-2"),
+        ("1m3", "1-milestone-3"),
+        ("1X", "1x"),
+        ("1A", "1a"),
+        ("1B", "1b"),
+        ("1M", "1m"),
+        ("1Ga", "1"),
+        ("1GA", "1"),
+        ("1RELEASE", "1"),
+        ("1RELeaSE", "1"),
+        ("1Final", "1"),
+        ("1FinaL", "1"),
+        ("1FINAL", "1"),
+        ("1Cr", "1Rc"),
+        ("1cR", "1rC"),
+        ("1m3", "1Milestone3"),
+        ("1m3", "1MileStone3"),
+        ("1m3", "1MILESTONE3"),
+        // MNG-7644
+        ("1.x", "1-x"),
+        ("2.0.a", "2.0.0.a"),
+        ("2.5.6.SEC01", "2.5.6-sec-1"),
+    ];
+
+    /// Versions in ascending order pairwise, from Maven's test suite.
+    const LESS: &[(&str, &str)] = &[
+        ("1", "2"),
+        ("1.5", "2"),
+        ("1", "2.5"),
+        ("1.0", "1.1"),
+        ("1.1", "1.2"),
+        ("1.0.0", "1.1"),
+        ("1.0.1", "1.1"),
+        ("1.1", "1.2.0"),
+        ("1.0-alpha-1", "1.0"),
+        ("1.0-alpha-1", "1.0-alpha-2"),
+        ("1.0-alpha-1", "1.0-beta-1"),
+        ("1.0-beta-1", "1.0-SNAPSHOT"),
+        ("1.0-SNAPSHOT", "1.0"),
+        ("1.0-alpha-1-SNAPSHOT", "1.0-alpha-1"),
+        ("1.0", "1.0-1"),
+        ("1.0-1", "1.0-2"),
+        ("1.0.0", "1.0-1"),
+        ("2.0-1", "2.0.1"),
+        ("2.0.1-klm", "2.0.1-lmn"),
+        ("2.0.1", "2.0.1-xyz"),
+        ("2.0.1", "2.0.1-123"),
+        ("2.0.1-xyz", "2.0.1-123"),
+        // MNG-5568
+        ("6.1.0rc3", "6.1.0"),
+        ("6.1.0rc3", "6.1H.5-beta"),
+        ("6.1.0", "6.1H.5-beta"),
+        // MNG-6572
+        ("20190126.230843", "1234567890.12345"),
+        ("1234567890.12345", "123456789012345.1H.5-beta"),
+        (
+            "123456789012345.1H.5-beta",
+            "12345678901234567890.1H.5-beta",
+        ),
+        (
+            "12345678901234567890.1H.5-beta",
+            "1234567890123456789012345678901234567890.1H.5-beta",
+        ),
+        // MNG-6964
+        ("1-0.alpha", "1"),
+        ("1-0.beta", "1"),
+        ("1-0.alpha", "1-0.beta"),
+    ];
+
+    /// Versions and their canonical form.
+    const CANONICAL: &[(&str, &str)] = &[
+        ("1.0.0-SNAPSHOT", "1-snapshot"),
+        ("1.0-final", "1"),
+        ("1a1", "1-alpha-1"),
+        ("1.0.RC1", "1-rc-1"),
+        ("2.0.1-xyz", "2.0.1-xyz"),
+        ("1-0.alpha", "1-0-alpha"),
+        ("-1", "0-1"),
+        ("01.002-cr", "1.2-rc"),
+        ("", ""),
+    ];
+
+    /// Check that the given versions are strictly ascending, comparing each pair.
+    fn check_ordered(versions: &[&str]) {
+        let versions: Vec<_> = versions.iter().map(|v| MavenVersion::parse(v)).collect();
+        for (i, a) in versions.iter().enumerate() {
+            for (j, b) in versions.iter().enumerate() {
+                assert_eq!(a.cmp(b), i.cmp(&j), "Comparing {} to {}", a, b);
+            }
+        }
+    }
+
+    #[test]
+    fn ord() {
+        check_ordered(QUALIFIER_ORDERED);
+        check_ordered(NUMBER_ORDERED);
+        for (a, b) in LESS {
+            check_ordered(&[a, b]);
+        }
+    }
+
+    #[test]
+    fn transitive() {
+        let versions: Vec<_> = QUALIFIER_ORDERED
+            .iter()
+            .chain(NUMBER_ORDERED)
+            .chain(&["1.x", "1-rc", "1.0.a1", "2.5.6.SEC01", "2.5.6-rc1", "2.5.6"])
+            .map(|v| MavenVersion::parse(v))
+            .collect();
+        for a in &versions {
+            for b in &versions {
+                for c in &versions {
+                    if a <= b && b <= c {
+                        assert!(a <= c, "Comparing {} to {} and {}", a, c, b);
+                    }
+                }
+            }
+        }
+
+        // Used to be a cycle before Maven 3.9
+        let mut spring: Vec<_> = ["2.5.6", "2.5.6.SEC01", "2.5.6-rc1"]
+            .iter()
+            .cycle()
+            .take(40)
+            .map(|v| MavenVersion::parse(v))
+            .collect();
+        spring.sort();
+        assert!(spring.windows(2).all(|w| w[0] <= w[1]));
+        assert_eq!(spring[0].as_str(), "2.5.6-rc1");
+        assert_eq!(spring[39].as_str(), "2.5.6.SEC01");
+    }
+
+    #[test]
+    fn eq() {
+        for (a, b) in EQUAL {
+            let (a, b) = (MavenVersion::parse(a), MavenVersion::parse(b));
+            assert_eq!(a, b, "Comparing {} to {}", a, b);
+            assert_eq!(a.canonical(), b.canonical(), "Comparing {} to {}", a, b);
+            assert_eq!(hash_of(&a), hash_of(&b), "Hashing {} and {}", a, b);
+        }
+    }
+
+    #[test]
+    fn canonical() {
+        for (version, canonical) in CANONICAL {
+            assert_eq!(
+                MavenVersion::parse(version).canonical(),
+                *canonical,
+                "Canonical form of {}",
+                version,
+            );
+        }
+    }
+}
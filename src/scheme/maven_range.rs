@@ -0,0 +1,450 @@
+//! Maven version range module, which provides the `MavenRange` struct.
+//!
+//! Maven version ranges are a list of sets with inclusive `[` `]` or exclusive `(` `)` bounds,
+//! such as `[1.0,2.0)` or `(,1.0],[1.2,)`, or a bare recommended version that matches anything.
+
+use std::fmt;
+use std::ops::Bound;
+use std::str::FromStr;
+
+use crate::{Cmp, Error, Manifest, Scheme, Version, VersionBuf};
+
+/// Maven version range, as used in Maven dependency versions.
+///
+/// A range is a comma separated list of sets, and contains a version if any of its sets does. A
+/// set is either an exact version such as `[1.2]`, or a lower and upper bound separated by a
+/// comma, which may be omitted for an unbounded side, such as `(,1.5]`. A bare version such as
+/// `1.0` is a recommended version, and contains any version.
+///
+/// Versions are always compared with the Maven scheme, like `MavenVersion`.
+///
+/// # Examples
+///
+/// ```
+/// use version_compare::{MavenRange, Version};
+///
+/// let range = MavenRange::parse("(,1.0],[1.2,)").unwrap();
+///
+/// assert!(range.contains(&Version::from("1.0").unwrap()));
+/// assert!(!range.contains(&Version::from("1.1").unwrap()));
+/// assert!(range.contains(&Version::from("1.2-sp1").unwrap()));
+/// assert!(!range.contains(&Version::from("1.2-rc1").unwrap()));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MavenRange {
+    recommended: Option<VersionBuf>,
+    restrictions: Vec<(Bound<VersionBuf>, Bound<VersionBuf>)>,
+}
+
+impl MavenRange {
+    /// Parse a Maven version range.
+    ///
+    /// Like Maven, an error is returned if a set isn't closed, has identical or reversed bounds,
+    /// is an exact version in `(` `)`, or has a lower bound below the upper bound of the previous
+    /// set. Sets with an unbounded side aren't checked for overlap, so `[1.0,),[2.0,3.0]` is
+    /// valid. Unlike Maven, a version containing a bracket or comma is an error as well.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use version_compare::{Error, MavenRange};
+    ///
+    /// assert!(MavenRange::parse("[1.0,2.0)").is_ok());
+    /// assert_eq!(MavenRange::parse("[1.0,2.0").unwrap_err(), Error::UnclosedBracket { at: 0 });
+    /// assert_eq!(MavenRange::parse("[1.0,1.2),(1.1,1.3]").unwrap_err(), Error::InvalidRange { at: 10 });
+    /// ```
+    pub fn parse(range: &str) -> Result<Self, Error> {
+        let mut restrictions: Vec<(Bound<VersionBuf>, Bound<VersionBuf>)> = Vec::new();
+        let mut at = skip_whitespace(range, 0);
+
+        while range[at..].starts_with(|c| c == '[' || c == '(') {
+            // Like Maven, a set ends at the first closing bracket
+            let end = range[at..]
+                .find(|c| c == ']' || c == ')')
+                .ok_or(Error::UnclosedBracket { at })?
+                + at;
+            let (lower, upper) = parse_restriction(range, at, end)?;
+
+            // Sets must be ascending, and may only touch the previous set, which like Maven is
+            // only checked if both bounds are present
+            if let Some((_, previous)) = restrictions.last() {
+                let overlaps = match (&lower, previous) {
+                    (Bound::Included(lower), Bound::Included(upper))
+                    | (Bound::Included(lower), Bound::Excluded(upper))
+                    | (Bound::Excluded(lower), Bound::Included(upper))
+                    | (Bound::Excluded(lower), Bound::Excluded(upper)) => {
+                        lower.compare_to(upper, Cmp::Lt)
+                    }
+                    _ => false,
+                };
+                if overlaps {
+                    return Err(Error::InvalidRange { at });
+                }
+            }
+            restrictions.push((lower, upper));
+
+            at = skip_whitespace(range, end + 1);
+            if range[at..].starts_with(',') {
+                let comma = at;
+                at = skip_whitespace(range, at + 1);
+                if at == range.len() {
+                    return Err(Error::EmptyClause { at: comma });
+                }
+            }
+        }
+
+        // Anything else must be a single bare recommended version
+        if at < range.len() && !restrictions.is_empty() {
+            return Err(Error::UnexpectedChar { at });
+        }
+        if restrictions.is_empty() {
+            let version = parse_version(range, at, range.len())?;
+            return Ok(MavenRange {
+                recommended: Some(version),
+                restrictions: vec![(Bound::Unbounded, Bound::Unbounded)],
+            });
+        }
+
+        Ok(MavenRange {
+            recommended: None,
+            restrictions,
+        })
+    }
+
+    /// Get the recommended version, if this range is a bare version.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use version_compare::MavenRange;
+    ///
+    /// assert_eq!(MavenRange::parse("1.0").unwrap().recommended().unwrap().as_str(), "1.0");
+    /// assert!(MavenRange::parse("[1.0]").unwrap().recommended().is_none());
+    /// ```
+    pub fn recommended(&self) -> Option<&VersionBuf> {
+        self.recommended.as_ref()
+    }
+
+    /// Get the sets of this range, each being a lower and upper bound.
+    ///
+    /// An exact version is a set with equal inclusive bounds, and a recommended version has a
+    /// single unbounded set.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::ops::Bound;
+    /// use version_compare::MavenRange;
+    ///
+    /// let range = MavenRange::parse("(,1.0],[1.2,)").unwrap();
+    ///
+    /// assert_eq!(range.restrictions().len(), 2);
+    /// assert_eq!(range.restrictions()[0].0, Bound::Unbounded);
+    /// ```
+    pub fn restrictions(&self) -> &[(Bound<VersionBuf>, Bound<VersionBuf>)] {
+        &self.restrictions
+    }
+
+    /// Check whether this range contains the given `version`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use version_compare::{MavenRange, Version};
+    ///
+    /// let range = MavenRange::parse("[1.0,2.0)").unwrap();
+    ///
+    /// assert!(range.contains(&Version::from("1.0.0").unwrap()));
+    /// assert!(range.contains(&Version::from("2.0-SNAPSHOT").unwrap()));
+    /// assert!(!range.contains(&Version::from("2.0").unwrap()));
+    /// ```
+    pub fn contains(&self, version: &Version) -> bool {
+        // Compare from the side of the bounds, so the Maven scheme is used
+        self.restrictions.iter().any(|(lower, upper)| {
+            let above = match lower {
+                Bound::Included(v) => v.as_version().compare_to(version, Cmp::Le),
+                Bound::Excluded(v) => v.as_version().compare_to(version, Cmp::Lt),
+                Bound::Unbounded => true,
+            };
+            let below = match upper {
+                Bound::Included(v) => v.as_version().compare_to(version, Cmp::Ge),
+                Bound::Excluded(v) => v.as_version().compare_to(version, Cmp::Gt),
+                Bound::Unbounded => true,
+            };
+            above && below
+        })
+    }
+}
+
+impl FromStr for MavenRange {
+    type Err = Error;
+
+    fn from_str(range: &str) -> Result<Self, Self::Err> {
+        MavenRange::parse(range)
+    }
+}
+
+/// Format the range in Maven's notation, that parses into an equal range.
+impl fmt::Display for MavenRange {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if let Some(version) = &self.recommended {
+            return write!(f, "{}", version);
+        }
+
+        for (i, restriction) in self.restrictions.iter().enumerate() {
+            if i > 0 {
+                write!(f, ",")?;
+            }
+            match restriction {
+                (Bound::Included(lower), Bound::Included(upper))
+                    if lower.as_str() == upper.as_str() =>
+                {
+                    write!(f, "[{}]", lower)?;
+                }
+                (lower, upper) => {
+                    match lower {
+                        Bound::Included(v) => write!(f, "[{}", v)?,
+                        Bound::Excluded(v) => write!(f, "({}", v)?,
+                        Bound::Unbounded => write!(f, "(")?,
+                    }
+                    match upper {
+                        Bound::Included(v) => write!(f, ",{}]", v)?,
+                        Bound::Excluded(v) => write!(f, ",{})", v)?,
+                        Bound::Unbounded => write!(f, ",)")?,
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Parse the set between the brackets at byte offsets `start` and `end`.
+fn parse_restriction(
+    range: &str,
+    start: usize,
+    end: usize,
+) -> Result<(Bound<VersionBuf>, Bound<VersionBuf>), Error> {
+    let lower_inclusive = range[start..].starts_with('[');
+    let upper_inclusive = range[end..].starts_with(']');
+
+    // A single version is an exact version, and must be inclusive
+    let comma = match range[start + 1..end].find(',') {
+        Some(comma) => comma + start + 1,
+        None => {
+            if !lower_inclusive || !upper_inclusive {
+                return Err(Error::InvalidRange { at: start });
+            }
+            let version = parse_version(range, start + 1, end)?;
+            return Ok((Bound::Included(version.clone()), Bound::Included(version)));
+        }
+    };
+
+    let (lower, upper) = (range[start + 1..comma].trim(), range[comma + 1..end].trim());
+    if lower == upper {
+        return Err(Error::InvalidRange { at: start });
+    }
+    let bound = |version: &str, from: usize, to: usize, inclusive: bool| {
+        if version.is_empty() {
+            return Ok(Bound::Unbounded);
+        }
+        let version = parse_version(range, from, to)?;
+        Ok(if inclusive {
+            Bound::Included(version)
+        } else {
+            Bound::Excluded(version)
+        })
+    };
+    let lower = bound(lower, start + 1, comma, lower_inclusive)?;
+    let upper = bound(upper, comma + 1, end, upper_inclusive)?;
+
+    // Bounds may not be reversed
+    if let (Bound::Included(l), Bound::Included(u))
+    | (Bound::Included(l), Bound::Excluded(u))
+    | (Bound::Excluded(l), Bound::Included(u))
+    | (Bound::Excluded(l), Bound::Excluded(u)) = (&lower, &upper)
+    {
+        if u.compare_to(l, Cmp::Lt) {
+            return Err(Error::InvalidRange { at: start });
+        }
+    }
+
+    Ok((lower, upper))
+}
+
+/// Parse the version between byte offsets `from` and `to`, surrounding whitespace is trimmed.
+fn parse_version(range: &str, from: usize, to: usize) -> Result<VersionBuf, Error> {
+    let at = skip_whitespace(range, from);
+    let version = range[at.min(to)..to].trim_end();
+    if version.is_empty() {
+        return Err(Error::MissingVersion { at: to });
+    }
+    if let Some(i) = version.find(|c| matches!(c, '[' | ']' | '(' | ')' | ',')) {
+        return Err(Error::UnexpectedChar { at: at + i });
+    }
+
+    let manifest = Manifest {
+        scheme: Scheme::Maven,
+        ..Manifest::default()
+    };
+    VersionBuf::from_manifest(version, &manifest).map_err(|err| err.offset(at))
+}
+
+/// Get the byte offset of the first non-whitespace character at or after `at`.
+fn skip_whitespace(range: &str, at: usize) -> usize {
+    range[at..]
+        .find(|c: char| !c.is_whitespace())
+        .map_or(range.len(), |i| i + at)
+}
+
+#[cfg_attr(tarpaulin, skip)]
+#[cfg(test)]
+mod tests {
+    use std::ops::Bound;
+
+    use crate::{Error, Manifest, Scheme, Version};
+
+    use super::MavenRange;
+
+    /// Ranges, with versions they contain and don't contain.
+    const CONTAINS: &[(&str, &[&str], &[&str])] = &[
+        (
+            "(,1.0]",
+            &["0.9", "1.0", "1-ga", "1.0-rc1"],
+            &["1.0.1", "1.1"],
+        ),
+        ("[1.0]", &["1.0", "1", "1.0.0"], &["1.0.1", "0.9", "1.0-sp"]),
+        (
+            "[1.2,1.3]",
+            &["1.2", "1.2.5", "1.3"],
+            &["1.1", "1.3.1", "1.2-rc1"],
+        ),
+        (
+            "[1.0,2.0)",
+            &["1.0", "1.9.9", "2.0-SNAPSHOT"],
+            &["0.9", "2.0"],
+        ),
+        ("[1.5,)", &["1.5", "2", "100.0"], &["1.4", "1.5-beta-2"]),
+        (
+            "(,1.0],[1.2,)",
+            &["0.1", "1.0", "1.2", "3.0"],
+            &["1.1", "1.0.1", "1.2-rc1"],
+        ),
+        (
+            "(,1.1),(1.1,)",
+            &["1.0", "1.1.1", "1.1-sp"],
+            &["1.1", "1.1.0"],
+        ),
+        ("1.0", &["0.1", "1.0", "5.0"], &[]),
+        (
+            "[1.0,1.2),[1.2,1.3]",
+            &["1.0", "1.2", "1.3"],
+            &["0.9", "1.4"],
+        ),
+        // Sets with an unbounded side may overlap
+        ("[1.0,),[2.0,3.0]", &["1.0", "2.5", "5.0"], &["0.9"]),
+        ("[1.0,1.2),(,1.3]", &["0.5", "1.1", "1.3"], &["1.4"]),
+    ];
+
+    #[test]
+    fn parse() {
+        let range = MavenRange::parse("[1.0,2.0)").unwrap();
+        assert!(range.recommended().is_none());
+        match range.restrictions() {
+            [(Bound::Included(lower), Bound::Excluded(upper))] => {
+                assert_eq!(lower.as_str(), "1.0");
+                assert_eq!(upper.as_str(), "2.0");
+            }
+            restrictions => panic!("Unexpected restrictions {:?}", restrictions),
+        }
+
+        // Spacing must not matter, and the comma between sets is optional like in Maven
+        for other in &[" [ 1.0 , 2.0 ) ", "[1.0,2.0)\t"] {
+            assert_eq!(
+                MavenRange::parse(other).unwrap(),
+                range,
+                "Parsing {}",
+                other
+            );
+        }
+        assert_eq!(
+            MavenRange::parse("[1.0,1.1)[1.2,1.3)").unwrap(),
+            MavenRange::parse("[1.0,1.1),[1.2,1.3)").unwrap(),
+        );
+
+        // Qualifiers without numbers are valid Maven versions
+        assert!(MavenRange::parse("[alpha,beta]").is_ok());
+    }
+
+    #[test]
+    fn parse_error() {
+        let errors = [
+            ("", Error::MissingVersion { at: 0 }),
+            ("[1.0,1.2", Error::UnclosedBracket { at: 0 }),
+            ("[1.0,1.2),[1.3", Error::UnclosedBracket { at: 10 }),
+            ("(1.0)", Error::InvalidRange { at: 0 }),
+            ("[1.0,1.0]", Error::InvalidRange { at: 0 }),
+            ("(,)", Error::InvalidRange { at: 0 }),
+            ("[1.1,1.0]", Error::InvalidRange { at: 0 }),
+            ("[1.0,1.2),(1.1,1.3]", Error::InvalidRange { at: 10 }),
+            ("[1.2,1.3],[1.0,1.1]", Error::InvalidRange { at: 10 }),
+            ("[1.0,1.2),1.3", Error::UnexpectedChar { at: 10 }),
+            ("[1.0,1.2),", Error::EmptyClause { at: 9 }),
+            ("[1.0,1.2,1.3]", Error::UnexpectedChar { at: 8 }),
+            ("[]", Error::MissingVersion { at: 1 }),
+            ("[ ]", Error::MissingVersion { at: 2 }),
+            ("1.0]", Error::UnexpectedChar { at: 3 }),
+        ];
+        for (range, err) in &errors {
+            assert_eq!(
+                MavenRange::parse(range).unwrap_err(),
+                *err,
+                "Parsing {:?}",
+                range
+            );
+        }
+    }
+
+    #[test]
+    fn contains() {
+        for (range, contained, excluded) in CONTAINS {
+            let parsed = MavenRange::parse(range).unwrap();
+            for version in contained.iter() {
+                let version = Version::from(version).unwrap();
+                assert!(parsed.contains(&version), "{} contains {}", range, version);
+            }
+            for version in excluded.iter() {
+                let version = Version::from(version).unwrap();
+                assert!(!parsed.contains(&version), "{} excludes {}", range, version);
+            }
+        }
+
+        // The Maven scheme is used regardless of the manifest of the version
+        let manifest = Manifest {
+            scheme: Scheme::Maven,
+            ..Manifest::default()
+        };
+        let range = MavenRange::parse("[1.0-alpha,1.0-beta]").unwrap();
+        assert!(range.contains(&Version::from_manifest("1.0-alpha-2", &manifest).unwrap()));
+        assert!(range.contains(&Version::from("1.0-alpha-2").unwrap()));
+        assert!(!range.contains(&Version::from("1.0-milestone-1").unwrap()));
+    }
+
+    #[test]
+    fn display() {
+        let ranges = [
+            ("(,1.0]", "(,1.0]"),
+            ("[1.0]", "[1.0]"),
+            ("[ 1.2 , 1.3 ]", "[1.2,1.3]"),
+            ("(,1.0],[1.2,)", "(,1.0],[1.2,)"),
+            ("[1.0,1.1)[1.2,1.3)", "[1.0,1.1),[1.2,1.3)"),
+            ("[,1.0]", "(,1.0]"),
+            (" 1.0 ", "1.0"),
+        ];
+        for (range, display) in &ranges {
+            let parsed = MavenRange::parse(range).unwrap();
+            assert_eq!(parsed.to_string(), *display, "Formatting {}", range);
+            assert_eq!(MavenRange::parse(display).unwrap(), parsed);
+        }
+    }
+}
@@ -0,0 +1,1066 @@
+//! npm version range module, which provides the `NpmRange` struct.
+//!
+//! npm ranges are the range expressions of node-semver, such as `^1.2.3`, `~1.2`, `1.x`,
+//! `1.2 - 2.3.4` and `>=1.0.0 <2.0.0 || 3.x`. Like node-semver, they're desugared into sets of
+//! primitive comparators when parsing.
+
+use std::cmp::Ordering;
+use std::fmt;
+use std::str::FromStr;
+
+use super::semver::{for_identifiers, numeric};
+use crate::{Cmp, Error, Manifest, Scheme, SemverVersion, Version, VersionBuf};
+
+/// npm version range, compatible with the `Range` class of node-semver.
+///
+/// A range is a `||` separated list of comparator sets, and matches a version if any of its sets
+/// does. Each set is a whitespace separated list of comparators, and matches a version if all of
+/// them do. Caret, tilde, X-ranges and hyphen ranges are desugared into primitive comparators,
+/// exactly like node-semver does.
+///
+/// Like node-semver, a pre-release version only matches a set if a comparator of the set has a
+/// pre-release on the same `major.minor.patch`, unless the range is parsed with
+/// `parse_include_prerelease`.
+///
+/// # Examples
+///
+/// ```
+/// use version_compare::{NpmRange, Version};
+///
+/// let range = NpmRange::parse(">=1.0.0 <2.0.0 || 3.x").unwrap();
+///
+/// assert!(range.matches(&Version::from("1.4.0").unwrap()));
+/// assert!(range.matches(&Version::from("3.1.0").unwrap()));
+/// assert!(!range.matches(&Version::from("2.1.0").unwrap()));
+/// assert!(!range.matches(&Version::from("3.2.0-beta.1").unwrap()));
+/// assert_eq!(range.to_string(), ">=1.0.0 <2.0.0||>=3.0.0 <4.0.0-0");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NpmRange {
+    sets: Vec<Vec<(Cmp, VersionBuf)>>,
+    include_prerelease: bool,
+}
+
+impl NpmRange {
+    /// Parse an npm version range.
+    ///
+    /// Ranges are parsed like node-semver does without the `loose` option. Comparator versions
+    /// must be valid semantic versions, optionally prefixed with a `v`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use version_compare::{Error, NpmRange};
+    ///
+    /// assert!(NpmRange::parse("^1.2.3 || 2.x").is_ok());
+    /// assert_eq!(NpmRange::parse(">=01.2.3").unwrap_err(), Error::LeadingZero { at: 2 });
+    /// assert_eq!(NpmRange::parse("1.2.3 || >=").unwrap_err(), Error::MissingVersion { at: 11 });
+    /// ```
+    pub fn parse(range: &str) -> Result<Self, Error> {
+        NpmRange::parse_with(range, false)
+    }
+
+    /// Parse an npm version range, that matches pre-release versions like any other version.
+    ///
+    /// This is the `includePrerelease` option of node-semver, which also desugars ranges to
+    /// include pre-releases of their bounds.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use version_compare::{NpmRange, Version};
+    ///
+    /// let version = Version::from("1.3.0-beta").unwrap();
+    ///
+    /// assert!(!NpmRange::parse("^1.2.3").unwrap().matches(&version));
+    /// assert!(NpmRange::parse_include_prerelease("^1.2.3").unwrap().matches(&version));
+    /// ```
+    pub fn parse_include_prerelease(range: &str) -> Result<Self, Error> {
+        NpmRange::parse_with(range, true)
+    }
+
+    fn parse_with(range: &str, include_prerelease: bool) -> Result<Self, Error> {
+        let mut sets = Vec::new();
+        let mut at = 0;
+        for set in range.split("||") {
+            sets.push(parse_set(set, at, include_prerelease)?);
+            at += set.len() + 2;
+        }
+
+        // Sets matching nothing are dropped, and a set matching anything matches the range
+        if sets.len() > 1 {
+            let first = sets[0].clone();
+            sets.retain(|set| !is_null_set(set));
+            if sets.is_empty() {
+                sets.push(first);
+            } else if sets.iter().any(|set| set.is_empty()) {
+                sets = vec![Vec::new()];
+            }
+        }
+
+        Ok(NpmRange {
+            sets,
+            include_prerelease,
+        })
+    }
+
+    /// Get the comparator sets of this range.
+    ///
+    /// Versions of the comparators are normalized to `major.minor.patch[-pre]`. A set without
+    /// comparators matches any version.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use version_compare::{Cmp, NpmRange};
+    ///
+    /// let range = NpmRange::parse("~1.2 || *").unwrap();
+    /// assert_eq!(range.sets(), &[vec![]]);
+    ///
+    /// let range = NpmRange::parse("~1.2").unwrap();
+    /// assert_eq!(range.sets()[0][0].0, Cmp::Ge);
+    /// assert_eq!(range.sets()[0][0].1.as_str(), "1.2.0");
+    /// assert_eq!(range.sets()[0][1].0, Cmp::Lt);
+    /// assert_eq!(range.sets()[0][1].1.as_str(), "1.3.0-0");
+    /// ```
+    pub fn sets(&self) -> &[Vec<(Cmp, VersionBuf)>] {
+        &self.sets
+    }
+
+    /// Check whether this range was parsed to include pre-release versions.
+    pub fn includes_prerelease(&self) -> bool {
+        self.include_prerelease
+    }
+
+    /// Check whether the given `version` matches this range.
+    ///
+    /// Like node-semver, surrounding whitespace and a leading `v` are ignored. A version that
+    /// isn't a valid semantic version never matches.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use version_compare::{NpmRange, Version};
+    ///
+    /// let range = NpmRange::parse("1.2 - 2.3.4").unwrap();
+    ///
+    /// assert!(range.matches(&Version::from("v1.2.0").unwrap()));
+    /// assert!(range.matches(&Version::from("2.3.4").unwrap()));
+    /// assert!(!range.matches(&Version::from("2.3.5").unwrap()));
+    /// assert!(!range.matches(&Version::from("2.3").unwrap()));
+    /// ```
+    pub fn matches(&self, version: &Version) -> bool {
+        let version = version.as_str().trim();
+        let version = if version.starts_with('v') {
+            &version[1..]
+        } else {
+            version
+        };
+        let version = match SemverVersion::parse(version) {
+            Ok(version) => version,
+            Err(_) => return false,
+        };
+
+        self.sets
+            .iter()
+            .any(|set| matches_set(set, &version, self.include_prerelease))
+    }
+}
+
+impl FromStr for NpmRange {
+    type Err = Error;
+
+    fn from_str(range: &str) -> Result<Self, Self::Err> {
+        NpmRange::parse(range)
+    }
+}
+
+/// Format the desugared range, like the `range` property of a node-semver `Range`.
+impl fmt::Display for NpmRange {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (i, set) in self.sets.iter().enumerate() {
+            if i > 0 {
+                write!(f, "||")?;
+            }
+            for (j, (op, version)) in set.iter().enumerate() {
+                if j > 0 {
+                    write!(f, " ")?;
+                }
+                match op {
+                    Cmp::Eq => write!(f, "{}", version)?,
+                    op => write!(f, "{}{}", op.sign(), version)?,
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Check whether a parsed `version` matches all comparators of a set.
+fn matches_set(
+    set: &[(Cmp, VersionBuf)],
+    version: &SemverVersion,
+    include_prerelease: bool,
+) -> bool {
+    let mut prerelease_allowed = include_prerelease || !version.is_prerelease();
+    for (op, bound) in set {
+        let bound = match SemverVersion::parse(bound.as_str()) {
+            Ok(bound) => bound,
+            Err(_) => return false,
+        };
+        let ordering = version.cmp(&bound);
+        let matches = match op {
+            Cmp::Eq => ordering == Ordering::Equal,
+            Cmp::Ne => ordering != Ordering::Equal,
+            Cmp::Lt => ordering == Ordering::Less,
+            Cmp::Le => ordering != Ordering::Greater,
+            Cmp::Ge => ordering != Ordering::Less,
+            Cmp::Gt => ordering == Ordering::Greater,
+        };
+        if !matches {
+            return false;
+        }
+
+        // Pre-releases only match if a comparator has one on the same version
+        if bound.is_prerelease()
+            && bound.major() == version.major()
+            && bound.minor() == version.minor()
+            && bound.patch() == version.patch()
+        {
+            prerelease_allowed = true;
+        }
+    }
+    prerelease_allowed
+}
+
+/// Check whether a comparator set is `<0.0.0-0`, which matches nothing.
+fn is_null_set(set: &[(Cmp, VersionBuf)]) -> bool {
+    match set {
+        [(Cmp::Lt, version)] => version.as_str() == "0.0.0-0",
+        _ => false,
+    }
+}
+
+/// Parse a `||` separated comparator set located at byte offset `at`.
+fn parse_set(
+    set: &str,
+    at: usize,
+    include_prerelease: bool,
+) -> Result<Vec<(Cmp, VersionBuf)>, Error> {
+    let mut words = Vec::new();
+    let mut start = None;
+    for (i, c) in set.char_indices().chain(Some((set.len(), ' '))) {
+        match (start, c.is_whitespace()) {
+            (Some(from), true) => {
+                words.push((from, &set[from..i]));
+                start = None;
+            }
+            (None, false) => start = Some(i),
+            _ => {}
+        }
+    }
+    let end = |i: usize| words[i].0 + words[i].1.len();
+
+    let mut comparators = Vec::new();
+    let mut push = |comparator: &str, at: usize| {
+        parse_comparator(comparator, include_prerelease)
+            .map(|comparator| comparators.push(comparator))
+            .map_err(|err| err.offset(at))
+    };
+
+    // A hyphen between two partial versions is a hyphen range, which is the whole set
+    if let Some(hyphen) = words.iter().position(|(_, word)| *word == "-") {
+        if hyphen > 0 && hyphen + 1 < words.len() {
+            let (from_at, to_at) = (words[0].0, words[hyphen + 1].0);
+            let from = parse_xrange(&set[from_at..end(hyphen - 1)]);
+            let to = parse_xrange(&set[to_at..end(words.len() - 1)]);
+            if let (Some(from), Some(to)) = (from, to) {
+                let desugared = replace_hyphen((from_at, from), (to_at, to), include_prerelease)
+                    .map_err(|err| err.offset(at))?;
+                for comparator in desugared.split_whitespace() {
+                    push(comparator, at + from_at)?;
+                }
+                return Ok(finish_set(comparators));
+            }
+        }
+    }
+
+    // An operator separated from its version by whitespace is joined with it
+    let mut i = 0;
+    while i < words.len() {
+        let start = words[i].0;
+        if matches!(words[i].1, "<" | ">" | "<=" | ">=" | "=" | "~" | "~>" | "^")
+            && i + 1 < words.len()
+        {
+            i += 1;
+        }
+        let token = &set[start..end(i)];
+        i += 1;
+
+        // Ranges are desugared into primitive comparators, anything else must be one already
+        match replace_caret(token, include_prerelease)
+            .or_else(|| replace_tilde(token))
+            .or_else(|| replace_xrange(token, include_prerelease))
+        {
+            Some(desugared) => {
+                for comparator in desugared.split_whitespace() {
+                    push(comparator, at + start)?;
+                }
+            }
+            None => push(token, at + start)?,
+        }
+    }
+
+    Ok(finish_set(comparators))
+}
+
+/// Finish a comparator set, where `None` is a comparator matching anything.
+fn finish_set(comparators: Vec<Option<(Cmp, VersionBuf)>>) -> Vec<(Cmp, VersionBuf)> {
+    let mut set: Vec<(Cmp, VersionBuf)> = Vec::new();
+    for comparator in comparators.into_iter().flatten() {
+        // A comparator matching nothing makes the whole set match nothing
+        if comparator.0 == Cmp::Lt && comparator.1.as_str() == "0.0.0-0" {
+            return vec![comparator];
+        }
+        let duplicate = set
+            .iter()
+            .any(|(op, version)| *op == comparator.0 && version.as_str() == comparator.1.as_str());
+        if !duplicate {
+            set.push(comparator);
+        }
+    }
+    set
+}
+
+/// Parse a primitive comparator, such as `>=1.2.3`.
+///
+/// Returns `None` for a comparator matching anything.
+fn parse_comparator(
+    comparator: &str,
+    include_prerelease: bool,
+) -> Result<Option<(Cmp, VersionBuf)>, Error> {
+    if comparator.is_empty() {
+        return Ok(None);
+    }
+    let (op, version) = split_operator(comparator);
+    let version = version.trim_start();
+    if op == ">="
+        && version
+            == if include_prerelease {
+                "0.0.0-0"
+            } else {
+                "0.0.0"
+            }
+    {
+        return Ok(None);
+    }
+
+    let mut at = comparator.len() - version.len();
+    let version = if version.starts_with('v') {
+        at += 1;
+        &version[1..]
+    } else {
+        version
+    };
+    if version.is_empty() {
+        return Err(Error::MissingVersion { at });
+    }
+    let version = SemverVersion::parse(version).map_err(|err| err.offset(at))?;
+
+    let op = match op {
+        "<" => Cmp::Lt,
+        "<=" => Cmp::Le,
+        ">" => Cmp::Gt,
+        ">=" => Cmp::Ge,
+        _ => Cmp::Eq,
+    };
+    let normalized = match version.pre() {
+        Some(pre) => format!(
+            "{}.{}.{}-{}",
+            version.major(),
+            version.minor(),
+            version.patch(),
+            pre
+        ),
+        None => format!(
+            "{}.{}.{}",
+            version.major(),
+            version.minor(),
+            version.patch()
+        ),
+    };
+    let manifest = Manifest {
+        scheme: Scheme::Semver,
+        ..Manifest::default()
+    };
+    VersionBuf::from_manifest(normalized, &manifest).map(|version| Some((op, version)))
+}
+
+/// Split a leading `<`, `>`, `<=`, `>=` or `=` operator off a comparator.
+fn split_operator(comparator: &str) -> (&str, &str) {
+    let mut len = 0;
+    if comparator.starts_with(|c| c == '<' || c == '>') {
+        len = 1;
+    }
+    if comparator[len..].starts_with('=') {
+        len += 1;
+    }
+    comparator.split_at(len)
+}
+
+/// Partial version, where missing and wildcard identifiers are `None`.
+#[derive(Clone, Copy)]
+struct XRange<'a> {
+    version: &'a str,
+    major: Option<&'a str>,
+    minor: Option<&'a str>,
+    patch: Option<&'a str>,
+    pre: Option<&'a str>,
+}
+
+/// Parse a partial version such as `1.2`, `1.x` or `1.2.3-beta`, ignoring leading `v`, `=` and
+/// whitespace characters like node-semver.
+///
+/// Returns `None` if this isn't a partial version.
+fn parse_xrange(version: &str) -> Option<XRange<'_>> {
+    let trimmed = version.trim_start_matches(|c: char| c == 'v' || c == '=' || c.is_whitespace());
+    let (rest, build) = match trimmed.find('+') {
+        Some(plus) => (&trimmed[..plus], Some(&trimmed[plus + 1..])),
+        None => (trimmed, None),
+    };
+    let (core, pre) = match rest.find('-') {
+        Some(hyphen) => (&rest[..hyphen], Some(&rest[hyphen + 1..])),
+        None => (rest, None),
+    };
+
+    // Identifiers are numbers without leading zeros, or wildcards
+    let identifiers: Vec<&str> = core.split('.').collect();
+    let valid = |id: &&str| {
+        matches!(*id, "0" | "x" | "X" | "*")
+            || (!id.starts_with('0') && !id.is_empty() && id.bytes().all(|b| b.is_ascii_digit()))
+    };
+    if identifiers.len() > 3 || !identifiers.iter().all(valid) {
+        return None;
+    }
+
+    // Pre-release and build metadata may only follow three identifiers
+    if (pre.is_some() || build.is_some()) && identifiers.len() < 3 {
+        return None;
+    }
+    if let Some(pre) = pre {
+        for_identifiers(pre, 0, |identifier, at| numeric(identifier, at).map(|_| ())).ok()?;
+    }
+    if let Some(build) = build {
+        for_identifiers(build, 0, |_, _| Ok(())).ok()?;
+    }
+
+    let number = |i: usize| {
+        identifiers
+            .get(i)
+            .cloned()
+            .filter(|id| !matches!(*id, "x" | "X" | "*"))
+    };
+    let major = number(0);
+    let minor = major.and(number(1));
+    Some(XRange {
+        version,
+        major,
+        minor,
+        patch: minor.and(number(2)),
+        pre,
+    })
+}
+
+/// Increment a decimal number.
+fn increment(number: &str) -> String {
+    let mut digits = number.as_bytes().to_vec();
+    for digit in digits.iter_mut().rev() {
+        if *digit == b'9' {
+            *digit = b'0';
+        } else {
+            *digit += 1;
+            return String::from_utf8(digits).unwrap();
+        }
+    }
+    digits.insert(0, b'1');
+    String::from_utf8(digits).unwrap()
+}
+
+/// Desugar a caret range such as `^1.2.3`, allowing changes that don't modify the left-most
+/// non-zero identifier.
+fn replace_caret(token: &str, include_prerelease: bool) -> Option<String> {
+    if !token.starts_with('^') {
+        return None;
+    }
+    let x = parse_xrange(&token[1..])?;
+    let z = if include_prerelease { "-0" } else { "" };
+
+    let (major, minor, patch) = match (x.major, x.minor, x.patch) {
+        (None, _, _) => return Some(String::new()),
+        (Some(major), None, _) => {
+            return Some(format!(">={}.0.0{} <{}.0.0-0", major, z, increment(major)))
+        }
+        (Some("0"), Some(minor), None) => {
+            return Some(format!(">=0.{}.0{} <0.{}.0-0", minor, z, increment(minor)))
+        }
+        (Some(major), Some(minor), None) => {
+            return Some(format!(
+                ">={}.{}.0{} <{}.0.0-0",
+                major,
+                minor,
+                z,
+                increment(major)
+            ))
+        }
+        (Some(major), Some(minor), Some(patch)) => (major, minor, patch),
+    };
+
+    // Like node-semver, only zero major versions include pre-releases of the lower bound
+    let from = match x.pre {
+        Some(pre) => format!("{}.{}.{}-{}", major, minor, patch, pre),
+        None if major == "0" => format!("0.{}.{}{}", minor, patch, z),
+        None => format!("{}.{}.{}", major, minor, patch),
+    };
+    let to = match (major, minor) {
+        ("0", "0") => format!("0.0.{}", increment(patch)),
+        ("0", _) => format!("0.{}.0", increment(minor)),
+        _ => format!("{}.0.0", increment(major)),
+    };
+    Some(format!(">={} <{}-0", from, to))
+}
+
+/// Desugar a tilde range such as `~1.2.3`, allowing patch level changes.
+fn replace_tilde(token: &str) -> Option<String> {
+    if !token.starts_with('~') {
+        return None;
+    }
+    let rest = &token[1..];
+    let x = parse_xrange(if rest.starts_with('>') {
+        &rest[1..]
+    } else {
+        rest
+    })?;
+
+    Some(match (x.major, x.minor, x.patch) {
+        (None, _, _) => String::new(),
+        (Some(major), None, _) => format!(">={}.0.0 <{}.0.0-0", major, increment(major)),
+        (Some(major), Some(minor), None) => {
+            format!(
+                ">={}.{}.0 <{}.{}.0-0",
+                major,
+                minor,
+                major,
+                increment(minor)
+            )
+        }
+        (Some(major), Some(minor), Some(patch)) => format!(
+            ">={}.{}.{}{} <{}.{}.0-0",
+            major,
+            minor,
+            patch,
+            x.pre.map_or(String::new(), |pre| format!("-{}", pre)),
+            major,
+            increment(minor),
+        ),
+    })
+}
+
+/// Desugar an X-range such as `1.x` or `>=1.2`, which may have an operator.
+///
+/// Returns `None` for a full version, which is a primitive comparator already.
+fn replace_xrange(token: &str, include_prerelease: bool) -> Option<String> {
+    let (op, rest) = split_operator(token);
+    let x = parse_xrange(rest)?;
+    let pr = if include_prerelease { "-0" } else { "" };
+
+    let major = match x.major {
+        // Nothing is below or above any version
+        None if op == "<" || op == ">" => return Some("<0.0.0-0".to_string()),
+        None => return Some(String::new()),
+        Some(major) => major,
+    };
+    Some(match (x.minor, x.patch, op) {
+        (Some(_), Some(_), _) => return None,
+        (None, _, "") | (None, _, "=") => {
+            format!(">={}.0.0{} <{}.0.0-0", major, pr, increment(major))
+        }
+        (Some(minor), _, "") | (Some(minor), _, "=") => format!(
+            ">={}.{}.0{} <{}.{}.0-0",
+            major,
+            minor,
+            pr,
+            major,
+            increment(minor)
+        ),
+        // Above or up to a partial version rounds up to the next major or minor version
+        (None, _, ">") => format!(">={}.0.0{}", increment(major), pr),
+        (Some(minor), _, ">") => format!(">={}.{}.0{}", major, increment(minor), pr),
+        (None, _, "<=") => format!("<{}.0.0-0", increment(major)),
+        (Some(minor), _, "<=") => format!("<{}.{}.0-0", major, increment(minor)),
+        (minor, _, "<") => format!("<{}.{}.0-0", major, minor.unwrap_or("0")),
+        (minor, _, op) => format!("{}{}.{}.0{}", op, major, minor.unwrap_or("0"), pr),
+    })
+}
+
+/// Desugar a hyphen range such as `1.2 - 2.3.4`, of which the bounds are located at the given
+/// byte offsets.
+///
+/// A bound that is a full version is used as is, and an error is returned if it isn't a valid
+/// comparator version.
+fn replace_hyphen(
+    (from_at, from): (usize, XRange),
+    (to_at, to): (usize, XRange),
+    include_prerelease: bool,
+) -> Result<String, Error> {
+    let z = if include_prerelease { "-0" } else { "" };
+
+    let from = match (from.major, from.minor, from.patch) {
+        (None, _, _) => String::new(),
+        (Some(major), None, _) => format!(">={}.0.0{}", major, z),
+        (Some(major), Some(minor), None) => format!(">={}.{}.0{}", major, minor, z),
+        _ if from.pre.is_some() => format!(">={}", full(from.version, from_at)?),
+        _ => format!(">={}{}", full(from.version, from_at)?, z),
+    };
+    let to = match (to.major, to.minor, to.patch) {
+        (None, _, _) => String::new(),
+        (Some(major), None, _) => format!("<{}.0.0-0", increment(major)),
+        (Some(major), Some(minor), None) => format!("<{}.{}.0-0", major, increment(minor)),
+        (Some(major), Some(minor), Some(patch)) => match to.pre {
+            Some(pre) => format!("<={}.{}.{}-{}", major, minor, patch, pre),
+            None if include_prerelease => format!("<{}.{}.{}-0", major, minor, increment(patch)),
+            None => format!("<={}", full(to.version, to_at)?),
+        },
+    };
+    Ok(format!("{} {}", from, to))
+}
+
+/// Check a full version bound of a hyphen range located at byte offset `at`, which may only be
+/// prefixed with a single `v`.
+fn full(version: &str, at: usize) -> Result<&str, Error> {
+    let prefix = version.len() - version.trim_start_matches(|c| c == 'v' || c == '=').len();
+    match prefix {
+        0 => Ok(version),
+        1 if version.starts_with('v') => Ok(version),
+        _ if version.starts_with('v') => Err(Error::UnexpectedChar { at: at + 1 }),
+        _ => Err(Error::UnexpectedChar { at }),
+    }
+}
+
+#[cfg_attr(tarpaulin, skip)]
+#[cfg(test)]
+mod tests {
+    use crate::{Cmp, Error, Version};
+
+    use super::NpmRange;
+
+    /// Ranges with their desugared form, or `None` if invalid, from the node-semver fixtures.
+    ///
+    /// The second field is whether pre-releases are included.
+    const PARSE: &[(&str, bool, Option<&str>)] = &[
+        ("1.0.0 - 2.0.0", false, Some(">=1.0.0 <=2.0.0")),
+        ("1.0.0 - 2.0.0", true, Some(">=1.0.0-0 <2.0.1-0")),
+        ("1 - 2", false, Some(">=1.0.0 <3.0.0-0")),
+        ("1 - 2", true, Some(">=1.0.0-0 <3.0.0-0")),
+        ("1.0 - 2.0", false, Some(">=1.0.0 <2.1.0-0")),
+        ("1.0.0", false, Some("1.0.0")),
+        (">=*", false, Some("")),
+        ("", false, Some("")),
+        ("*", false, Some("")),
+        (">=1.0.0", false, Some(">=1.0.0")),
+        (">1.0.0", false, Some(">1.0.0")),
+        ("<=2.0.0", false, Some("<=2.0.0")),
+        ("1", false, Some(">=1.0.0 <2.0.0-0")),
+        ("<2.0.0", false, Some("<2.0.0")),
+        (">= 1.0.0", false, Some(">=1.0.0")),
+        (">=  1.0.0", false, Some(">=1.0.0")),
+        ("> 1.0.0", false, Some(">1.0.0")),
+        ("<=   2.0.0", false, Some("<=2.0.0")),
+        ("<    2.0.0", false, Some("<2.0.0")),
+        ("<\t2.0.0", false, Some("<2.0.0")),
+        (">=0.1.97", false, Some(">=0.1.97")),
+        ("0.1.20 || 1.2.4", false, Some("0.1.20||1.2.4")),
+        (">=0.2.3 || <0.0.1", false, Some(">=0.2.3||<0.0.1")),
+        ("||", false, Some("")),
+        ("2.x.x", false, Some(">=2.0.0 <3.0.0-0")),
+        ("1.2.x", false, Some(">=1.2.0 <1.3.0-0")),
+        (
+            "1.2.x || 2.x",
+            false,
+            Some(">=1.2.0 <1.3.0-0||>=2.0.0 <3.0.0-0"),
+        ),
+        ("x", false, Some("")),
+        ("2.*.*", false, Some(">=2.0.0 <3.0.0-0")),
+        ("1.2.*", false, Some(">=1.2.0 <1.3.0-0")),
+        (
+            "1.2.* || 2.*",
+            false,
+            Some(">=1.2.0 <1.3.0-0||>=2.0.0 <3.0.0-0"),
+        ),
+        ("2", false, Some(">=2.0.0 <3.0.0-0")),
+        ("2.3", false, Some(">=2.3.0 <2.4.0-0")),
+        ("~2.4", false, Some(">=2.4.0 <2.5.0-0")),
+        ("~>3.2.1", false, Some(">=3.2.1 <3.3.0-0")),
+        ("~1", false, Some(">=1.0.0 <2.0.0-0")),
+        ("~>1", false, Some(">=1.0.0 <2.0.0-0")),
+        ("~> 1", false, Some(">=1.0.0 <2.0.0-0")),
+        ("~1.0", false, Some(">=1.0.0 <1.1.0-0")),
+        ("~ 1.0", false, Some(">=1.0.0 <1.1.0-0")),
+        ("^0", false, Some("<1.0.0-0")),
+        ("^ 1", false, Some(">=1.0.0 <2.0.0-0")),
+        ("^0.1", false, Some(">=0.1.0 <0.2.0-0")),
+        ("^1.0", false, Some(">=1.0.0 <2.0.0-0")),
+        ("^1.2", false, Some(">=1.2.0 <2.0.0-0")),
+        ("^0.0.1", false, Some(">=0.0.1 <0.0.2-0")),
+        ("^0.0.1-beta", false, Some(">=0.0.1-beta <0.0.2-0")),
+        ("^0.1.2", false, Some(">=0.1.2 <0.2.0-0")),
+        ("^1.2.3", false, Some(">=1.2.3 <2.0.0-0")),
+        ("^1.2.3-beta.4", false, Some(">=1.2.3-beta.4 <2.0.0-0")),
+        ("<1", false, Some("<1.0.0-0")),
+        ("< 1", false, Some("<1.0.0-0")),
+        (">=1", false, Some(">=1.0.0")),
+        (">= 1", false, Some(">=1.0.0")),
+        ("<1.2", false, Some("<1.2.0-0")),
+        ("< 1.2", false, Some("<1.2.0-0")),
+        ("^ 1.2 ^ 1", false, Some(">=1.2.0 <2.0.0-0 >=1.0.0")),
+        ("1.2 - 3.4.5", false, Some(">=1.2.0 <=3.4.5")),
+        ("1.2.3 - 3.4", false, Some(">=1.2.3 <3.5.0-0")),
+        ("1.2 - 3.4", false, Some(">=1.2.0 <3.5.0-0")),
+        (">1", false, Some(">=2.0.0")),
+        (">1.2", false, Some(">=1.3.0")),
+        (">X", false, Some("<0.0.0-0")),
+        ("<X", false, Some("<0.0.0-0")),
+        ("<x <* || >* 2.x", false, Some("<0.0.0-0")),
+        (">x 2.x || * || <x", false, Some("")),
+        (">01.02.03", false, None),
+        ("~1.2.3beta", false, None),
+        ("=0.7.x", false, Some(">=0.7.0 <0.8.0-0")),
+        ("<=0.7.x", false, Some("<0.8.0-0")),
+        (">=0.7.x", false, Some(">=0.7.0")),
+        ("~1.2.1 >=1.2.3", false, Some(">=1.2.1 <1.3.0-0 >=1.2.3")),
+        ("^1.2.3", true, Some(">=1.2.3 <2.0.0-0")),
+        ("^0.1.2", true, Some(">=0.1.2-0 <0.2.0-0")),
+        ("^0.0.1", true, Some(">=0.0.1-0 <0.0.2-0")),
+        ("~1.2", true, Some(">=1.2.0 <1.3.0-0")),
+        ("1.2.x", true, Some(">=1.2.0-0 <1.3.0-0")),
+        (">=0.0.0", false, Some("")),
+        (">=0.0.0", true, Some(">=0.0.0")),
+        (">=0.0.0-0", true, Some("")),
+        ("<0.0.0-0 || 1.x", false, Some(">=1.0.0 <2.0.0-0")),
+        ("<0.0.0-0 || <0.0.0-0", false, Some("<0.0.0-0")),
+        (">1.2.3 >1.2.3", false, Some(">1.2.3")),
+        ("=1.2.3", false, Some("1.2.3")),
+        ("v1.2.3", false, Some("1.2.3")),
+        ("1.2.3 - =2.0.0", false, None),
+        ("=1.2.3 - 2.0.0", false, None),
+        ("1.2.3+build - 2.0.0", true, Some(">=1.2.3 <2.0.1-0")),
+        (">=1.2.3 <", false, None),
+        (">= 1.2.3 ^", false, None),
+        ("1.2.3 ||| 2", false, None),
+        ("1.2.3 - 2.0.0 - 3", false, None),
+        ("*.2.3", false, Some("")),
+        ("1.*.3", false, Some(">=1.0.0 <2.0.0-0")),
+        (">=1.2.3-01", false, None),
+        ("^1.2.3.4", false, None),
+        ("blerg", false, None),
+        ("1.2.3 foo", false, None),
+    ];
+
+    /// Ranges with a version they include, from the node-semver fixtures.
+    const INCLUDE: &[(&str, &str, bool)] = &[
+        ("1.0.0 - 2.0.0", "1.2.3", false),
+        ("^1.2.3+build", "1.2.3", false),
+        ("^1.2.3+build", "1.3.0", false),
+        ("1.2.3-pre+asdf - 2.4.3-pre+asdf", "1.2.3", false),
+        ("1.2.3-pre+asdf - 2.4.3-pre+asdf", "1.2.3-pre.2", false),
+        ("1.2.3-pre+asdf - 2.4.3-pre+asdf", "2.4.3-alpha", false),
+        ("1.2.3+asdf - 2.4.3+asdf", "1.2.3", false),
+        ("1.0.0", "1.0.0", false),
+        (">=*", "0.2.4", false),
+        ("", "1.0.0", false),
+        ("*", "1.2.3", false),
+        (">=1.0.0", "1.0.0", false),
+        (">=1.0.0", "1.0.1", false),
+        (">=1.0.0", "1.1.0", false),
+        (">1.0.0", "1.0.1", false),
+        (">1.0.0", "1.1.0", false),
+        ("<=2.0.0", "2.0.0", false),
+        ("<=2.0.0", "1.9999.9999", false),
+        ("<=2.0.0", "0.2.9", false),
+        ("<2.0.0", "1.9999.9999", false),
+        ("<2.0.0", "0.2.9", false),
+        (">= 1.0.0", "1.0.0", false),
+        (">=  1.0.0", "1.0.1", false),
+        (">=   1.0.0", "1.1.0", false),
+        ("> 1.0.0", "1.0.1", false),
+        (">  1.0.0", "1.1.0", false),
+        ("<=   2.0.0", "2.0.0", false),
+        ("<= 2.0.0", "1.9999.9999", false),
+        ("<=  2.0.0", "0.2.9", false),
+        ("<    2.0.0", "1.9999.9999", false),
+        ("<\t2.0.0", "0.2.9", false),
+        (">=0.1.97", "0.1.97", false),
+        ("0.1.20 || 1.2.4", "1.2.4", false),
+        (">=0.2.3 || <0.0.1", "0.0.0", false),
+        (">=0.2.3 || <0.0.1", "0.2.3", false),
+        (">=0.2.3 || <0.0.1", "0.2.4", false),
+        ("||", "1.3.4", false),
+        ("2.x.x", "2.1.3", false),
+        ("1.2.x", "1.2.3", false),
+        ("1.2.x || 2.x", "2.1.3", false),
+        ("1.2.x || 2.x", "1.2.3", false),
+        ("x", "1.2.3", false),
+        ("2.*.*", "2.1.3", false),
+        ("1.2.*", "1.2.3", false),
+        ("1.2.* || 2.*", "2.1.3", false),
+        ("1.2.* || 2.*", "1.2.3", false),
+        ("2", "2.1.2", false),
+        ("2.3", "2.3.1", false),
+        ("~0.0.1", "0.0.1", false),
+        ("~0.0.1", "0.0.2", false),
+        ("~x", "0.0.9", false),
+        ("~2", "2.0.9", false),
+        ("~2.4", "2.4.0", false),
+        ("~2.4", "2.4.5", false),
+        ("~>3.2.1", "3.2.2", false),
+        ("~1", "1.2.3", false),
+        ("~>1", "1.2.3", false),
+        ("~> 1", "1.2.3", false),
+        ("~1.0", "1.0.2", false),
+        ("~ 1.0", "1.0.2", false),
+        ("~ 1.0.3", "1.0.12", false),
+        (">=1", "1.0.0", false),
+        (">= 1", "1.0.0", false),
+        ("<1.2", "1.1.1", false),
+        ("< 1.2", "1.1.1", false),
+        ("~v0.5.4-pre", "0.5.5", false),
+        ("~v0.5.4-pre", "0.5.4", false),
+        ("=0.7.x", "0.7.2", false),
+        ("<=0.7.x", "0.7.2", false),
+        (">=0.7.x", "0.7.2", false),
+        ("<=0.7.x", "0.6.2", false),
+        ("~1.2.1 >=1.2.3", "1.2.3", false),
+        ("~1.2.1 =1.2.3", "1.2.3", false),
+        ("~1.2.1 1.2.3", "1.2.3", false),
+        ("~1.2.1 >=1.2.3 1.2.3", "1.2.3", false),
+        ("~1.2.1 1.2.3 >=1.2.3", "1.2.3", false),
+        (">=1.2.1 1.2.3", "1.2.3", false),
+        ("1.2.3 >=1.2.1", "1.2.3", false),
+        (">=1.2.3 >=1.2.1", "1.2.3", false),
+        (">=1.2.1 >=1.2.3", "1.2.3", false),
+        (">=1.2", "1.2.8", false),
+        ("^1.2.3", "1.8.1", false),
+        ("^0.1.2", "0.1.2", false),
+        ("^0.1", "0.1.2", false),
+        ("^0.0.1", "0.0.1", false),
+        ("^1.2", "1.4.2", false),
+        ("^1.2 ^1", "1.4.2", false),
+        ("^1.2.3-alpha", "1.2.3-pre", false),
+        ("^1.2.0-alpha", "1.2.0-pre", false),
+        ("^0.0.1-alpha", "0.0.1-beta", false),
+        ("^0.0.1-alpha", "0.0.1", false),
+        ("^0.1.1-alpha", "0.1.1-beta", false),
+        ("^x", "1.2.3", false),
+        ("x - 1.0.0", "0.9.7", false),
+        ("x - 1.x", "0.9.7", false),
+        ("1.0.0 - x", "1.9.7", false),
+        ("1.x - x", "1.9.7", false),
+        ("<=7.x", "7.9.9", false),
+        ("2.x", "2.0.0-pre.0", true),
+        ("2.x", "2.1.0-pre.0", true),
+        ("1.1.x", "1.1.0-a", true),
+        ("1.1.x", "1.1.1-a", true),
+        ("*", "1.0.0-rc1", true),
+        ("^1.0.0-0", "1.0.1-rc1", true),
+        ("^1.0.0-rc2", "1.0.1-rc1", true),
+        ("^1.0.0", "1.0.1-rc1", true),
+        ("^1.0.0", "1.1.0-rc1", true),
+        ("1 - 2", "2.0.0-pre", true),
+        ("1 - 2", "1.0.0-pre", true),
+        ("1.0 - 2", "1.0.0-pre", true),
+        ("=0.7.x", "0.7.0-asdf", true),
+        (">=0.7.x", "0.7.0-asdf", true),
+        ("<=0.7.x", "0.7.0-asdf", true),
+        (">=1.0.0 <=1.1.0", "1.1.0-pre", true),
+    ];
+
+    /// Ranges with a version they exclude, from the node-semver fixtures.
+    const EXCLUDE: &[(&str, &str, bool)] = &[
+        ("1.0.0 - 2.0.0", "2.2.3", false),
+        ("1.2.3+asdf - 2.4.3+asdf", "1.2.3-pre.2", false),
+        ("1.2.3+asdf - 2.4.3+asdf", "2.4.3-alpha", false),
+        ("^1.2.3+build", "2.0.0", false),
+        ("^1.2.3+build", "1.2.0", false),
+        ("^1.2.3", "1.2.3-pre", false),
+        ("^1.2", "1.2.0-pre", false),
+        (">1.2", "1.3.0-beta", false),
+        ("<=1.2.3", "1.2.3-beta", false),
+        ("^1.2.3", "1.2.3-beta", false),
+        ("=0.7.x", "0.7.0-asdf", false),
+        (">=0.7.x", "0.7.0-asdf", false),
+        ("<=0.7.x", "0.7.0-asdf", false),
+        ("1.0.0", "1.0.1", false),
+        (">=1.0.0", "0.0.0", false),
+        (">=1.0.0", "0.0.1", false),
+        (">=1.0.0", "0.1.0", false),
+        (">1.0.0", "0.0.1", false),
+        (">1.0.0", "0.1.0", false),
+        ("<=2.0.0", "3.0.0", false),
+        ("<=2.0.0", "2.9999.9999", false),
+        ("<=2.0.0", "2.2.9", false),
+        ("<2.0.0", "2.9999.9999", false),
+        ("<2.0.0", "2.2.9", false),
+        (">=0.1.97", "0.1.93", false),
+        ("0.1.20 || 1.2.4", "1.2.3", false),
+        (">=0.2.3 || <0.0.1", "0.0.3", false),
+        (">=0.2.3 || <0.0.1", "0.2.2", false),
+        ("2.x.x", "1.1.3", false),
+        ("2.x.x", "3.1.3", false),
+        ("1.2.x", "1.3.3", false),
+        ("1.2.x || 2.x", "3.1.3", false),
+        ("1.2.x || 2.x", "1.1.3", false),
+        ("2.*.*", "1.1.3", false),
+        ("2.*.*", "3.1.3", false),
+        ("1.2.*", "1.3.3", false),
+        ("1.2.* || 2.*", "3.1.3", false),
+        ("1.2.* || 2.*", "1.1.3", false),
+        ("2", "1.1.2", false),
+        ("2.3", "2.4.1", false),
+        ("~0.0.1", "0.1.0-alpha", false),
+        ("~0.0.1", "0.1.0", false),
+        ("~2.4", "2.5.0", false),
+        ("~2.4", "2.3.9", false),
+        ("~>3.2.1", "3.3.2", false),
+        ("~>3.2.1", "3.2.0", false),
+        ("~1", "0.2.3", false),
+        ("~>1", "2.2.3", false),
+        ("~1.0", "1.1.0", false),
+        ("<1", "1.0.0", false),
+        (">=1.2", "1.1.1", false),
+        ("~v0.5.4-beta", "0.5.4-alpha", false),
+        ("=0.7.x", "0.8.2", false),
+        (">=0.7.x", "0.6.2", false),
+        ("<0.7.x", "0.7.2", false),
+        ("<1.2.3", "1.2.3-beta", false),
+        ("=1.2.3", "1.2.3-beta", false),
+        (">1.2", "1.2.8", false),
+        ("^0.0.1", "0.0.2-alpha", false),
+        ("^0.0.1", "0.0.2", false),
+        ("^1.2.3", "2.0.0-alpha", false),
+        ("^1.2.3", "1.2.2", false),
+        ("^1.2", "1.1.9", false),
+        ("^1.2.3", "2.0.0-pre", false),
+        ("^1.0.0-0", "1.0.1-rc1", false),
+        ("^1.0.0-rc2", "1.0.1-rc1", false),
+        ("^1.0.0", "1.0.1-rc1", false),
+        ("^1.0.0", "1.1.0-rc1", false),
+        ("1 - 2", "3.0.0-pre", true),
+        ("1 - 2", "2.0.0-pre", false),
+        ("1 - 2", "1.0.0-pre", false),
+        ("1.0 - 2", "1.0.0-pre", false),
+        ("1.1.x", "1.0.0-a", false),
+        ("1.1.x", "1.1.0-a", false),
+        ("1.1.x", "1.2.0-a", false),
+        ("1.1.x", "1.2.0-a", true),
+        ("1.1.x", "1.0.0-a", true),
+        ("1.x", "1.0.0-a", false),
+        ("1.x", "1.1.0-a", false),
+        ("1.x", "1.2.0-a", false),
+        ("1.x", "0.0.0-a", true),
+        ("1.x", "2.0.0-a", true),
+        (">=1.0.0 <1.1.0", "1.1.0", false),
+        (">=1.0.0 <1.1.0", "1.1.0", true),
+        (">=1.0.0 <1.1.0", "1.1.0-pre", false),
+        (">=1.0.0 <1.1.0-pre", "1.1.0-pre", false),
+        ("<x <* || >* 2.x", "1.2.3", false),
+        (">x 2.x || * || <x", "1.2.3-pre", false),
+    ];
+
+    /// Parse a range the way the tables specify.
+    fn parse(range: &str, include_prerelease: bool) -> Result<NpmRange, Error> {
+        if include_prerelease {
+            NpmRange::parse_include_prerelease(range)
+        } else {
+            NpmRange::parse(range)
+        }
+    }
+
+    #[test]
+    fn conformance_parse() {
+        for (range, include_prerelease, desugared) in PARSE {
+            let parsed = parse(range, *include_prerelease);
+            assert_eq!(
+                parsed.as_ref().ok().map(|range| range.to_string()),
+                desugared.map(|desugared| desugared.to_string()),
+                "Parsing {:?}, {:?}",
+                range,
+                parsed,
+            );
+        }
+    }
+
+    #[test]
+    fn conformance_matches() {
+        for (range, version, include_prerelease) in INCLUDE {
+            let parsed = parse(range, *include_prerelease).unwrap();
+            let version = Version::from(version).unwrap();
+            assert!(parsed.matches(&version), "{} includes {}", range, version);
+        }
+        for (range, version, include_prerelease) in EXCLUDE {
+            let parsed = parse(range, *include_prerelease).unwrap();
+            let version = Version::from(version).unwrap();
+            assert!(!parsed.matches(&version), "{} excludes {}", range, version);
+        }
+    }
+
+    #[test]
+    fn parse_error() {
+        let errors = [
+            (">=1.2.3 <", Error::MissingVersion { at: 9 }),
+            ("1.2.3 || >= v", Error::MissingVersion { at: 13 }),
+            (">01.02.03", Error::LeadingZero { at: 1 }),
+            ("^1.2.3.4", Error::UnexpectedChar { at: 0 }),
+            ("1.2.3 foo", Error::UnexpectedChar { at: 6 }),
+            ("1.2.3 ||| 2", Error::UnexpectedChar { at: 8 }),
+            ("1.2.3 - =2.0.0", Error::UnexpectedChar { at: 8 }),
+            ("v=1.2.3 - 2.0.0", Error::UnexpectedChar { at: 1 }),
+        ];
+        for (range, err) in &errors {
+            assert_eq!(
+                NpmRange::parse(range).unwrap_err(),
+                *err,
+                "Parsing {:?}",
+                range
+            );
+        }
+    }
+
+    #[test]
+    fn sets() {
+        let range = NpmRange::parse("^1.2.3 || 0.1.x").unwrap();
+        let sets: Vec<Vec<(Cmp, &str)>> = range
+            .sets()
+            .iter()
+            .map(|set| set.iter().map(|(op, v)| (*op, v.as_str())).collect())
+            .collect();
+        assert_eq!(
+            sets,
+            vec![
+                vec![(Cmp::Ge, "1.2.3"), (Cmp::Lt, "2.0.0-0")],
+                vec![(Cmp::Ge, "0.1.0"), (Cmp::Lt, "0.2.0-0")],
+            ],
+        );
+        assert!(!range.includes_prerelease());
+
+        // The desugared form parses into an equal range
+        assert_eq!(NpmRange::parse(&range.to_string()).unwrap(), range);
+    }
+
+    #[test]
+    fn matches_invalid() {
+        let range = NpmRange::parse("*").unwrap();
+        assert!(range.matches(&Version::from(" v1.2.3 ").unwrap()));
+        assert!(!range.matches(&Version::from("1.2").unwrap()));
+        assert!(!range.matches(&Version::from("1.2.3.4").unwrap()));
+        assert!(!range.matches(&Version::from("1.2.3-pre").unwrap()));
+    }
+}
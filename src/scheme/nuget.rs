@@ -0,0 +1,343 @@
+//! NuGet version scheme, which provides the `NuGetVersion` struct.
+//!
+//! NuGet package versions have the form `major[.minor[.patch[.revision]]][-release][+metadata]`,
+//! and are ordered like the default `VersionComparer` of `NuGet.Versioning`.
+
+use std::cmp::Ordering;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+
+use crate::{Error, Number, Part};
+
+use super::semver::for_identifiers;
+
+/// NuGet package version, ordered like `NuGet.Versioning`.
+///
+/// Versions have up to four numbers, missing numbers being zero. Release labels are compared
+/// like semantic version pre-release identifiers, except that labels are compared
+/// case-insensitively. Metadata is ignored when comparing.
+///
+/// # Examples
+///
+/// ```
+/// use version_compare::NuGetVersion;
+///
+/// let a = NuGetVersion::parse("1.0.0.0-Beta.2+sha.1a2b3c").unwrap();
+/// let b = NuGetVersion::parse("1.0-beta.2").unwrap();
+/// let c = NuGetVersion::parse("1.0.0.1").unwrap();
+///
+/// assert_eq!(a.release(), Some("Beta.2"));
+/// assert_eq!(a.metadata(), Some("sha.1a2b3c"));
+/// assert_eq!(a.normalized(), "1.0.0-Beta.2");
+/// assert_eq!(a, b);
+/// assert!(b < c);
+/// ```
+#[derive(Debug, Clone)]
+pub struct NuGetVersion<'a> {
+    version: &'a str,
+    numbers: [u32; 4],
+    release: Option<&'a str>,
+    labels: Vec<Part<'a>>,
+    metadata: Option<&'a str>,
+}
+
+impl<'a> NuGetVersion<'a> {
+    /// Parse a NuGet version string.
+    ///
+    /// Like `NuGetVersion.TryParse`, numbers may have leading zeros, but must fit an `i32`. An
+    /// error is returned for more than four numbers, empty numbers, labels or metadata, and
+    /// invalid characters.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use version_compare::{Error, NuGetVersion};
+    ///
+    /// assert_eq!(NuGetVersion::parse("01.2").unwrap().normalized(), "1.2.0");
+    /// assert_eq!(NuGetVersion::parse("1.2.3.4.5").unwrap_err(), Error::UnexpectedChar { at: 7 });
+    /// assert_eq!(NuGetVersion::parse("1.2-").unwrap_err(), Error::EmptyPart { at: 4 });
+    /// ```
+    pub fn parse(version: &'a str) -> Result<Self, Error> {
+        // Split off metadata, then the release labels
+        let (rest, metadata) = match version.find('+') {
+            Some(plus) => (&version[..plus], Some((plus + 1, &version[plus + 1..]))),
+            None => (version, None),
+        };
+        let (core, release) = match rest.find('-') {
+            Some(hyphen) => (&rest[..hyphen], Some((hyphen + 1, &rest[hyphen + 1..]))),
+            None => (rest, None),
+        };
+
+        // One to four numbers
+        let mut numbers = [0; 4];
+        let mut at = 0;
+        for (i, number) in core.split('.').enumerate() {
+            if i == 4 {
+                return Err(Error::UnexpectedChar { at: at - 1 });
+            }
+            if number.is_empty() {
+                return Err(Error::EmptyPart { at });
+            }
+            if let Some(i) = number.find(|c: char| !c.is_ascii_digit()) {
+                return Err(Error::UnexpectedChar { at: at + i });
+            }
+            numbers[i] = match number.parse() {
+                Ok(n) if n <= i32::MAX as u32 => n,
+                _ => return Err(Error::NumberOverflow { at }),
+            };
+            at += number.len() + 1;
+        }
+
+        let mut labels = Vec::new();
+        if let Some((at, release)) = release {
+            for_identifiers(release, at, |label, _| {
+                labels.push(match Number::parse(label) {
+                    Some(number) => Part::Number(number),
+                    None => Part::Text(label),
+                });
+                Ok(())
+            })?;
+        }
+        if let Some((at, metadata)) = metadata {
+            for_identifiers(metadata, at, |_, _| Ok(()))?;
+        }
+
+        Ok(NuGetVersion {
+            version,
+            numbers,
+            release: release.map(|(_, release)| release),
+            labels,
+            metadata: metadata.map(|(_, metadata)| metadata),
+        })
+    }
+
+    /// Get the major version number.
+    pub fn major(&self) -> u32 {
+        self.numbers[0]
+    }
+
+    /// Get the minor version number, `0` if missing.
+    pub fn minor(&self) -> u32 {
+        self.numbers[1]
+    }
+
+    /// Get the patch version number, `0` if missing.
+    pub fn patch(&self) -> u32 {
+        self.numbers[2]
+    }
+
+    /// Get the revision, the fourth version number, `0` if missing.
+    pub fn revision(&self) -> u32 {
+        self.numbers[3]
+    }
+
+    /// Get the release labels, without leading `-`, if any.
+    pub fn release(&self) -> Option<&'a str> {
+        self.release
+    }
+
+    /// Get the metadata, without leading `+`, if any.
+    pub fn metadata(&self) -> Option<&'a str> {
+        self.metadata
+    }
+
+    /// Check whether this is a pre-release, having release labels.
+    pub fn is_prerelease(&self) -> bool {
+        self.release.is_some()
+    }
+
+    /// Get the normalized version string, like `NuGetVersion.ToNormalizedString`.
+    ///
+    /// Has at least three numbers without leading zeros, the revision only if it isn't zero, and
+    /// no metadata.
+    pub fn normalized(&self) -> String {
+        let mut normalized = format!("{}.{}.{}", self.major(), self.minor(), self.patch());
+        if self.revision() > 0 {
+            normalized.push_str(&format!(".{}", self.revision()));
+        }
+        if let Some(release) = self.release {
+            normalized.push('-');
+            normalized.push_str(release);
+        }
+        normalized
+    }
+
+    /// Get the full version string.
+    pub fn as_str(&self) -> &'a str {
+        self.version
+    }
+
+    /// Compare only the version numbers, ignoring release labels and metadata.
+    pub(super) fn cmp_numbers(&self, other: &Self) -> Ordering {
+        self.numbers.cmp(&other.numbers)
+    }
+}
+
+impl<'a> Ord for NuGetVersion<'a> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.cmp_numbers(other)
+            .then_with(|| match (self.release, other.release) {
+                (None, None) => Ordering::Equal,
+                (None, Some(_)) => Ordering::Greater,
+                (Some(_), None) => Ordering::Less,
+                (Some(_), Some(_)) => {
+                    let pairs = self.labels.iter().zip(&other.labels);
+                    for (a, b) in pairs {
+                        let ord = match (a, b) {
+                            (Part::Number(a), Part::Number(b)) => a.cmp(b),
+                            (Part::Number(_), Part::Text(_)) => Ordering::Less,
+                            (Part::Text(_), Part::Number(_)) => Ordering::Greater,
+                            (Part::Text(a), Part::Text(b)) => {
+                                let (a, b) = (a.bytes(), b.bytes());
+                                a.map(|c| c.to_ascii_lowercase())
+                                    .cmp(b.map(|c| c.to_ascii_lowercase()))
+                            }
+                        };
+                        if ord != Ordering::Equal {
+                            return ord;
+                        }
+                    }
+                    self.labels.len().cmp(&other.labels.len())
+                }
+            })
+    }
+}
+
+impl<'a> PartialOrd for NuGetVersion<'a> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// Equality ignoring metadata and the case of release labels.
+impl<'a> PartialEq for NuGetVersion<'a> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl<'a> Eq for NuGetVersion<'a> {}
+
+/// Metadata isn't hashed, and release labels are hashed case-insensitively.
+impl<'a> Hash for NuGetVersion<'a> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.numbers.hash(state);
+        self.release.is_some().hash(state);
+        self.labels.len().hash(state);
+        for label in &self.labels {
+            match label {
+                Part::Number(n) => n.hash(state),
+                Part::Text(t) => t.to_ascii_lowercase().hash(state),
+            }
+        }
+    }
+}
+
+impl<'a> fmt::Display for NuGetVersion<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.version)
+    }
+}
+
+#[cfg_attr(tarpaulin, skip)]
+#[cfg(test)]
+mod tests {
+
+    use crate::test::hash_of;
+    use crate::{Cmp, Error};
+
+    use super::NuGetVersion;
+
+    /// Version comparisons, as the default `VersionComparer` reports them.
+    const COMPARE: &[(&str, &str, Cmp)] = &[
+        ("1.0", "1.0.0", Cmp::Eq),
+        ("1.0.0.0", "1", Cmp::Eq),
+        ("1.0.0.1", "1.0.0", Cmp::Gt),
+        ("1.0.0.10", "1.0.0.9", Cmp::Gt),
+        ("01.002", "1.2.0", Cmp::Eq),
+        ("1.0.0-beta", "1.0.0", Cmp::Lt),
+        ("1.0.0-BETA", "1.0.0-beta", Cmp::Eq),
+        ("1.0.0-Alpha", "1.0.0-beta", Cmp::Lt),
+        ("1.0.0-alpha.2", "1.0.0-alpha.10", Cmp::Lt),
+        ("1.0.0-alpha.1", "1.0.0-alpha.beta", Cmp::Lt),
+        ("1.0.0-alpha", "1.0.0-alpha.1", Cmp::Lt),
+        ("1.0.0-rc.1", "1.0.0.1-alpha", Cmp::Lt),
+        ("1.0.0+build.1", "1.0.0+build.2", Cmp::Eq),
+        ("1.0.0-beta+a", "1.0.0-Beta+b", Cmp::Eq),
+        ("2.0.0", "10.0.0", Cmp::Lt),
+    ];
+
+    #[test]
+    fn parse() {
+        let version = NuGetVersion::parse("1.2.3.4-rc.1+meta").unwrap();
+        assert_eq!(
+            (
+                version.major(),
+                version.minor(),
+                version.patch(),
+                version.revision()
+            ),
+            (1, 2, 3, 4),
+        );
+        assert_eq!(version.release(), Some("rc.1"));
+        assert_eq!(version.metadata(), Some("meta"));
+        assert!(version.is_prerelease());
+        assert_eq!(version.normalized(), "1.2.3.4-rc.1");
+        assert_eq!(version.as_str(), "1.2.3.4-rc.1+meta");
+
+        let version = NuGetVersion::parse("3").unwrap();
+        assert_eq!(version.normalized(), "3.0.0");
+        assert!(!version.is_prerelease());
+    }
+
+    #[test]
+    fn parse_error() {
+        let errors = [
+            ("", Error::EmptyPart { at: 0 }),
+            ("1.", Error::EmptyPart { at: 2 }),
+            ("1..0", Error::EmptyPart { at: 2 }),
+            ("v1.0", Error::UnexpectedChar { at: 0 }),
+            ("1.0a", Error::UnexpectedChar { at: 3 }),
+            ("1.2.3.4.5", Error::UnexpectedChar { at: 7 }),
+            ("2147483648.0", Error::NumberOverflow { at: 0 }),
+            ("1.0-", Error::EmptyPart { at: 4 }),
+            ("1.0-beta..1", Error::EmptyPart { at: 9 }),
+            ("1.0-beta_1", Error::UnexpectedChar { at: 8 }),
+            ("1.0+", Error::EmptyPart { at: 4 }),
+            (" 1.0", Error::UnexpectedChar { at: 0 }),
+        ];
+        for (version, err) in &errors {
+            assert_eq!(
+                NuGetVersion::parse(version).unwrap_err(),
+                *err,
+                "Parsing {:?}",
+                version,
+            );
+        }
+    }
+
+    #[test]
+    fn compare() {
+        for (a, b, cmp) in COMPARE {
+            let (a, b) = (
+                NuGetVersion::parse(a).unwrap(),
+                NuGetVersion::parse(b).unwrap(),
+            );
+            assert_eq!(Cmp::from(a.cmp(&b)), *cmp, "Comparing {} to {}", a, b);
+            assert_eq!(Cmp::from(b.cmp(&a)), cmp.flip(), "Comparing {} to {}", b, a);
+        }
+    }
+
+    #[test]
+    fn hash_eq() {
+        for (a, b, cmp) in COMPARE {
+            if *cmp == Cmp::Eq {
+                let (a, b) = (
+                    NuGetVersion::parse(a).unwrap(),
+                    NuGetVersion::parse(b).unwrap(),
+                );
+                assert_eq!(hash_of(&a), hash_of(&b), "Hashing {} and {}", a, b);
+            }
+        }
+    }
+}
@@ -0,0 +1,682 @@
+//! NuGet version range module, which provides the `NuGetRange` struct.
+//!
+//! NuGet version ranges use interval notation with inclusive `[` `]` or exclusive `(` `)`
+//! bounds, such as `[1.0,2.0)`, a bare minimum version such as `1.0`, or a floating version such
+//! as `1.*` or `1.0.0-beta*`.
+
+use std::cmp::Ordering;
+use std::fmt;
+use std::ops::Bound;
+use std::str::FromStr;
+
+use crate::{Cmp, Error, Manifest, NuGetVersion, Scheme, Version, VersionBuf};
+
+/// NuGet version range, as used in `PackageReference` and `packages.config` dependencies.
+///
+/// A range is either an interval such as `[1.0,2.0)`, where either bound may be omitted like
+/// `(,2.0]`, an exact version such as `[1.2]`, or a bare version such as `1.0` meaning a minimum
+/// inclusive version. The minimum version may float, such as `1.*`, which prefers the highest
+/// version matching the float when resolving with `find_best_match`.
+///
+/// Versions are always compared with the NuGet scheme, like `NuGetVersion`.
+///
+/// # Examples
+///
+/// ```
+/// use version_compare::{NuGetRange, Version};
+///
+/// let range = NuGetRange::parse("[1.0,2.0)").unwrap();
+///
+/// assert!(range.contains(&Version::from("1.0.0.0").unwrap()));
+/// assert!(range.contains(&Version::from("2.0.0-beta").unwrap()));
+/// assert!(!range.contains(&Version::from("2.0").unwrap()));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NuGetRange {
+    lower: Bound<VersionBuf>,
+    upper: Bound<VersionBuf>,
+    float: Option<(Float, String)>,
+}
+
+/// Floating behavior of a minimum version, like NuGet's `NuGetVersionFloatBehavior`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Float {
+    /// Pre-release labels starting with the given prefix, such as `1.0.0-beta*`.
+    Prerelease(String),
+
+    /// Highest revision, such as `1.0.0.*`.
+    Revision,
+
+    /// Highest patch, such as `1.0.*`.
+    Patch,
+
+    /// Highest minor, such as `1.*`.
+    Minor,
+
+    /// Highest major, `*`.
+    Major,
+
+    /// Highest version including pre-releases, `*-*`.
+    AbsoluteLatest,
+
+    /// Highest revision including pre-releases, such as `1.0.0.*-*`.
+    PrereleaseRevision,
+
+    /// Highest patch including pre-releases, such as `1.0.*-*`.
+    PrereleasePatch,
+
+    /// Highest minor including pre-releases, such as `1.*-*`.
+    PrereleaseMinor,
+}
+
+impl NuGetRange {
+    /// Parse a NuGet version range.
+    ///
+    /// Like NuGet, an error is returned if the range isn't closed, has no bounds, has more than
+    /// two bounds, or is an exact version in `(` `)`. Only the minimum version may float.
+    /// Reversed bounds are an error as well.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use version_compare::{Error, NuGetRange};
+    ///
+    /// assert!(NuGetRange::parse("[1.*, 2.0)").is_ok());
+    /// assert_eq!(NuGetRange::parse("[1.0,2.0").unwrap_err(), Error::UnclosedBracket { at: 0 });
+    /// assert_eq!(NuGetRange::parse("(1.0)").unwrap_err(), Error::InvalidRange { at: 0 });
+    /// ```
+    pub fn parse(range: &str) -> Result<Self, Error> {
+        let start = skip_whitespace(range, 0);
+        let end = range.trim_end().len();
+
+        // A bare version is a minimum inclusive version, which may float
+        if !range[start..].starts_with(|c| c == '[' || c == '(') {
+            let (lower, float) = parse_bound(range, start, range.len(), true)?;
+            return Ok(NuGetRange {
+                lower: Bound::Included(lower),
+                upper: Bound::Unbounded,
+                float,
+            });
+        }
+        if end == start + 1 || !range[..end].ends_with(|c| c == ']' || c == ')') {
+            return Err(Error::UnclosedBracket { at: start });
+        }
+        let lower_inclusive = range[start..].starts_with('[');
+        let upper_inclusive = range[..end].ends_with(']');
+        let end = end - 1;
+
+        // A single version is an exact version, and must be inclusive
+        let comma = match range[start + 1..end].find(',') {
+            Some(comma) => comma + start + 1,
+            None => {
+                if !lower_inclusive || !upper_inclusive {
+                    return Err(Error::InvalidRange { at: start });
+                }
+                let (version, _) = parse_bound(range, start + 1, end, false)?;
+                return Ok(NuGetRange {
+                    lower: Bound::Included(version.clone()),
+                    upper: Bound::Included(version),
+                    float: None,
+                });
+            }
+        };
+        if let Some(i) = range[comma + 1..end].find(',') {
+            return Err(Error::UnexpectedChar { at: comma + 1 + i });
+        }
+        if range[start + 1..comma].trim().is_empty() && range[comma + 1..end].trim().is_empty() {
+            return Err(Error::InvalidRange { at: start });
+        }
+
+        let (mut lower, mut upper, mut float) = (Bound::Unbounded, Bound::Unbounded, None);
+        if !range[start + 1..comma].trim().is_empty() {
+            let (version, floating) = parse_bound(range, start + 1, comma, true)?;
+            float = floating;
+            lower = if lower_inclusive {
+                Bound::Included(version)
+            } else {
+                Bound::Excluded(version)
+            };
+        }
+        if !range[comma + 1..end].trim().is_empty() {
+            let (version, _) = parse_bound(range, comma + 1, end, false)?;
+            upper = if upper_inclusive {
+                Bound::Included(version)
+            } else {
+                Bound::Excluded(version)
+            };
+        }
+
+        // Bounds may not be reversed, nor exclude their only version
+        if let (Bound::Included(l), Bound::Included(u)) = (&lower, &upper) {
+            if u.compare_to(l, Cmp::Lt) {
+                return Err(Error::InvalidRange { at: start });
+            }
+        }
+        if let (Bound::Included(l), Bound::Excluded(u))
+        | (Bound::Excluded(l), Bound::Included(u))
+        | (Bound::Excluded(l), Bound::Excluded(u)) = (&lower, &upper)
+        {
+            if u.compare_to(l, Cmp::Le) {
+                return Err(Error::InvalidRange { at: start });
+            }
+        }
+
+        Ok(NuGetRange {
+            lower,
+            upper,
+            float,
+        })
+    }
+
+    /// Get the lower bound of this range.
+    ///
+    /// For a floating range, this is the minimum version of the float, such as `1.0` for `1.*`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::ops::Bound;
+    /// use version_compare::NuGetRange;
+    ///
+    /// match NuGetRange::parse("1.*").unwrap().lower() {
+    ///     Bound::Included(version) => assert_eq!(version.as_str(), "1.0"),
+    ///     bound => panic!("Unexpected bound {:?}", bound),
+    /// }
+    /// ```
+    pub fn lower(&self) -> &Bound<VersionBuf> {
+        &self.lower
+    }
+
+    /// Get the upper bound of this range.
+    pub fn upper(&self) -> &Bound<VersionBuf> {
+        &self.upper
+    }
+
+    /// Check whether the minimum version of this range floats, such as `1.*`.
+    pub fn is_floating(&self) -> bool {
+        self.float.is_some()
+    }
+
+    /// Check whether this range contains the given `version`.
+    ///
+    /// Like NuGet's `VersionRange.Satisfies`, only the bounds are considered, so `1.*` contains
+    /// `2.0` and pre-releases above the minimum version.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use version_compare::{NuGetRange, Version};
+    ///
+    /// let range = NuGetRange::parse("(, 2.0]").unwrap();
+    ///
+    /// assert!(range.contains(&Version::from("0.1").unwrap()));
+    /// assert!(range.contains(&Version::from("2.0.0.0").unwrap()));
+    /// assert!(!range.contains(&Version::from("2.0.0.1").unwrap()));
+    /// ```
+    pub fn contains(&self, version: &Version) -> bool {
+        // Compare from the side of the bounds, so the NuGet scheme is used
+        let above = match &self.lower {
+            Bound::Included(v) => v.as_version().compare_to(version, Cmp::Le),
+            Bound::Excluded(v) => v.as_version().compare_to(version, Cmp::Lt),
+            Bound::Unbounded => true,
+        };
+        let below = match &self.upper {
+            Bound::Included(v) => v.as_version().compare_to(version, Cmp::Ge),
+            Bound::Excluded(v) => v.as_version().compare_to(version, Cmp::Gt),
+            Bound::Unbounded => true,
+        };
+        above && below
+    }
+
+    /// Find the version NuGet would pick from the given `versions`, like
+    /// `VersionRange.FindBestMatch`.
+    ///
+    /// Pre-releases are only picked if a bound is a pre-release or the float allows them.
+    /// Without float, the lowest version in range is picked. With a float, the highest version
+    /// matching the float is picked, falling back to the version nearest to the float. Versions
+    /// that aren't valid NuGet versions are ignored.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use version_compare::{NuGetRange, Version};
+    ///
+    /// let versions: Vec<_> = ["1.0.1", "1.2.0", "1.3.0-beta", "2.0.0"]
+    ///     .iter()
+    ///     .map(|v| Version::from(v).unwrap())
+    ///     .collect();
+    ///
+    /// let best = |range| NuGetRange::parse(range).unwrap().find_best_match(&versions);
+    /// assert_eq!(best("1.0").unwrap().as_str(), "1.0.1");
+    /// assert_eq!(best("1.*").unwrap().as_str(), "1.2.0");
+    /// assert_eq!(best("1.*-*").unwrap().as_str(), "1.3.0-beta");
+    /// assert!(best("[3.0,)").is_none());
+    /// ```
+    pub fn find_best_match<'a, 'b, I>(&self, versions: I) -> Option<&'a Version<'b>>
+    where
+        I: IntoIterator<Item = &'a Version<'b>>,
+    {
+        let bounds_prerelease = [&self.lower, &self.upper].iter().any(|bound| match bound {
+            Bound::Included(v) | Bound::Excluded(v) => parse(v).is_prerelease(),
+            Bound::Unbounded => false,
+        });
+        let float_prerelease = match &self.float {
+            Some((float, _)) => !matches!(
+                float,
+                Float::Revision | Float::Patch | Float::Minor | Float::Major
+            ),
+            None => false,
+        };
+
+        let mut best: Option<(NuGetVersion, &'a Version<'b>)> = None;
+        for version in versions {
+            let parsed = match NuGetVersion::parse(version.as_str()) {
+                Ok(parsed) => parsed,
+                Err(_) => continue,
+            };
+            if parsed.is_prerelease() && !bounds_prerelease && !float_prerelease {
+                continue;
+            }
+            if !self.contains(version) {
+                continue;
+            }
+            let better = match &best {
+                Some((current, _)) => self.is_better(current, &parsed),
+                None => true,
+            };
+            if better {
+                best = Some((parsed, version));
+            }
+        }
+        best.map(|(_, version)| version)
+    }
+
+    /// Check whether `considering` is a better match than `current`, both being in range.
+    fn is_better(&self, current: &NuGetVersion, considering: &NuGetVersion) -> bool {
+        let (float, min) = match (&self.float, &self.lower) {
+            (Some((float, _)), Bound::Included(min)) | (Some((float, _)), Bound::Excluded(min)) => {
+                (float, parse(min))
+            }
+            // Favor lower versions without float
+            _ => return current > considering,
+        };
+
+        // Favor the highest version in the float, otherwise the one nearest to the float
+        match (
+            floats(float, &min, current),
+            floats(float, &min, considering),
+        ) {
+            (true, false) => false,
+            (false, true) => true,
+            (true, true) => current < considering,
+            (false, false) => match (current < &min, considering < &min) {
+                (true, false) => true,
+                (false, true) => false,
+                (false, false) => current > considering,
+                (true, true) => current < considering,
+            },
+        }
+    }
+}
+
+impl FromStr for NuGetRange {
+    type Err = Error;
+
+    fn from_str(range: &str) -> Result<Self, Self::Err> {
+        NuGetRange::parse(range)
+    }
+}
+
+/// Format the range in NuGet's normalized notation, such as `[1.0.0, 2.0.0)`, that parses into
+/// an equivalent range. Floating ranges are formatted as the float, such as `1.*`.
+impl fmt::Display for NuGetRange {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if let Some((_, float)) = &self.float {
+            return write!(f, "{}", float);
+        }
+
+        match (&self.lower, &self.upper) {
+            (Bound::Included(lower), Bound::Included(upper)) if lower.compare(upper) == Cmp::Eq => {
+                write!(f, "[{}]", parse(lower).normalized())
+            }
+            (lower, upper) => {
+                match lower {
+                    Bound::Included(v) => write!(f, "[{}, ", parse(v).normalized())?,
+                    Bound::Excluded(v) => write!(f, "({}, ", parse(v).normalized())?,
+                    Bound::Unbounded => write!(f, "(, ")?,
+                }
+                match upper {
+                    Bound::Included(v) => write!(f, "{}]", parse(v).normalized()),
+                    Bound::Excluded(v) => write!(f, "{})", parse(v).normalized()),
+                    Bound::Unbounded => write!(f, ")"),
+                }
+            }
+        }
+    }
+}
+
+/// Parse a bound version that was validated with the NuGet scheme.
+fn parse(version: &VersionBuf) -> NuGetVersion<'_> {
+    NuGetVersion::parse(version.as_str()).expect("bound is a valid NuGet version")
+}
+
+/// Check whether `version` matches the `float` with minimum version `min`, like NuGet's
+/// `FloatRange.Satisfies`.
+fn floats(float: &Float, min: &NuGetVersion, version: &NuGetVersion) -> bool {
+    let stable = !version.is_prerelease();
+    let major = version.major() == min.major();
+    let minor = major && version.minor() == min.minor();
+    let patch = minor && version.patch() == min.patch();
+    match float {
+        Float::Prerelease(prefix) => {
+            let labels = version.release().unwrap_or("").to_ascii_lowercase();
+            version.cmp_numbers(min) == Ordering::Equal
+                && (stable || labels.starts_with(&prefix.to_ascii_lowercase()))
+        }
+        Float::Revision => patch && stable,
+        Float::Patch => minor && stable,
+        Float::Minor => major && stable,
+        Float::Major => stable,
+        Float::AbsoluteLatest => true,
+        Float::PrereleaseRevision => patch,
+        Float::PrereleasePatch => minor,
+        Float::PrereleaseMinor => major,
+    }
+}
+
+/// Parse the version between byte offsets `from` and `to`, surrounding whitespace is trimmed.
+///
+/// If `floating`, the version may float, and the minimum version of the float is returned along
+/// with the float.
+fn parse_bound(
+    range: &str,
+    from: usize,
+    to: usize,
+    floating: bool,
+) -> Result<(VersionBuf, Option<(Float, String)>), Error> {
+    let at = skip_whitespace(range, from);
+    let version = range[at.min(to)..to].trim_end();
+    if version.is_empty() {
+        return Err(Error::MissingVersion { at: to });
+    }
+    if let Some(i) = version.find(|c| matches!(c, '[' | ']' | '(' | ')' | ',')) {
+        return Err(Error::UnexpectedChar { at: at + i });
+    }
+
+    let (min, float) = match version.find('*') {
+        Some(star) if floating => {
+            let (float, min) =
+                parse_float(version).ok_or(Error::UnexpectedChar { at: at + star })?;
+            (min, Some((float, version.to_owned())))
+        }
+        _ => (version.to_owned(), None),
+    };
+
+    let manifest = Manifest {
+        scheme: Scheme::NuGet,
+        ..Manifest::default()
+    };
+    let min = VersionBuf::from_manifest(min, &manifest).map_err(|err| err.offset(at))?;
+    Ok((min, float))
+}
+
+/// Parse a floating version, returning the float and its minimum version.
+///
+/// The minimum version is the float without `*`, and with a `0` added where a number or label is
+/// missing, such as `1.0` for `1.*`. Returns `None` if the `*` isn't at a supported position.
+fn parse_float(version: &str) -> Option<(Float, String)> {
+    let numbers = |core: &str| core.split('.').count();
+    let (float, min) = match version {
+        "*" => (Float::Major, "0.0.0".to_owned()),
+        "*-*" => (Float::AbsoluteLatest, "0.0.0-0".to_owned()),
+        _ if version.ends_with(".*-*") => {
+            let core = &version[..version.len() - 4];
+            let float = match numbers(core) {
+                1 => Float::PrereleaseMinor,
+                2 => Float::PrereleasePatch,
+                _ => Float::PrereleaseRevision,
+            };
+            (float, format!("{}.0-0", core))
+        }
+        _ if version.ends_with('*') && version.contains('-') => {
+            let min = &version[..version.len() - 1];
+            let prefix = &min[min.find('-').unwrap_or(0) + 1..];
+            let mut min = min.to_owned();
+            if min.ends_with(|c| c == '-' || c == '.') {
+                min.push('0');
+            }
+            (Float::Prerelease(prefix.to_owned()), min)
+        }
+        _ if version.ends_with(".*") => {
+            let core = &version[..version.len() - 2];
+            let float = match numbers(core) {
+                1 => Float::Minor,
+                2 => Float::Patch,
+                _ => Float::Revision,
+            };
+            (float, format!("{}.0", core))
+        }
+        _ => return None,
+    };
+    Some((float, min))
+}
+
+/// Get the byte offset of the first non-whitespace character at or after `at`.
+fn skip_whitespace(range: &str, at: usize) -> usize {
+    range[at..]
+        .find(|c: char| !c.is_whitespace())
+        .map_or(range.len(), |i| i + at)
+}
+
+#[cfg_attr(tarpaulin, skip)]
+#[cfg(test)]
+mod tests {
+    use std::ops::Bound;
+
+    use crate::{Error, Version};
+
+    use super::NuGetRange;
+
+    /// Ranges, with versions they contain and don't contain.
+    const CONTAINS: &[(&str, &[&str], &[&str])] = &[
+        (
+            "1.0",
+            &["1.0", "1.0.0.0", "1.0.1-beta", "5.0"],
+            &["0.9", "1.0.0-rc.1"],
+        ),
+        (
+            "[1.0]",
+            &["1.0", "1.0.0.0"],
+            &["1.0.0.1", "0.9", "1.0-beta"],
+        ),
+        ("(1.0,)", &["1.0.0.1", "1.0.1-beta", "2.0"], &["1.0", "0.9"]),
+        ("[1.0,2.0]", &["1.0", "1.5", "2.0"], &["0.9", "2.0.0.1"]),
+        ("(1.0,2.0)", &["1.0.1", "2.0-beta"], &["1.0", "2.0"]),
+        ("[1.0,2.0)", &["1.0", "1.9.9.9"], &["0.9", "2.0"]),
+        ("(,1.0]", &["0.1", "1.0"], &["1.0.0.1", "1.1"]),
+        ("(,1.0)", &["0.1", "1.0-rc"], &["1.0"]),
+        (
+            "[1.0-BETA,1.0]",
+            &["1.0.0-beta", "1.0.0-beta.1"],
+            &["1.0.0-alpha"],
+        ),
+        (
+            "1.*",
+            &["1.0", "1.5", "2.0", "2.0-beta"],
+            &["0.9", "1.0-beta"],
+        ),
+        ("*", &["0.0.0", "1.0", "0.1-beta"], &[]),
+        (
+            "1.0.0-beta*",
+            &["1.0.0-beta", "1.0.0-beta2", "1.0.0"],
+            &["1.0.0-alpha"],
+        ),
+        ("[1.*, 2.0)", &["1.0", "1.9"], &["0.9", "2.0"]),
+    ];
+
+    fn versions(versions: &[&'static str]) -> Vec<Version<'static>> {
+        versions.iter().map(|v| Version::from(v).unwrap()).collect()
+    }
+
+    #[test]
+    fn parse() {
+        let range = NuGetRange::parse("[1.0,2.0)").unwrap();
+        assert!(!range.is_floating());
+        match (range.lower(), range.upper()) {
+            (Bound::Included(lower), Bound::Excluded(upper)) => {
+                assert_eq!(lower.as_str(), "1.0");
+                assert_eq!(upper.as_str(), "2.0");
+            }
+            bounds => panic!("Unexpected bounds {:?}", bounds),
+        }
+
+        // Spacing must not matter
+        for other in &[" [ 1.0 , 2.0 ) ", "[1.0,2.0)\t"] {
+            assert_eq!(
+                NuGetRange::parse(other).unwrap(),
+                range,
+                "Parsing {}",
+                other
+            );
+        }
+
+        // Floats parse into their minimum version
+        let floats = [
+            ("*", "0.0.0"),
+            ("*-*", "0.0.0-0"),
+            ("1.*", "1.0"),
+            ("1.2.*", "1.2.0"),
+            ("1.2.3.*", "1.2.3.0"),
+            ("1.*-*", "1.0-0"),
+            ("1.2.*-*", "1.2.0-0"),
+            ("1.0.0-*", "1.0.0-0"),
+            ("1.0.0-beta*", "1.0.0-beta"),
+            ("1.0.0-beta.*", "1.0.0-beta.0"),
+            ("[1.*, )", "1.0"),
+        ];
+        for (range, min) in &floats {
+            let parsed = NuGetRange::parse(range).unwrap();
+            assert!(parsed.is_floating(), "Parsing {}", range);
+            match parsed.lower() {
+                Bound::Included(lower) => assert_eq!(lower.as_str(), *min, "Parsing {}", range),
+                bound => panic!("Unexpected bound {:?}", bound),
+            }
+        }
+    }
+
+    #[test]
+    fn parse_error() {
+        let errors = [
+            ("", Error::MissingVersion { at: 0 }),
+            ("[1.0,2.0", Error::UnclosedBracket { at: 0 }),
+            ("[", Error::UnclosedBracket { at: 0 }),
+            ("(1.0)", Error::InvalidRange { at: 0 }),
+            ("[1.0)", Error::InvalidRange { at: 0 }),
+            ("(,)", Error::InvalidRange { at: 0 }),
+            ("[2.0,1.0]", Error::InvalidRange { at: 0 }),
+            ("(1.0,1.0]", Error::InvalidRange { at: 0 }),
+            ("[1.0,2.0,3.0]", Error::UnexpectedChar { at: 8 }),
+            ("[]", Error::MissingVersion { at: 1 }),
+            ("[1.0,2.*)", Error::UnexpectedChar { at: 7 }),
+            ("[1.*]", Error::UnexpectedChar { at: 3 }),
+            ("1.*.3", Error::UnexpectedChar { at: 2 }),
+            ("1.0]", Error::UnexpectedChar { at: 3 }),
+            ("[1.0, abc)", Error::UnexpectedChar { at: 6 }),
+        ];
+        for (range, err) in &errors {
+            assert_eq!(
+                NuGetRange::parse(range).unwrap_err(),
+                *err,
+                "Parsing {:?}",
+                range
+            );
+        }
+    }
+
+    #[test]
+    fn contains() {
+        for (range, included, excluded) in CONTAINS {
+            let parsed = NuGetRange::parse(range).unwrap();
+            for version in *included {
+                let version = Version::from(version).unwrap();
+                assert!(
+                    parsed.contains(&version),
+                    "{} should contain {}",
+                    range,
+                    version
+                );
+            }
+            for version in *excluded {
+                let version = Version::from(version).unwrap();
+                assert!(
+                    !parsed.contains(&version),
+                    "{} should not contain {}",
+                    range,
+                    version
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn find_best_match() {
+        let available = versions(&[
+            "0.9.0",
+            "1.0.0",
+            "1.0.1",
+            "1.1.0-beta",
+            "1.1.0",
+            "1.2.0-beta.1",
+            "1.2.0-beta.2",
+            "2.0.0-rc",
+            "2.0.0",
+            "1.5.0.0.0",
+        ]);
+        let cases = [
+            ("1.0", Some("1.0.0")),
+            ("(1.0,)", Some("1.0.1")),
+            ("[1.1.0-alpha, )", Some("1.1.0-beta")),
+            ("1.*", Some("1.1.0")),
+            ("1.0.*", Some("1.0.1")),
+            ("1.*-*", Some("1.2.0-beta.2")),
+            ("1.2.0-beta.*", Some("1.2.0-beta.2")),
+            ("1.2.0-BETA*", Some("1.2.0-beta.2")),
+            ("*", Some("2.0.0")),
+            ("*-*", Some("2.0.0")),
+            ("[1.*, 1.1)", Some("1.0.1")),
+            ("1.5.*", Some("2.0.0")),
+            ("0.5.*", Some("0.9.0")),
+            ("[3.0,)", None),
+            ("[1.2.0-beta.1]", Some("1.2.0-beta.1")),
+        ];
+        for (range, best) in &cases {
+            let found = NuGetRange::parse(range)
+                .unwrap()
+                .find_best_match(&available)
+                .map(|v| v.as_str());
+            assert_eq!(found, *best, "Best match for {}", range);
+        }
+    }
+
+    #[test]
+    fn display() {
+        let ranges = [
+            ("[1.0,2.0)", "[1.0.0, 2.0.0)"),
+            ("1.0", "[1.0.0, )"),
+            ("(,2.0.0.1]", "(, 2.0.0.1]"),
+            ("[ 01.0 ]", "[1.0.0]"),
+            ("1.0.0-Beta+meta", "[1.0.0-Beta, )"),
+            (" 1.* ", "1.*"),
+        ];
+        for (range, display) in &ranges {
+            let parsed = NuGetRange::parse(range).unwrap();
+            assert_eq!(parsed.to_string(), *display);
+            assert_eq!(
+                NuGetRange::parse(display).unwrap(),
+                parsed,
+                "Parsing {}",
+                display
+            );
+        }
+    }
+}
@@ -0,0 +1,634 @@
+//! PEP 440 version scheme, which provides the `Pep440Version` struct.
+//!
+//! Python package versions are specified by PEP 440. A version has an optional epoch, a release
+//! segment, and optional pre-release, post-release, development release and local version
+//! segments, such as `1!2.0rc1.post2.dev3+ubuntu.1`.
+
+use std::cmp::Ordering;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+
+use crate::{Error, Number, Part};
+
+/// Python package version, as specified by PEP 440.
+///
+/// Versions are normalized when parsing, so alternative spellings such as `1.0alpha1`, `1.0-a.1`
+/// and `v1.0a1` are equal, and display as the canonical `1.0a1`.
+///
+/// Versions are ordered by epoch, release, pre-release, post-release, development release and
+/// local version. Development releases come before pre-releases, which come before the final
+/// release, which comes before post-releases.
+///
+/// # Examples
+///
+/// ```
+/// use version_compare::Pep440Version;
+///
+/// let versions = ["1.0.dev1", "1.0a1", "1.0", "1.0.post1"];
+/// let parsed: Vec<_> = versions.iter().map(|v| Pep440Version::parse(v).unwrap()).collect();
+/// assert!(parsed.windows(2).all(|w| w[0] < w[1]));
+///
+/// let version = Pep440Version::parse("1!2.0-ALPHA.1+Ubuntu-1").unwrap();
+/// assert_eq!(version.to_string(), "1!2.0a1+ubuntu.1");
+/// ```
+#[derive(Debug, Clone)]
+pub struct Pep440Version<'a> {
+    version: &'a str,
+    epoch: Number<'a>,
+    release: Vec<Number<'a>>,
+    pre: Option<(PreLabel, Number<'a>)>,
+    post: Option<Number<'a>>,
+    dev: Option<Number<'a>>,
+    local: Vec<Part<'a>>,
+}
+
+/// Pre-release label, in order.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+enum PreLabel {
+    Alpha,
+    Beta,
+    Rc,
+}
+
+impl PreLabel {
+    /// Get the normalized label.
+    fn as_str(self) -> &'static str {
+        match self {
+            PreLabel::Alpha => "a",
+            PreLabel::Beta => "b",
+            PreLabel::Rc => "rc",
+        }
+    }
+}
+
+/// Sort key for the pre-release segment.
+///
+/// A development release without pre-release or post-release sorts before all pre-releases.
+#[derive(PartialEq, Eq, PartialOrd, Ord, Hash)]
+enum PreKey<'a> {
+    DevOnly,
+    Pre(PreLabel, Number<'a>),
+    Final,
+}
+
+impl<'a> Pep440Version<'a> {
+    /// Parse a PEP 440 version string.
+    ///
+    /// Parsing is case-insensitive, surrounding whitespace and a leading `v` are ignored, and all
+    /// alternative spellings allowed by PEP 440 are accepted. An error is returned for strings
+    /// that aren't a valid PEP 440 version.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use version_compare::{Error, Pep440Version};
+    ///
+    /// assert!(Pep440Version::parse("1.0.preview2").is_ok());
+    /// assert_eq!(
+    ///     Pep440Version::parse("1.0-beta.2.dev").unwrap().to_string(),
+    ///     "1.0b2.dev0",
+    /// );
+    /// assert_eq!(
+    ///     Pep440Version::parse("1.0.dev1.post1").unwrap_err(),
+    ///     Error::UnexpectedChar { at: 8 },
+    /// );
+    /// ```
+    pub fn parse(version: &'a str) -> Result<Self, Error> {
+        let mut parser = Parser {
+            input: version,
+            pos: version.len() - version.trim_start().len(),
+        };
+        let end = version.trim_end().len();
+
+        if parser.eat(&["v"]).is_some() && parser.peek_digit().is_none() {
+            return Err(Error::UnexpectedChar { at: parser.pos });
+        }
+
+        // Epoch, which must be followed by an exclamation mark
+        let start = parser.pos;
+        let epoch = match parser.digits() {
+            Some(epoch) if parser.eat(&["!"]).is_some() => epoch,
+            _ => {
+                parser.pos = start;
+                0.into()
+            }
+        };
+
+        // Release segment
+        let mut release = vec![parser
+            .digits()
+            .ok_or(Error::UnexpectedChar { at: parser.pos })?];
+        loop {
+            let start = parser.pos;
+            match parser.eat(&["."]).and_then(|_| parser.digits()) {
+                Some(number) => release.push(number),
+                None => {
+                    parser.pos = start;
+                    break;
+                }
+            }
+        }
+
+        // Pre-release segment
+        let pre = parser
+            .labeled(&["alpha", "a", "beta", "b", "preview", "pre", "c", "rc"])
+            .map(|(label, number)| {
+                let label = match label {
+                    0 | 1 => PreLabel::Alpha,
+                    2 | 3 => PreLabel::Beta,
+                    _ => PreLabel::Rc,
+                };
+                (label, number)
+            });
+
+        // Post-release segment, either as implicit `-1` or labeled
+        let start = parser.pos;
+        let post = match parser.eat(&["-"]).and_then(|_| parser.digits()) {
+            Some(number) => Some(number),
+            None => {
+                parser.pos = start;
+                parser
+                    .labeled(&["post", "rev", "r"])
+                    .map(|(_, number)| number)
+            }
+        };
+
+        // Development release segment
+        let dev = parser.labeled(&["dev"]).map(|(_, number)| number);
+
+        // Local version segments
+        let mut local = Vec::new();
+        if parser.eat(&["+"]).is_some() {
+            loop {
+                let start = parser.pos;
+                let len = version[start..end]
+                    .bytes()
+                    .take_while(u8::is_ascii_alphanumeric)
+                    .count();
+                if len == 0 {
+                    return Err(Error::UnexpectedChar { at: start });
+                }
+                parser.pos += len;
+
+                let segment = &version[start..parser.pos];
+                local.push(match Number::parse(segment) {
+                    Some(number) => Part::Number(number),
+                    None => Part::Text(segment),
+                });
+
+                if parser.eat(&["-", "_", "."]).is_none() {
+                    break;
+                }
+            }
+        }
+
+        if parser.pos != end {
+            return Err(Error::UnexpectedChar { at: parser.pos });
+        }
+
+        Ok(Pep440Version {
+            version: version.trim(),
+            epoch,
+            release,
+            pre,
+            post,
+            dev,
+            local,
+        })
+    }
+
+    /// Get the epoch, `0` if the version has none.
+    pub fn epoch(&self) -> Number<'a> {
+        self.epoch
+    }
+
+    /// Get the numbers of the release segment, such as `[1, 2, 0]` for `1.2.0`.
+    pub fn release(&self) -> &[Number<'a>] {
+        &self.release
+    }
+
+    /// Get the pre-release segment, as normalized label `a`, `b` or `rc` and number.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use version_compare::Pep440Version;
+    ///
+    /// let version = Pep440Version::parse("1.0.preview").unwrap();
+    ///
+    /// assert_eq!(version.pre(), Some(("rc", 0.into())));
+    /// ```
+    pub fn pre(&self) -> Option<(&'static str, Number<'a>)> {
+        self.pre.map(|(label, number)| (label.as_str(), number))
+    }
+
+    /// Get the post-release number, if any.
+    pub fn post(&self) -> Option<Number<'a>> {
+        self.post
+    }
+
+    /// Get the development release number, if any.
+    pub fn dev(&self) -> Option<Number<'a>> {
+        self.dev
+    }
+
+    /// Get the local version segments, empty if the version has no local version.
+    pub fn local(&self) -> &[Part<'a>] {
+        &self.local
+    }
+
+    /// Check whether this is a pre-release, including development releases.
+    pub fn is_prerelease(&self) -> bool {
+        self.pre.is_some() || self.dev.is_some()
+    }
+
+    /// Check whether this is a post-release.
+    pub fn is_postrelease(&self) -> bool {
+        self.post.is_some()
+    }
+
+    /// Get the version string, without surrounding whitespace.
+    pub fn as_str(&self) -> &'a str {
+        self.version
+    }
+
+    /// Compare the public part of this version to the `other` version, ignoring local versions.
+    pub(crate) fn cmp_public(&self, other: &Self) -> Ordering {
+        self.cmp_base(other)
+            .then_with(|| self.pre_key().cmp(&other.pre_key()))
+            .then_with(|| self.post.cmp(&other.post))
+            .then_with(|| match (self.dev, other.dev) {
+                (None, None) => Ordering::Equal,
+                (None, Some(_)) => Ordering::Greater,
+                (Some(_), None) => Ordering::Less,
+                (Some(a), Some(b)) => a.cmp(&b),
+            })
+    }
+
+    /// Compare the base of this version to the `other` version, being the epoch and release.
+    pub(crate) fn cmp_base(&self, other: &Self) -> Ordering {
+        self.epoch.cmp(&other.epoch).then_with(|| {
+            let len = self.release.len().max(other.release.len());
+            (0..len)
+                .map(|i| {
+                    let a = self.release.get(i).copied().unwrap_or_else(|| 0.into());
+                    let b = other.release.get(i).copied().unwrap_or_else(|| 0.into());
+                    a.cmp(&b)
+                })
+                .find(|ord| *ord != Ordering::Equal)
+                .unwrap_or(Ordering::Equal)
+        })
+    }
+
+    /// Get the sort key of the pre-release segment.
+    fn pre_key(&self) -> PreKey<'a> {
+        match (self.pre, self.post, self.dev) {
+            (None, None, Some(_)) => PreKey::DevOnly,
+            (Some((label, number)), _, _) => PreKey::Pre(label, number),
+            _ => PreKey::Final,
+        }
+    }
+
+    /// Get the release numbers without trailing zeros.
+    fn trimmed_release(&self) -> &[Number<'a>] {
+        let len = self.release.len()
+            - self
+                .release
+                .iter()
+                .rev()
+                .take_while(|n| n.is_zero())
+                .count();
+        &self.release[..len]
+    }
+}
+
+impl<'a> Ord for Pep440Version<'a> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.cmp_public(other).then_with(|| {
+            // Local segments compare numbers above text, and text case-insensitively
+            for (a, b) in self.local.iter().zip(other.local.iter()) {
+                let ord = match (a, b) {
+                    (Part::Number(a), Part::Number(b)) => a.cmp(b),
+                    (Part::Number(_), Part::Text(_)) => Ordering::Greater,
+                    (Part::Text(_), Part::Number(_)) => Ordering::Less,
+                    (Part::Text(a), Part::Text(b)) => lowercase(a).cmp(lowercase(b)),
+                };
+                if ord != Ordering::Equal {
+                    return ord;
+                }
+            }
+            self.local.len().cmp(&other.local.len())
+        })
+    }
+}
+
+impl<'a> PartialOrd for Pep440Version<'a> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<'a> PartialEq for Pep440Version<'a> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl<'a> Eq for Pep440Version<'a> {}
+
+/// The release is hashed without trailing zeros, so `1.0` and `1.0.0` hash equally.
+impl<'a> Hash for Pep440Version<'a> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.epoch.hash(state);
+        self.trimmed_release().hash(state);
+        self.pre_key().hash(state);
+        self.post.hash(state);
+        self.dev.hash(state);
+        self.local.len().hash(state);
+        for part in &self.local {
+            match part {
+                Part::Number(n) => n.hash(state),
+                Part::Text(t) => lowercase(t).for_each(|c| c.hash(state)),
+            }
+        }
+    }
+}
+
+/// Format the version in its normalized form.
+impl<'a> fmt::Display for Pep440Version<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if !self.epoch.is_zero() {
+            write!(f, "{}!", self.epoch)?;
+        }
+        for (i, number) in self.release.iter().enumerate() {
+            if i > 0 {
+                write!(f, ".")?;
+            }
+            write!(f, "{}", number)?;
+        }
+        if let Some((label, number)) = self.pre {
+            write!(f, "{}{}", label.as_str(), number)?;
+        }
+        if let Some(number) = self.post {
+            write!(f, ".post{}", number)?;
+        }
+        if let Some(number) = self.dev {
+            write!(f, ".dev{}", number)?;
+        }
+        for (i, part) in self.local.iter().enumerate() {
+            write!(f, "{}", if i == 0 { "+" } else { "." })?;
+            match part {
+                Part::Number(n) => write!(f, "{}", n)?,
+                Part::Text(t) => write!(f, "{}", t.to_ascii_lowercase())?,
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Lowercase an ASCII string for comparison.
+fn lowercase(s: &str) -> impl Iterator<Item = u8> + '_ {
+    s.bytes().map(|b| b.to_ascii_lowercase())
+}
+
+/// Simple case-insensitive parser over a version string.
+struct Parser<'a> {
+    input: &'a str,
+    pos: usize,
+}
+
+impl<'a> Parser<'a> {
+    /// Get the digit at the current position, if any.
+    fn peek_digit(&self) -> Option<u8> {
+        self.input
+            .as_bytes()
+            .get(self.pos)
+            .copied()
+            .filter(u8::is_ascii_digit)
+    }
+
+    /// Consume a number at the current position.
+    fn digits(&mut self) -> Option<Number<'a>> {
+        let len = self.input[self.pos..]
+            .bytes()
+            .take_while(u8::is_ascii_digit)
+            .count();
+        let number = Number::parse(&self.input[self.pos..self.pos + len])?;
+        self.pos += len;
+        Some(number)
+    }
+
+    /// Consume the first of the given `words` at the current position, case-insensitively.
+    ///
+    /// Returns the index of the consumed word.
+    fn eat(&mut self, words: &[&str]) -> Option<usize> {
+        let rest = &self.input.as_bytes()[self.pos..];
+        let index = words.iter().position(|word| {
+            rest.len() >= word.len() && rest[..word.len()].eq_ignore_ascii_case(word.as_bytes())
+        })?;
+        self.pos += words[index].len();
+        Some(index)
+    }
+
+    /// Consume a labeled segment, such as `.post1`, `-rc.2` or `dev`.
+    ///
+    /// The label may be preceded and followed by a separator, the number is `0` if omitted.
+    fn labeled(&mut self, labels: &[&str]) -> Option<(usize, Number<'a>)> {
+        let start = self.pos;
+        self.eat(&["-", "_", "."]);
+        let label = match self.eat(labels) {
+            Some(label) => label,
+            None => {
+                self.pos = start;
+                return None;
+            }
+        };
+
+        self.eat(&["-", "_", "."]);
+        let number = self.digits().unwrap_or_else(|| 0.into());
+
+        Some((label, number))
+    }
+}
+
+#[cfg_attr(tarpaulin, skip)]
+#[cfg(test)]
+mod tests {
+
+    use crate::test::hash_of;
+    use crate::{Error, Part};
+
+    use super::Pep440Version;
+
+    /// Versions in ascending order, from the test suite of the `packaging` project.
+    const ORDERED: &[&str] = &[
+        "1.0.dev456",
+        "1.0a1",
+        "1.0a2.dev456",
+        "1.0a12.dev456",
+        "1.0a12",
+        "1.0b1.dev456",
+        "1.0b2",
+        "1.0b2.post345.dev456",
+        "1.0b2.post345",
+        "1.0b2-346",
+        "1.0c1.dev456",
+        "1.0c1",
+        "1.0rc2",
+        "1.0c3",
+        "1.0",
+        "1.0.post456.dev34",
+        "1.0.post456",
+        "1.1.dev1",
+        "1.2+123abc",
+        "1.2+123abc456",
+        "1.2+abc",
+        "1.2+abc123",
+        "1.2+abc123def",
+        "1.2+1234.abc",
+        "1.2+123456",
+        "1.2.r32+123456",
+        "1.2.rev33+123456",
+        "1!1.0.dev456",
+        "1!1.0a1",
+        "1!1.0a2.dev456",
+        "1!1.0a12.dev456",
+        "1!1.0a12",
+        "1!1.0b1.dev456",
+        "1!1.0b2",
+        "1!1.0b2.post345.dev456",
+        "1!1.0b2.post345",
+        "1!1.0b2-346",
+        "1!1.0c1.dev456",
+        "1!1.0c1",
+        "1!1.0rc2",
+        "1!1.0c3",
+        "1!1.0",
+        "1!1.0.post456.dev34",
+        "1!1.0.post456",
+        "1!1.1.dev1",
+        "1!1.2+123abc",
+        "1!1.2+123abc456",
+        "1!1.2+abc",
+        "1!1.2+abc123",
+        "1!1.2+abc123def",
+        "1!1.2+1234.abc",
+        "1!1.2+123456",
+        "1!1.2.r32+123456",
+        "1!1.2.rev33+123456",
+    ];
+
+    /// Version strings and their normalized form.
+    const NORMALIZED: &[(&str, &str)] = &[
+        ("1.0alpha1", "1.0a1"),
+        ("1.0-alpha.1", "1.0a1"),
+        ("1.0A1", "1.0a1"),
+        ("1.0beta2", "1.0b2"),
+        ("1.0c1", "1.0rc1"),
+        ("1.0pre1", "1.0rc1"),
+        ("1.0.preview2", "1.0rc2"),
+        ("1.0rc", "1.0rc0"),
+        ("1.0a.", "1.0a0"),
+        ("1.0-1", "1.0.post1"),
+        ("1.0.rev1", "1.0.post1"),
+        ("1.0r", "1.0.post0"),
+        ("1.0_post_2", "1.0.post2"),
+        ("1.0PoSt1", "1.0.post1"),
+        ("1.0-dev", "1.0.dev0"),
+        ("1.0dev2", "1.0.dev2"),
+        ("v1.0", "1.0"),
+        (" 1.0\n", "1.0"),
+        ("0!1.0", "1.0"),
+        ("1!01.02", "1!1.2"),
+        ("1.0+Ubuntu-1", "1.0+ubuntu.1"),
+        ("1.0+ubuntu_01", "1.0+ubuntu.1"),
+        ("1.01.0a01.post01.dev01+001", "1.1.0a1.post1.dev1+1"),
+    ];
+
+    #[test]
+    fn parse() {
+        let version = Pep440Version::parse("2!1.2.3rc4.post5.dev6+local.7").unwrap();
+        assert_eq!(version.epoch(), 2.into());
+        assert_eq!(version.release(), [1.into(), 2.into(), 3.into()]);
+        assert_eq!(version.pre(), Some(("rc", 4.into())));
+        assert_eq!(version.post(), Some(5.into()));
+        assert_eq!(version.dev(), Some(6.into()));
+        assert_eq!(
+            version.local(),
+            [Part::Text("local"), Part::Number(7.into())]
+        );
+        assert!(version.is_prerelease());
+        assert!(version.is_postrelease());
+
+        let version = Pep440Version::parse("1.0").unwrap();
+        assert_eq!(version.epoch(), 0.into());
+        assert_eq!(version.pre(), None);
+        assert!(version.local().is_empty());
+        assert!(!version.is_prerelease());
+        assert!(Pep440Version::parse("1.0.dev0").unwrap().is_prerelease());
+    }
+
+    #[test]
+    fn parse_error() {
+        let errors = [
+            ("", Error::UnexpectedChar { at: 0 }),
+            ("french toast", Error::UnexpectedChar { at: 0 }),
+            ("v", Error::UnexpectedChar { at: 1 }),
+            ("1.0.*", Error::UnexpectedChar { at: 3 }),
+            ("1..0", Error::UnexpectedChar { at: 1 }),
+            ("1.0+", Error::UnexpectedChar { at: 4 }),
+            ("1.0+_foo", Error::UnexpectedChar { at: 4 }),
+            ("1.0+a..b", Error::UnexpectedChar { at: 6 }),
+            ("1.0.dev1.post1", Error::UnexpectedChar { at: 8 }),
+            ("1.0 a1", Error::UnexpectedChar { at: 3 }),
+            ("1!", Error::UnexpectedChar { at: 2 }),
+        ];
+        for (version, err) in &errors {
+            assert_eq!(
+                Pep440Version::parse(version)
+                    .as_ref()
+                    .map(|v| v.to_string()),
+                Err(err),
+                "Parsing {:?}",
+                version,
+            );
+        }
+    }
+
+    #[test]
+    fn normalize() {
+        for (version, normalized) in NORMALIZED {
+            let parsed = Pep440Version::parse(version).unwrap();
+            assert_eq!(parsed.to_string(), *normalized, "Normalizing {:?}", version);
+
+            // The normalized form must be equal
+            let reparsed = Pep440Version::parse(normalized).unwrap();
+            assert_eq!(parsed, reparsed);
+            assert_eq!(hash_of(&parsed), hash_of(&reparsed));
+        }
+    }
+
+    #[test]
+    fn ord() {
+        let versions: Vec<_> = ORDERED
+            .iter()
+            .map(|v| Pep440Version::parse(v).unwrap())
+            .collect();
+        for (i, a) in versions.iter().enumerate() {
+            for (j, b) in versions.iter().enumerate() {
+                assert_eq!(a.cmp(b), i.cmp(&j), "Comparing {} to {}", a, b);
+            }
+        }
+
+        // Trailing zeros and case are insignificant
+        let equal = [("1.0", "1.0.0"), ("1.0+ABC", "1.0+abc"), ("1!0", "1!0.0.0")];
+        for (a, b) in &equal {
+            let (a, b) = (
+                Pep440Version::parse(a).unwrap(),
+                Pep440Version::parse(b).unwrap(),
+            );
+            assert_eq!(a, b);
+            assert_eq!(hash_of(&a), hash_of(&b));
+        }
+    }
+}
@@ -0,0 +1,561 @@
+//! PEP 440 version specifier module, which provides the `Pep440Specifier` struct.
+//!
+//! A version specifier is a list of comma separated clauses, such as `~=1.4.2, !=1.4.5`. A
+//! version matches the specifier if it matches all clauses.
+
+use std::cmp::Ordering;
+use std::fmt;
+use std::str::FromStr;
+
+use crate::{Cmp, Error, Number, Pep440Version, Version};
+
+/// Python version specifier, as specified by PEP 440.
+///
+/// Supports all PEP 440 operators: `~=`, `==`, `!=`, `<=`, `>=`, `<`, `>` and `===`, and prefix
+/// matching with `==` and `!=`, such as `!=1.2.*`.
+///
+/// Pre-releases only match if any `==`, `===`, `<=`, `>=` or `~=` clause mentions a pre-release,
+/// unless this is changed with `set_prereleases`.
+///
+/// Versions are always compared with the PEP 440 scheme, like `Pep440Version`.
+///
+/// # Examples
+///
+/// ```
+/// use version_compare::{Pep440Specifier, Version};
+///
+/// let spec = Pep440Specifier::parse("~=1.4.2, !=1.4.5").unwrap();
+///
+/// assert!(spec.matches(&Version::from("1.4.7").unwrap()));
+/// assert!(!spec.matches(&Version::from("1.4.5").unwrap()));
+/// assert!(!spec.matches(&Version::from("1.5").unwrap()));
+/// assert!(!spec.matches(&Version::from("1.4.8rc1").unwrap()));
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct Pep440Specifier {
+    clauses: Vec<Clause>,
+    prereleases: Option<bool>,
+}
+
+/// A single specifier clause.
+#[derive(Debug, Clone)]
+struct Clause {
+    operator: Operator,
+    version: String,
+
+    /// Whether the version is a valid pre-release.
+    prerelease: bool,
+}
+
+/// Specifier clause operator.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Operator {
+    /// Plain comparison operator.
+    Cmp(Cmp),
+
+    /// Prefix matching with `==` or `!=`, such as `==1.2.*`.
+    Prefix(Cmp),
+
+    /// Compatible release operator `~=`.
+    Compatible,
+
+    /// Arbitrary equality operator `===`.
+    Arbitrary,
+}
+
+/// Operator signs, longest first.
+const OPERATORS: &[&str] = &["===", "~=", "==", "!=", "<=", ">=", "<", ">"];
+
+impl Pep440Specifier {
+    /// Parse a version specifier, made of comma separated clauses.
+    ///
+    /// An empty specifier has no clauses, and matches any final release.
+    ///
+    /// An error is returned for malformed clauses, such as an unknown or missing operator, an
+    /// operator without version, an invalid version, or a version that isn't allowed with its
+    /// operator, such as a local version with `>=`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use version_compare::{Error, Pep440Specifier};
+    ///
+    /// assert!(Pep440Specifier::parse(">=1.0, <2.0, !=1.5.*").is_ok());
+    /// assert!(Pep440Specifier::parse("===foobar").is_ok());
+    /// assert_eq!(
+    ///     Pep440Specifier::parse(">=1.0, ~=2").unwrap_err(),
+    ///     Error::EmptyPart { at: 10 },
+    /// );
+    /// ```
+    pub fn parse(spec: &str) -> Result<Self, Error> {
+        let mut clauses = Vec::new();
+        if spec.trim().is_empty() {
+            return Ok(Pep440Specifier::default());
+        }
+
+        let mut at = 0;
+        for clause in spec.split(',') {
+            // Point to the comma ending an empty clause, or the one before a trailing one
+            if clause.trim().is_empty() {
+                let end = at + clause.len();
+                let at = if end < spec.len() { end } else { at - 1 };
+                return Err(Error::EmptyClause { at });
+            }
+
+            clauses.push(Clause::parse(clause, at)?);
+            at += clause.len() + 1;
+        }
+
+        Ok(Pep440Specifier {
+            clauses,
+            prereleases: None,
+        })
+    }
+
+    /// Set whether pre-releases match.
+    ///
+    /// If `None`, which is the default, pre-releases only match if any `==`, `===`, `<=`, `>=` or
+    /// `~=` clause mentions a pre-release, like the `packaging` project does.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use version_compare::{Pep440Specifier, Version};
+    ///
+    /// let mut spec = Pep440Specifier::parse(">=1.0").unwrap();
+    /// let version = Version::from("2.0b1").unwrap();
+    /// assert!(!spec.matches(&version));
+    ///
+    /// spec.set_prereleases(Some(true));
+    /// assert!(spec.matches(&version));
+    /// ```
+    pub fn set_prereleases(&mut self, prereleases: Option<bool>) {
+        self.prereleases = prereleases;
+    }
+
+    /// Check whether the given `version` matches all clauses of this specifier.
+    ///
+    /// A version that isn't a valid PEP 440 version never matches.
+    pub fn matches(&self, version: &Version) -> bool {
+        let version = match Pep440Version::parse(version.as_str()) {
+            Ok(version) => version,
+            Err(_) => return false,
+        };
+        let prereleases = self.prereleases.unwrap_or_else(|| {
+            self.clauses.iter().any(|clause| {
+                let inclusive = match clause.operator {
+                    Operator::Cmp(cmp) => matches!(cmp, Cmp::Eq | Cmp::Le | Cmp::Ge),
+                    Operator::Prefix(cmp) => cmp == Cmp::Eq,
+                    Operator::Compatible | Operator::Arbitrary => true,
+                };
+                inclusive && clause.prerelease
+            })
+        });
+        if !prereleases && version.is_prerelease() {
+            return false;
+        }
+
+        self.clauses.iter().all(|clause| clause.matches(&version))
+    }
+}
+
+impl FromStr for Pep440Specifier {
+    type Err = Error;
+
+    fn from_str(spec: &str) -> Result<Self, Self::Err> {
+        Pep440Specifier::parse(spec)
+    }
+}
+
+impl fmt::Display for Pep440Specifier {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (i, clause) in self.clauses.iter().enumerate() {
+            if i > 0 {
+                write!(f, ", ")?;
+            }
+            match clause.operator {
+                Operator::Cmp(cmp) => write!(f, "{}{}", cmp.sign(), clause.version)?,
+                Operator::Prefix(cmp) => write!(f, "{}{}.*", cmp.sign(), clause.version)?,
+                Operator::Compatible => write!(f, "~={}", clause.version)?,
+                Operator::Arbitrary => write!(f, "==={}", clause.version)?,
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Clause {
+    /// Parse a single clause, located at byte offset `at` in the specifier.
+    fn parse(clause: &str, at: usize) -> Result<Self, Error> {
+        let start = clause.len() - clause.trim_start().len();
+        let trimmed = clause.trim();
+
+        let sign = OPERATORS
+            .iter()
+            .find(|sign| trimmed.starts_with(*sign))
+            .ok_or(Error::UnknownOperatorSign { at: at + start })?;
+        let rest = &trimmed[sign.len()..];
+        let version = rest.trim_start();
+        let version_at = at + start + sign.len() + rest.len() - version.len();
+        if version.is_empty() {
+            return Err(Error::MissingVersion { at: version_at });
+        }
+
+        // Arbitrary equality accepts any version string
+        if *sign == "===" {
+            return Ok(Clause {
+                operator: Operator::Arbitrary,
+                version: version.to_string(),
+                prerelease: Pep440Version::parse(version).map_or(false, |v| v.is_prerelease()),
+            });
+        }
+
+        let wildcard = (*sign == "==" || *sign == "!=") && version.ends_with(".*");
+        let (operator, version) = match *sign {
+            "~=" => (Operator::Compatible, version),
+            _ if wildcard => (
+                Operator::Prefix(Cmp::from_sign(sign)?),
+                &version[..version.len() - 2],
+            ),
+            _ => (Operator::Cmp(Cmp::from_sign(sign)?), version),
+        };
+        let parsed = Pep440Version::parse(version).map_err(|err| err.offset(version_at))?;
+
+        // Only plain equality allows local versions, and prefix matching allows nothing but a
+        // release
+        match operator {
+            Operator::Cmp(Cmp::Eq) | Operator::Cmp(Cmp::Ne) => {}
+            Operator::Prefix(_) => {
+                if parsed.pre().is_some()
+                    || parsed.is_postrelease()
+                    || parsed.dev().is_some()
+                    || !parsed.local().is_empty()
+                {
+                    let release = version.trim_start_matches(|c: char| {
+                        c.is_ascii_digit() || matches!(c, '.' | '!' | 'v' | 'V')
+                    });
+                    return Err(Error::UnexpectedChar {
+                        at: version_at + version.len() - release.len(),
+                    });
+                }
+            }
+            _ => {
+                if let Some(at) = version.find('+') {
+                    return Err(Error::UnexpectedChar {
+                        at: version_at + at,
+                    });
+                }
+            }
+        }
+        if operator == Operator::Compatible && parsed.release().len() < 2 {
+            return Err(Error::EmptyPart {
+                at: version_at + version.len(),
+            });
+        }
+
+        Ok(Clause {
+            operator,
+            version: version.to_string(),
+            prerelease: parsed.is_prerelease(),
+        })
+    }
+
+    /// Check whether the given `version` matches this clause.
+    fn matches(&self, version: &Pep440Version) -> bool {
+        if self.operator == Operator::Arbitrary {
+            return version.as_str().eq_ignore_ascii_case(&self.version);
+        }
+        // The version of other operators is validated when parsing
+        let spec = match Pep440Version::parse(&self.version) {
+            Ok(spec) => spec,
+            Err(_) => unreachable!(),
+        };
+        let spec = &spec;
+
+        match self.operator {
+            Operator::Cmp(Cmp::Eq) => equal(version, spec),
+            Operator::Cmp(Cmp::Ne) => !equal(version, spec),
+            Operator::Cmp(Cmp::Le) => version.cmp_public(spec) != Ordering::Greater,
+            Operator::Cmp(Cmp::Ge) => version.cmp_public(spec) != Ordering::Less,
+            Operator::Cmp(Cmp::Lt) => {
+                // A pre-release of the specified version is excluded, unless it is one itself
+                let excluded = version.cmp_base(spec) == Ordering::Equal
+                    && version.is_prerelease()
+                    && !spec.is_prerelease();
+                version < spec && !excluded
+            }
+            Operator::Cmp(Cmp::Gt) => {
+                // Post-releases and local versions of the specified version are excluded
+                let excluded = version.cmp_base(spec) == Ordering::Equal
+                    && ((version.is_postrelease() && !spec.is_postrelease())
+                        || !version.local().is_empty());
+                version > spec && !excluded
+            }
+            Operator::Prefix(Cmp::Eq) => prefix(version, spec.epoch(), spec.release()),
+            Operator::Prefix(_) => !prefix(version, spec.epoch(), spec.release()),
+            Operator::Compatible => {
+                let release = spec.release();
+                version.cmp_public(spec) != Ordering::Less
+                    && prefix(version, spec.epoch(), &release[..release.len() - 1])
+            }
+            _ => unreachable!(),
+        }
+    }
+}
+
+/// Check whether `version` equals `spec`, ignoring the local version of `version` if `spec` has
+/// none.
+fn equal(version: &Pep440Version, spec: &Pep440Version) -> bool {
+    if spec.local().is_empty() {
+        version.cmp_public(spec) == Ordering::Equal
+    } else {
+        version == spec
+    }
+}
+
+/// Check whether `version` has the given epoch, and its release starts with the given `release`
+/// numbers, padding the release of `version` with zeros.
+fn prefix(version: &Pep440Version, epoch: Number, release: &[Number]) -> bool {
+    version.epoch() == epoch
+        && release.iter().enumerate().all(|(i, number)| {
+            version
+                .release()
+                .get(i)
+                .map_or(number.is_zero(), |n| n == number)
+        })
+}
+
+#[cfg_attr(tarpaulin, skip)]
+#[cfg(test)]
+mod tests {
+    use crate::{Error, Version};
+
+    use super::Pep440Specifier;
+
+    /// Versions and specifiers they match, from the test suite of the `packaging` project.
+    const MATCHING: &[(&str, &str)] = &[
+        ("2.0", "==2"),
+        ("2.0", "==2.0"),
+        ("2.0", "==2.0.0"),
+        ("2.0+deadbeef", "==2"),
+        ("2.0+deadbeef", "==2.0"),
+        ("2.0+deadbeef", "==2.0.0"),
+        ("2.0+deadbeef", "==2+deadbeef"),
+        ("2.0+deadbeef", "==2.0+deadbeef"),
+        ("2.0+deadbeef.0", "==2.0.0+deadbeef.00"),
+        ("2.0", "==2.*"),
+        ("2.0", "==2.0.*"),
+        ("2.0.0", "==2.*"),
+        ("2.0.0", "==2.0.0.*"),
+        ("2.1+local.version", "==2.1.*"),
+        ("2", "==2.0.*"),
+        ("2.1", "!=2"),
+        ("2.1", "!=2.0"),
+        ("2.0.1", "!=2"),
+        ("2.0.1", "!=2.0"),
+        ("2.0.1", "!=2.0.0"),
+        ("2.0", "!=2.0+deadbeef"),
+        ("2.0", "!=3.*"),
+        ("2.1", "!=2.0.*"),
+        ("2.0", ">=2"),
+        ("2.0", ">=2.0"),
+        ("2.0", ">=2.0.0"),
+        ("2.0.post1", ">=2"),
+        ("2.0.post1.dev1", ">=2"),
+        ("3", ">=2"),
+        ("2.0", "<=2"),
+        ("2.0", "<=2.0"),
+        ("2.0", "<=2.0.0"),
+        ("2.0.dev1", "<=2"),
+        ("2.0a1", "<=2"),
+        ("2.0a1.dev1", "<=2"),
+        ("2.0b1", "<=2"),
+        ("2.0b1.post1", "<=2"),
+        ("2.0c1", "<=2"),
+        ("2.0c1.post1.dev1", "<=2"),
+        ("2.0rc1", "<=2"),
+        ("1", "<=2"),
+        ("3", ">2"),
+        ("2.1", ">2.0"),
+        ("2.0.1", ">2"),
+        ("2.1.post1", ">2"),
+        ("2.1+local.version", ">2"),
+        ("1", "<2"),
+        ("2.0", "<2.1"),
+        ("2.0.dev0", "<2.1"),
+        ("1", "~=1.0"),
+        ("1.0.1", "~=1.0"),
+        ("1.1", "~=1.0"),
+        ("1.9999999", "~=1.0"),
+        ("1.1", "~=1.0a1"),
+        ("2022.01.01", "~=2022.01.01"),
+        ("2!1.0", "~=2!1.0"),
+        ("2!1.0", "==2!1.*"),
+        ("2!1.0", "==2!1.0"),
+        ("2!1.0", "!=1.0"),
+        ("1.0", "!=2!1.0"),
+        ("1.0", "<=2!0.1"),
+        ("2!1.0", ">=2.0"),
+        ("1.0", "<2!0.1"),
+        ("2!1.0", ">2.0"),
+        ("2.0.5", ">2.0dev"),
+        ("1.0", "===1.0"),
+    ];
+
+    /// Versions and specifiers they don't match, from the test suite of the `packaging` project.
+    const NOT_MATCHING: &[(&str, &str)] = &[
+        ("2.1", "==2"),
+        ("2.1", "==2.0"),
+        ("2.1", "==2.0.0"),
+        ("2.0", "==2.0+deadbeef"),
+        ("2.0", "==3.*"),
+        ("2.1", "==2.0.*"),
+        ("2.0", "!=2"),
+        ("2.0", "!=2.0"),
+        ("2.0", "!=2.0.0"),
+        ("2.0+deadbeef", "!=2"),
+        ("2.0+deadbeef", "!=2.0"),
+        ("2.0+deadbeef", "!=2.0.0"),
+        ("2.0+deadbeef", "!=2+deadbeef"),
+        ("2.0+deadbeef", "!=2.0+deadbeef"),
+        ("2.0", "!=2.*"),
+        ("2.0", "!=2.0.*"),
+        ("2.0.0", "!=2.*"),
+        ("2.0.0", "!=2.0.0.*"),
+        ("2.0.dev1", ">=2"),
+        ("2.0a1", ">=2"),
+        ("2.0b1.post1", ">=2"),
+        ("1", ">=2"),
+        ("2.0.post1", "<=2"),
+        ("3", "<=2"),
+        ("1", ">2"),
+        ("2.0.dev1", ">2"),
+        ("2.0.post1", ">2"),
+        ("2.0+local.version", ">2"),
+        ("2.0.dev1", "<2"),
+        ("2.0a1", "<2"),
+        ("2.0rc1.post1", "<2"),
+        ("3", "<2"),
+        ("2.0", "~=1.0"),
+        ("1.1.0", "~=1.0.0"),
+        ("1.1.post1", "~=1.0.0"),
+        ("1.0", "~=2!1.0"),
+        ("2!1.0", "~=1.0"),
+        ("2!1.0", "==1.0"),
+        ("1.0", "==2!1.0"),
+        ("2!1.0", "==1.*"),
+        ("1.0", "==2!1.*"),
+        ("2!1.0", "!=2!1.0"),
+        ("1.0.0", "===1.0"),
+    ];
+
+    #[test]
+    fn matches() {
+        for (version, spec) in MATCHING {
+            let mut parsed = Pep440Specifier::parse(spec).unwrap();
+            parsed.set_prereleases(Some(true));
+            let version = Version::from(version).unwrap();
+            assert!(parsed.matches(&version), "{} must match {}", version, spec);
+        }
+    }
+
+    #[test]
+    fn not_matches() {
+        for (version, spec) in NOT_MATCHING {
+            let mut parsed = Pep440Specifier::parse(spec).unwrap();
+            parsed.set_prereleases(Some(true));
+            let version = Version::from(version).unwrap();
+            assert!(
+                !parsed.matches(&version),
+                "{} must not match {}",
+                version,
+                spec
+            );
+        }
+    }
+
+    #[test]
+    fn prereleases() {
+        let cases = [
+            ("", "1.0a1", false),
+            (">=1.0", "2.0a1", false),
+            (">=1.0.dev1", "2.0a1", true),
+            ("<=2.0a1", "2.0a1", true),
+            (">=1.0, !=2.0a1", "2.0a2", false),
+            ("==2.0a1", "2.0a1", true),
+            ("~=1.0a1", "1.0a2", true),
+            ("===1.0a1", "1.0a1", true),
+            ("<1.0a1", "0.9a1", false),
+            (">1.0a1", "1.0a2", false),
+            ("<1.0a1, >=0.9", "0.9a1", false),
+        ];
+        for (spec, version, matches) in &cases {
+            let spec = Pep440Specifier::parse(spec).unwrap();
+            let version = Version::from(version).unwrap();
+            assert_eq!(
+                spec.matches(&version),
+                *matches,
+                "{} against {}",
+                version,
+                spec
+            );
+        }
+
+        let mut spec = Pep440Specifier::parse(">=1.0a1").unwrap();
+        spec.set_prereleases(Some(false));
+        assert!(!spec.matches(&Version::from("1.0a2").unwrap()));
+    }
+
+    #[test]
+    fn parse_error() {
+        let errors = [
+            ("1.0", Error::UnknownOperatorSign { at: 0 }),
+            (">=1.0, =>2", Error::UnknownOperatorSign { at: 7 }),
+            (">=", Error::MissingVersion { at: 2 }),
+            (">=1.0,", Error::EmptyClause { at: 5 }),
+            (", >=1.0", Error::EmptyClause { at: 0 }),
+            (">=1.0,, <2", Error::EmptyClause { at: 6 }),
+            (">=1.0.*", Error::UnexpectedChar { at: 5 }),
+            ("==1.0a1.*", Error::UnexpectedChar { at: 5 }),
+            ("==1.0+local.*", Error::UnexpectedChar { at: 5 }),
+            (">=1.0+local", Error::UnexpectedChar { at: 5 }),
+            ("~=1.0+local", Error::UnexpectedChar { at: 5 }),
+            ("~=1", Error::EmptyPart { at: 3 }),
+            ("== french", Error::UnexpectedChar { at: 3 }),
+        ];
+        for (spec, err) in &errors {
+            assert_eq!(
+                Pep440Specifier::parse(spec).map(|s| s.to_string()).as_ref(),
+                Err(err),
+                "Parsing {}",
+                spec
+            );
+        }
+    }
+
+    #[test]
+    fn display() {
+        let specs = [
+            ("~= 1.4.2 ,!=1.4.5", "~=1.4.2, !=1.4.5"),
+            ("==1.2.*", "==1.2.*"),
+            ("===foo", "===foo"),
+            ("", ""),
+        ];
+        for (spec, display) in &specs {
+            let parsed = Pep440Specifier::parse(spec).unwrap();
+            assert_eq!(parsed.to_string(), *display);
+            assert_eq!(
+                display.parse::<Pep440Specifier>().unwrap().to_string(),
+                *display
+            );
+        }
+    }
+
+    #[test]
+    fn matches_invalid() {
+        let spec = Pep440Specifier::parse(">=1.0").unwrap();
+        assert!(spec.matches(&Version::from("v1.2").unwrap()));
+        assert!(!spec.matches(&Version::from("1.2-foo-bar").unwrap()));
+        assert!(!spec.matches(&Version::from("1.2 beta").unwrap()));
+    }
+}
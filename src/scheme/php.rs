@@ -0,0 +1,340 @@
+//! PHP version scheme, which provides the `PhpVersion` struct.
+//!
+//! PHP versions are ordered like PHP's `version_compare()`, which canonicalizes versions
+//! into dot separated runs of digits and special forms such as `alpha` or `RC`.
+
+use std::cmp::Ordering;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+
+use crate::Error;
+
+/// Special forms as matched by `version_compare()`, with their order.
+///
+/// A form matches any segment starting with it, and a number is ordered like `#`.
+const SPECIAL_FORMS: &[(&str, i8)] = &[
+    ("dev", 0),
+    ("alpha", 1),
+    ("a", 1),
+    ("beta", 2),
+    ("b", 2),
+    ("RC", 3),
+    ("rc", 3),
+    ("#", 4),
+    ("pl", 5),
+    ("p", 5),
+];
+
+/// PHP version, ordered like PHP's `version_compare()`.
+///
+/// The version is canonicalized by replacing `-`, `_`, `+` and other special characters with
+/// `.`, and inserting `.` between runs of digits and other characters. Segments are then
+/// compared one by one, numbers numerically and special forms by their order:
+/// `dev` < `alpha` = `a` < `beta` = `b` < `RC` = `rc` < `#` < numbers < `pl` = `p`. Unknown forms
+/// sort before `dev`. A version with more segments is greater if the next segment is a number,
+/// and ordered like that segment against a number otherwise, so `1.0rc1` is less than `1.0`.
+///
+/// `version_compare()` orders `#` equal to any number, which isn't transitive, as `1.0.#` would
+/// equal both `1.0.1` and `1.0.2`. To keep the order total, `#` is ordered like the end of a
+/// version instead, so `1.0#` equals `1.0` and is less than `1.0.0`. This is the only deviation
+/// from `version_compare()`.
+///
+/// # Examples
+///
+/// ```
+/// use version_compare::PhpVersion;
+///
+/// let a = PhpVersion::parse("1.0.0-dev").unwrap();
+/// let b = PhpVersion::parse("1.0.0RC1").unwrap();
+/// let c = PhpVersion::parse("1.0.0").unwrap();
+/// let d = PhpVersion::parse("1.0.0pl1").unwrap();
+///
+/// assert_eq!(b.canonical(), "1.0.0.RC.1");
+/// assert!(a < b && b < c && c < d);
+/// assert_eq!(b, PhpVersion::parse("1.0.0-rc.1").unwrap());
+/// ```
+#[derive(Debug, Clone)]
+pub struct PhpVersion<'a> {
+    version: &'a str,
+    canonical: String,
+    segments: Vec<Segment>,
+}
+
+/// Segment of a canonical version.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Segment {
+    /// Special form, with its order, `-1` if unknown.
+    Form(i8),
+
+    /// Number, saturated like `strtol`.
+    Number(i64),
+}
+
+impl<'a> PhpVersion<'a> {
+    /// Parse a PHP version string.
+    ///
+    /// `version_compare()` accepts any string, but isn't a total order for all of them. So an
+    /// error is returned for an empty version, and a version ending in a separator such as
+    /// `1.0-`, which doesn't equal itself in PHP.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use version_compare::{Error, PhpVersion};
+    ///
+    /// assert_eq!(PhpVersion::parse("5.3.0-beta_2").unwrap().canonical(), "5.3.0.beta.2");
+    /// assert_eq!(PhpVersion::parse("1.0-").unwrap_err(), Error::EmptyPart { at: 4 });
+    /// assert_eq!(PhpVersion::parse("1.0#2").unwrap().canonical(), "1.0.#.2");
+    /// ```
+    pub fn parse(version: &'a str) -> Result<Self, Error> {
+        let canonical = canonicalize(version);
+        if canonical.is_empty() || canonical.ends_with('.') {
+            return Err(Error::EmptyPart { at: version.len() });
+        }
+
+        let segments = canonical
+            .split('.')
+            .map(|segment| {
+                if segment.starts_with(|c: char| c.is_ascii_digit()) {
+                    Segment::Number(segment.parse().unwrap_or(i64::MAX))
+                } else {
+                    let form = SPECIAL_FORMS
+                        .iter()
+                        .find(|(form, _)| segment.starts_with(form));
+                    Segment::Form(form.map_or(-1, |(_, order)| *order))
+                }
+            })
+            .collect();
+
+        Ok(PhpVersion {
+            version,
+            canonical,
+            segments,
+        })
+    }
+
+    /// Get the canonical version string, as compared by `version_compare()`.
+    pub fn canonical(&self) -> &str {
+        &self.canonical
+    }
+
+    /// Get the full version string.
+    pub fn as_str(&self) -> &'a str {
+        self.version
+    }
+}
+
+/// Canonicalize a version like PHP's `php_canonicalize_version()`.
+///
+/// The first character is kept as is. Then `-`, `_`, `+` and other non-alphanumeric characters
+/// become `.`, and a `.` is inserted between a digit and a character other than a digit or `.`,
+/// without repeating `.`.
+fn canonicalize(version: &str) -> String {
+    let is_digit = |c: char| c.is_ascii_digit();
+    let is_other = |c: char| !c.is_ascii_digit() && c != '.';
+
+    let mut chars = version.chars();
+    let mut canonical = String::with_capacity(version.len() * 2);
+    let mut last = match chars.next() {
+        Some(first) => first,
+        None => return canonical,
+    };
+    canonical.push(last);
+
+    for c in chars {
+        let separated = canonical.ends_with('.');
+        if matches!(c, '-' | '_' | '+') {
+            if !separated {
+                canonical.push('.');
+            }
+        } else if (is_other(last) && is_digit(c)) || (is_digit(last) && is_other(c)) {
+            if !separated {
+                canonical.push('.');
+            }
+            canonical.push(c);
+        } else if !c.is_ascii_alphanumeric() {
+            if !separated {
+                canonical.push('.');
+            }
+        } else {
+            canonical.push(c);
+        }
+        last = c;
+    }
+    canonical
+}
+
+/// Get the rank of a segment, `None` being the end of a version.
+///
+/// The end of a version ranks like `#`, after other special forms ordered below numbers, and
+/// before any number.
+fn rank(segment: Option<&Segment>) -> (i8, i64) {
+    match segment {
+        Some(Segment::Form(order)) => (*order, -1),
+        Some(Segment::Number(n)) => (4, *n),
+        None => (4, -1),
+    }
+}
+
+impl<'a> Ord for PhpVersion<'a> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        let len = self.segments.len().max(other.segments.len());
+        (0..len)
+            .map(|i| rank(self.segments.get(i)).cmp(&rank(other.segments.get(i))))
+            .find(|ord| *ord != Ordering::Equal)
+            .unwrap_or(Ordering::Equal)
+    }
+}
+
+impl<'a> PartialOrd for PhpVersion<'a> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<'a> PartialEq for PhpVersion<'a> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl<'a> Eq for PhpVersion<'a> {}
+
+/// Segments are hashed by rank, skipping trailing `#` segments that rank like the end of a
+/// version.
+impl<'a> Hash for PhpVersion<'a> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        let len = self
+            .segments
+            .iter()
+            .rposition(|segment| rank(Some(segment)) != rank(None))
+            .map_or(0, |i| i + 1);
+        len.hash(state);
+        for segment in &self.segments[..len] {
+            rank(Some(segment)).hash(state);
+        }
+    }
+}
+
+impl<'a> fmt::Display for PhpVersion<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.version)
+    }
+}
+
+#[cfg_attr(tarpaulin, skip)]
+#[cfg(test)]
+mod tests {
+
+    use crate::test::hash_of;
+    use crate::{Cmp, Error};
+
+    use super::PhpVersion;
+
+    /// Version comparisons, as PHP's `version_compare()` reports them.
+    const VERSION_COMPARE: &[(&str, &str, Cmp)] = &[
+        ("1.0", "1.0", Cmp::Eq),
+        ("1.0", "1.0.0", Cmp::Lt),
+        ("5.2", "5.2.0", Cmp::Lt),
+        ("1.0.9", "1.0.10", Cmp::Lt),
+        ("01.002", "1.2", Cmp::Eq),
+        ("1.0-dev", "1.0-alpha", Cmp::Lt),
+        ("1.0alpha", "1.0a", Cmp::Eq),
+        ("1.0a1", "1.0b1", Cmp::Lt),
+        ("1.0beta", "1.0b", Cmp::Eq),
+        ("1.0b2", "1.0RC1", Cmp::Lt),
+        ("1.0RC1", "1.0rc1", Cmp::Eq),
+        ("1.0rc1", "1.0", Cmp::Lt),
+        ("1.0", "1.0pl1", Cmp::Lt),
+        ("1.0pl1", "1.0p1", Cmp::Eq),
+        ("1.0pl1", "1.0.1", Cmp::Gt),
+        ("1.0-dev", "1.0", Cmp::Lt),
+        ("1.0.0-beta.2", "1.0.0beta2", Cmp::Eq),
+        ("1.0.0_RC_1", "1.0.0+rc+1", Cmp::Eq),
+        ("1.0.a", "1.0", Cmp::Lt),
+        ("1.0.a", "1.0.dev", Cmp::Gt),
+        ("1.0foo", "1.0dev", Cmp::Lt),
+        ("1.0foo", "1.0xyz", Cmp::Eq),
+        ("1.0bar", "1.0beta", Cmp::Eq),
+        ("1.0Rc1", "1.0dev", Cmp::Lt),
+        ("1.0development", "1.0dev", Cmp::Eq),
+        ("1.0patch1", "1.0pl1", Cmp::Eq),
+        ("1..0", "1.0", Cmp::Eq),
+        ("1.0.0", "1.0.0.0", Cmp::Lt),
+        ("10000000000000000000", "20000000000000000000", Cmp::Eq),
+        ("1.0#1", "1.0#01", Cmp::Eq),
+        ("1.0RC1", "1.0#", Cmp::Lt),
+        ("1.0#", "1.0pl1", Cmp::Lt),
+        ("1.0#", "1.0", Cmp::Eq),
+    ];
+
+    #[test]
+    fn canonical() {
+        let versions = [
+            ("1.0.0", "1.0.0"),
+            ("1.0rc1", "1.0.rc.1"),
+            ("1.0-dev", "1.0.dev"),
+            ("5.3.0_alpha+3", "5.3.0.alpha.3"),
+            ("1..0", "1.0"),
+            ("1.0 beta", "1.0. beta"),
+            ("-1", "-.1"),
+            ("v1.2", "v.1.2"),
+            ("1.0#1", "1.0.#.1"),
+            ("1##2", "1.#.2"),
+        ];
+        for (version, canonical) in &versions {
+            assert_eq!(
+                PhpVersion::parse(version).unwrap().canonical(),
+                *canonical,
+                "Parsing {:?}",
+                version,
+            );
+        }
+    }
+
+    #[test]
+    fn parse_error() {
+        let errors = [
+            ("", Error::EmptyPart { at: 0 }),
+            (".", Error::EmptyPart { at: 1 }),
+            ("1.0.", Error::EmptyPart { at: 4 }),
+            ("1.0-", Error::EmptyPart { at: 4 }),
+            ("1.0beta_", Error::EmptyPart { at: 8 }),
+            ("#N#", Error::EmptyPart { at: 3 }),
+        ];
+        for (version, err) in &errors {
+            assert_eq!(
+                PhpVersion::parse(version).unwrap_err(),
+                *err,
+                "Parsing {:?}",
+                version,
+            );
+        }
+    }
+
+    #[test]
+    fn compare() {
+        for (a, b, cmp) in VERSION_COMPARE {
+            let (a, b) = (PhpVersion::parse(a).unwrap(), PhpVersion::parse(b).unwrap());
+            assert_eq!(Cmp::from(a.cmp(&b)), *cmp, "Comparing {} to {}", a, b);
+            assert_eq!(Cmp::from(b.cmp(&a)), cmp.flip(), "Comparing {} to {}", b, a);
+        }
+
+        // Unlike in PHP, `#` is less than any number
+        let (a, b) = (
+            PhpVersion::parse("1.0#").unwrap(),
+            PhpVersion::parse("1.0.0").unwrap(),
+        );
+        assert!(a < b);
+    }
+
+    #[test]
+    fn hash_eq() {
+        for (a, b, cmp) in VERSION_COMPARE {
+            if *cmp == Cmp::Eq {
+                let (a, b) = (PhpVersion::parse(a).unwrap(), PhpVersion::parse(b).unwrap());
+                assert_eq!(hash_of(&a), hash_of(&b), "Hashing {} and {}", a, b);
+            }
+        }
+    }
+}
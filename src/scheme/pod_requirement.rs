@@ -0,0 +1,275 @@
+//! CocoaPods requirement module, which provides the `PodRequirement` struct.
+//!
+//! A requirement is a list of comma separated clauses, such as `~> 1.2, >= 1.2.3`, as found in
+//! `Podfile` and `Podfile.lock` files. A version matches the requirement if it matches all
+//! clauses.
+
+use std::convert::TryFrom;
+use std::fmt;
+use std::str::FromStr;
+
+use crate::{Cmp, Error, Part, PartBuf, Version, VersionBuf, VersionReq};
+
+/// CocoaPods version requirement, like `Pod::Requirement`.
+///
+/// Supports the operators `=`, `!=`, `<`, `<=`, `>`, `>=` and the optimistic operator `~>`. A
+/// clause without operator means `=`. `~> 0.1.2` matches `0.1.2` up to but excluding `0.2`, and
+/// `~> 0.1` matches `0.1` up to but excluding `1.0`.
+///
+/// Versions are compared like `Version`, using the manifest of the matched version if it has any.
+///
+/// # Examples
+///
+/// ```
+/// use version_compare::{PodRequirement, Version};
+///
+/// let req = PodRequirement::parse("~> 1.2, >= 1.2.3").unwrap();
+///
+/// assert!(req.matches(&Version::from("1.9").unwrap()));
+/// assert!(!req.matches(&Version::from("1.2.2").unwrap()));
+/// assert!(!req.matches(&Version::from("2.0").unwrap()));
+/// assert_eq!(req.bounds().to_string(), ">=1.2, <2, >=1.2.3");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PodRequirement {
+    clauses: Vec<(Operator, VersionBuf)>,
+    bounds: VersionReq,
+}
+
+/// Requirement clause operator.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Operator {
+    /// Plain comparison operator.
+    Cmp(Cmp),
+
+    /// Optimistic operator `~>`.
+    Optimistic,
+}
+
+/// Operator signs, longest first.
+const OPERATORS: &[&str] = &["~>", "!=", "<=", ">=", "=", "<", ">"];
+
+impl PodRequirement {
+    /// Parse a requirement, made of comma separated clauses.
+    ///
+    /// An error is returned for malformed clauses, such as an empty clause, an unknown operator,
+    /// or an invalid version.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use version_compare::{Error, PodRequirement};
+    ///
+    /// assert!(PodRequirement::parse("1.2.3").is_ok());
+    /// assert_eq!(PodRequirement::parse("~>").unwrap_err(), Error::MissingVersion { at: 2 });
+    /// assert_eq!(PodRequirement::parse("~> 1.0,").unwrap_err(), Error::EmptyClause { at: 6 });
+    /// ```
+    pub fn parse(req: &str) -> Result<Self, Error> {
+        let mut clauses = Vec::new();
+        let mut bounds = Vec::new();
+        let mut at = 0;
+        for clause in req.split(',') {
+            // Point to the comma ending an empty clause, or the one before a trailing one
+            if clause.trim().is_empty() {
+                let end = at + clause.len();
+                let at = if end < req.len() || at == 0 {
+                    end
+                } else {
+                    at - 1
+                };
+                return Err(Error::EmptyClause { at });
+            }
+
+            let start = clause.len() - clause.trim_start().len();
+            let trimmed = clause.trim_start();
+            let (operator, sign) = match OPERATORS.iter().find(|sign| trimmed.starts_with(*sign)) {
+                Some(&"~>") => (Operator::Optimistic, "~>"),
+                Some(sign) => (Operator::Cmp(Cmp::from_sign(sign)?), *sign),
+                None => (Operator::Cmp(Cmp::Eq), ""),
+            };
+            let version = &trimmed[sign.len()..];
+            let version_at = at + start + sign.len() + version.len() - version.trim_start().len();
+            if version.trim().is_empty() {
+                return Err(Error::MissingVersion {
+                    at: at + start + sign.len() + version.len(),
+                });
+            }
+            if version.trim_start().starts_with(|c| "~<>=!".contains(c)) {
+                return Err(Error::UnexpectedChar { at: version_at });
+            }
+            let version =
+                VersionBuf::try_from(version.trim()).map_err(|err| err.offset(version_at))?;
+
+            match operator {
+                Operator::Cmp(cmp) => bounds.push((cmp, version.clone())),
+                Operator::Optimistic => {
+                    let bump = bump(&version).ok_or(Error::NumberOverflow { at: version_at })?;
+                    bounds.push((Cmp::Ge, version.clone()));
+                    bounds.push((Cmp::Lt, bump));
+                }
+            }
+            clauses.push((operator, version));
+            at += clause.len() + 1;
+        }
+
+        Ok(PodRequirement {
+            clauses,
+            bounds: VersionReq::from_clauses(bounds),
+        })
+    }
+
+    /// Get this requirement as plain comparisons, with `~>` clauses as lower and upper bound.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use version_compare::PodRequirement;
+    ///
+    /// let bounds = |req| PodRequirement::parse(req).unwrap().bounds().to_string();
+    /// assert_eq!(bounds("~> 0.1.2"), ">=0.1.2, <0.2");
+    /// assert_eq!(bounds("~> 1"), ">=1, <2");
+    /// assert_eq!(bounds("1.0"), "==1.0");
+    /// ```
+    pub fn bounds(&self) -> &VersionReq {
+        &self.bounds
+    }
+
+    /// Check whether the given `version` matches all clauses of this requirement.
+    pub fn matches(&self, version: &Version) -> bool {
+        self.bounds.matches(version)
+    }
+}
+
+impl FromStr for PodRequirement {
+    type Err = Error;
+
+    fn from_str(req: &str) -> Result<Self, Self::Err> {
+        PodRequirement::parse(req)
+    }
+}
+
+impl fmt::Display for PodRequirement {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (i, (operator, version)) in self.clauses.iter().enumerate() {
+            if i > 0 {
+                write!(f, ", ")?;
+            }
+            let sign = match operator {
+                Operator::Cmp(Cmp::Eq) => "=",
+                Operator::Cmp(cmp) => cmp.sign(),
+                Operator::Optimistic => "~>",
+            };
+            write!(f, "{} {}", sign, version)?;
+        }
+        Ok(())
+    }
+}
+
+/// Get the exclusive upper bound of `~> version`, like `Pod::Version#bump`.
+///
+/// The numbers before any text are kept, dropping the last one if there's more than one, and the
+/// new last number is incremented. Returns `None` if it overflows.
+fn bump(version: &VersionBuf) -> Option<VersionBuf> {
+    let mut numbers: Vec<u64> = Vec::new();
+    for part in version
+        .parts()
+        .iter()
+        .map(PartBuf::as_part)
+        .skip_while(|part| !matches!(part, Part::Number(_)))
+    {
+        match part {
+            Part::Number(n) => numbers.push(n.as_u64()?),
+            Part::Text(_) => break,
+        }
+    }
+    if numbers.len() > 1 {
+        numbers.pop();
+    }
+    let last = numbers.last_mut()?;
+    *last = last.checked_add(1)?;
+
+    let bump: Vec<String> = numbers.iter().map(u64::to_string).collect();
+    VersionBuf::try_from(bump.join(".")).ok()
+}
+
+#[cfg_attr(tarpaulin, skip)]
+#[cfg(test)]
+mod tests {
+    use crate::{Error, Version};
+
+    use super::PodRequirement;
+
+    #[test]
+    fn matches() {
+        let cases: &[(&str, &[&str], &[&str])] = &[
+            ("1.2", &["1.2", "1.2.0"], &["1.1", "1.3"]),
+            ("= 1.2", &["1.2"], &["1.1", "1.3"]),
+            ("!= 1.2", &["1.3"], &["1.2"]),
+            ("> 1.2", &["1.3"], &["1.1", "1.2"]),
+            (">= 1.2", &["1.2", "1.3"], &["1.1", "1.2-beta"]),
+            ("< 1.2", &["1.1", "1.2-beta"], &["1.2", "1.3"]),
+            ("<= 1.2", &["1.1", "1.2"], &["1.3"]),
+            ("> 1.1, < 1.3", &["1.2"], &["1.1", "1.3"]),
+            ("~> 0.1.2", &["0.1.2", "0.1.9"], &["0.1.1", "0.2", "0.2.0"]),
+            ("~> 0.1", &["0.1", "0.9.9"], &["0.0.9", "1.0"]),
+            ("~> 0", &["0", "0.9"], &["1.0"]),
+            ("~> 1.4.4", &["1.4.4", "1.4.5"], &["1.3", "1.4", "1.5"]),
+            (
+                "~> 1.0.0-beta.2",
+                &["1.0.0-beta.2", "1.0.0", "1.0.5"],
+                &["1.1"],
+            ),
+            ("~>2.0,>=2.0.3", &["2.0.3", "2.9"], &["2.0.2", "3.0"]),
+        ];
+        for (req, included, excluded) in cases {
+            let parsed = PodRequirement::parse(req).unwrap();
+            for version in *included {
+                let version = Version::from(version).unwrap();
+                assert!(parsed.matches(&version), "{} should match {}", req, version);
+            }
+            for version in *excluded {
+                let version = Version::from(version).unwrap();
+                assert!(
+                    !parsed.matches(&version),
+                    "{} should not match {}",
+                    req,
+                    version
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn parse_error() {
+        let errors = [
+            ("", Error::EmptyClause { at: 0 }),
+            ("~> 1.0,", Error::EmptyClause { at: 6 }),
+            (", ~> 1.0", Error::EmptyClause { at: 0 }),
+            (">=", Error::MissingVersion { at: 2 }),
+            ("~> ", Error::MissingVersion { at: 3 }),
+            ("=> 1.0", Error::UnexpectedChar { at: 1 }),
+            ("~> 1.0, ~> beta", Error::NoNumericPart { at: 11 }),
+        ];
+        for (req, err) in &errors {
+            assert_eq!(
+                PodRequirement::parse(req).unwrap_err(),
+                *err,
+                "Parsing {:?}",
+                req,
+            );
+        }
+    }
+
+    #[test]
+    fn display() {
+        let reqs = [
+            ("~> 1.2, >= 1.2.3", "~> 1.2, >= 1.2.3"),
+            ("1.2", "= 1.2"),
+            ("!=1.2,<2", "!= 1.2, < 2"),
+            (" ~>1.0-beta ", "~> 1.0-beta"),
+        ];
+        for (req, display) in &reqs {
+            assert_eq!(PodRequirement::parse(req).unwrap().to_string(), *display);
+        }
+    }
+}
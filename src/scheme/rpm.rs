@@ -0,0 +1,480 @@
+//! RPM version scheme, which provides the `RpmVersion` struct.
+//!
+//! RPM package versions have the form `[epoch:]version[-release]`, also known as EVR, and are
+//! ordered using the `rpmvercmp` algorithm from rpm.
+
+use std::cmp::Ordering;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+
+use crate::Error;
+
+/// RPM package version, ordered like rpm's `rpmvercmp`.
+///
+/// The epoch is compared first, then the version and release. Each is split into alphabetic and
+/// numeric segments, other characters only separate segments. Numeric segments are newer than
+/// alphabetic segments, `~` sorts before anything, even the end of a version, and `^` sorts after
+/// the end of a version, but before anything else.
+///
+/// rpm ignores the release when matching a dependency without release. To keep the order total,
+/// a missing release is compared as an empty release instead.
+///
+/// # Examples
+///
+/// ```
+/// use version_compare::RpmVersion;
+///
+/// let a = RpmVersion::parse("1:2.0~rc1-3.el9").unwrap();
+/// let b = RpmVersion::parse("2.0^git1-1.el9").unwrap();
+///
+/// assert_eq!(a.epoch(), 1);
+/// assert_eq!(a.version(), "2.0~rc1");
+/// assert_eq!(a.release(), Some("3.el9"));
+/// assert!(a > b);
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct RpmVersion<'a> {
+    evr: &'a str,
+    epoch: u32,
+    version: &'a str,
+    release: Option<&'a str>,
+}
+
+impl<'a> RpmVersion<'a> {
+    /// Parse an RPM version string in `[epoch:]version[-release]` form.
+    ///
+    /// The epoch is split off at a colon following only digits, and the release at the last
+    /// hyphen, as rpm does. An empty epoch is zero. An error is returned for an empty version or
+    /// release, a too large epoch, and whitespace.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use version_compare::{Error, RpmVersion};
+    ///
+    /// assert_eq!(RpmVersion::parse("0:1.0-1").unwrap().version(), "1.0");
+    /// assert_eq!(RpmVersion::parse("1:-1").unwrap_err(), Error::EmptyPart { at: 2 });
+    /// ```
+    pub fn parse(evr: &'a str) -> Result<Self, Error> {
+        if let Some(at) = evr.find(char::is_whitespace) {
+            return Err(Error::UnexpectedChar { at });
+        }
+
+        // The epoch ends at a colon, following nothing but digits
+        let digits = evr.bytes().take_while(u8::is_ascii_digit).count();
+        let (epoch, version_at) = if evr[digits..].starts_with(':') {
+            match &evr[..digits] {
+                "" => (0, digits + 1),
+                epoch => match epoch.parse() {
+                    Ok(epoch) => (epoch, digits + 1),
+                    Err(_) => return Err(Error::NumberOverflow { at: 0 }),
+                },
+            }
+        } else {
+            (0, 0)
+        };
+
+        // The release starts after the last hyphen
+        let rest = &evr[version_at..];
+        let (version, release) = match rest.rfind('-') {
+            Some(hyphen) if hyphen + 1 == rest.len() => {
+                return Err(Error::EmptyPart {
+                    at: version_at + hyphen + 1,
+                })
+            }
+            Some(hyphen) => (&rest[..hyphen], Some(&rest[hyphen + 1..])),
+            None => (rest, None),
+        };
+        if version.is_empty() {
+            return Err(Error::EmptyPart { at: version_at });
+        }
+
+        Ok(RpmVersion {
+            evr,
+            epoch,
+            version,
+            release,
+        })
+    }
+
+    /// Get the epoch, `0` if the version has none.
+    pub fn epoch(&self) -> u32 {
+        self.epoch
+    }
+
+    /// Get the version, without epoch and release.
+    pub fn version(&self) -> &'a str {
+        self.version
+    }
+
+    /// Get the release, if any.
+    pub fn release(&self) -> Option<&'a str> {
+        self.release
+    }
+
+    /// Get the full version string.
+    pub fn as_str(&self) -> &'a str {
+        self.evr
+    }
+}
+
+impl<'a> Ord for RpmVersion<'a> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.epoch
+            .cmp(&other.epoch)
+            .then_with(|| rpmvercmp(self.version, other.version))
+            .then_with(|| rpmvercmp(self.release.unwrap_or(""), other.release.unwrap_or("")))
+    }
+}
+
+impl<'a> PartialOrd for RpmVersion<'a> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<'a> PartialEq for RpmVersion<'a> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl<'a> Eq for RpmVersion<'a> {}
+
+/// `rpmvercmp` ignores separators and leading zeros, so `1.01` and `1_1` hash alike.
+impl<'a> Hash for RpmVersion<'a> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.epoch.hash(state);
+        hash_segments(self.version, state);
+        hash_segments(self.release.unwrap_or(""), state);
+    }
+}
+
+impl<'a> fmt::Display for RpmVersion<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.evr)
+    }
+}
+
+/// Check whether the given character starts a segment, any other character is a separator.
+fn is_segment_char(c: u8) -> bool {
+    c.is_ascii_alphanumeric() || c == b'~' || c == b'^'
+}
+
+/// Get the alphabetic or numeric segment at the start of the given string.
+fn segment(s: &[u8], numeric: bool) -> &[u8] {
+    let len = s
+        .iter()
+        .take_while(|c| {
+            if numeric {
+                c.is_ascii_digit()
+            } else {
+                c.is_ascii_alphabetic()
+            }
+        })
+        .count();
+    &s[..len]
+}
+
+/// Compare a version or release, as rpm's `rpmvercmp` does.
+fn rpmvercmp(a: &str, b: &str) -> Ordering {
+    if a == b {
+        return Ordering::Equal;
+    }
+
+    let (a, b) = (a.as_bytes(), b.as_bytes());
+    let (mut i, mut j) = (0, 0);
+    loop {
+        while i < a.len() && !is_segment_char(a[i]) {
+            i += 1;
+        }
+        while j < b.len() && !is_segment_char(b[j]) {
+            j += 1;
+        }
+        let (one, two) = (a.get(i).copied(), b.get(j).copied());
+
+        // A tilde sorts before everything else
+        if one == Some(b'~') || two == Some(b'~') {
+            if one != Some(b'~') {
+                return Ordering::Greater;
+            }
+            if two != Some(b'~') {
+                return Ordering::Less;
+            }
+            i += 1;
+            j += 1;
+            continue;
+        }
+
+        // A caret sorts like a tilde, except that the end of a version sorts before it
+        if one == Some(b'^') || two == Some(b'^') {
+            if one.is_none() {
+                return Ordering::Less;
+            }
+            if two.is_none() {
+                return Ordering::Greater;
+            }
+            if one != Some(b'^') {
+                return Ordering::Greater;
+            }
+            if two != Some(b'^') {
+                return Ordering::Less;
+            }
+            i += 1;
+            j += 1;
+            continue;
+        }
+
+        let (one, two) = match (one, two) {
+            (Some(one), Some(_)) => {
+                let numeric = one.is_ascii_digit();
+                (segment(&a[i..], numeric), segment(&b[j..], numeric))
+            }
+            _ => break,
+        };
+        i += one.len();
+        j += two.len();
+
+        // Segments of different types, numeric segments are newer than alphabetic ones
+        let numeric = one[0].is_ascii_digit();
+        if two.is_empty() {
+            return if numeric {
+                Ordering::Greater
+            } else {
+                Ordering::Less
+            };
+        }
+
+        let ord = if numeric {
+            let (one, two) = (trim_zeros(one), trim_zeros(two));
+            one.len().cmp(&two.len()).then_with(|| one.cmp(two))
+        } else {
+            one.cmp(two)
+        };
+        if ord != Ordering::Equal {
+            return ord;
+        }
+    }
+
+    // Whichever version has characters left wins
+    match (i < a.len(), j < b.len()) {
+        (false, false) => Ordering::Equal,
+        (false, true) => Ordering::Less,
+        (true, _) => Ordering::Greater,
+    }
+}
+
+/// Trim leading zeros from a numeric segment.
+fn trim_zeros(digits: &[u8]) -> &[u8] {
+    let zeros = digits.iter().take_while(|&&c| c == b'0').count();
+    &digits[zeros..]
+}
+
+/// Hash a version or release, consistent with `rpmvercmp`.
+///
+/// Hashes each segment and each `~` and `^`, skipping separators and leading zeros.
+fn hash_segments<H: Hasher>(version: &str, state: &mut H) {
+    let mut rest = version.as_bytes();
+    while let Some(&c) = rest.first() {
+        if !is_segment_char(c) {
+            rest = &rest[1..];
+            continue;
+        }
+        let len = match c {
+            b'~' | b'^' => 1,
+            c => segment(rest, c.is_ascii_digit()).len(),
+        };
+        let token = if c.is_ascii_digit() {
+            trim_zeros(&rest[..len])
+        } else {
+            &rest[..len]
+        };
+        c.is_ascii_digit().hash(state);
+        token.hash(state);
+        rest = &rest[len..];
+    }
+    0xffu8.hash(state);
+}
+
+#[cfg_attr(tarpaulin, skip)]
+#[cfg(test)]
+mod tests {
+    use std::cmp::Ordering;
+
+    use crate::test::hash_of;
+    use crate::{Cmp, Error};
+
+    use super::{rpmvercmp, RpmVersion};
+
+    /// Version comparisons, taken from rpm's own `rpmvercmp` test suite.
+    const RPMVERCMP: &[(&str, &str, Cmp)] = &[
+        ("1.0", "1.0", Cmp::Eq),
+        ("1.0", "2.0", Cmp::Lt),
+        ("2.0", "1.0", Cmp::Gt),
+        ("2.0.1", "2.0.1", Cmp::Eq),
+        ("2.0", "2.0.1", Cmp::Lt),
+        ("2.0.1", "2.0", Cmp::Gt),
+        ("2.0.1a", "2.0.1a", Cmp::Eq),
+        ("2.0.1a", "2.0.1", Cmp::Gt),
+        ("2.0.1", "2.0.1a", Cmp::Lt),
+        ("5.5p1", "5.5p1", Cmp::Eq),
+        ("5.5p1", "5.5p2", Cmp::Lt),
+        ("5.5p2", "5.5p1", Cmp::Gt),
+        ("5.5p10", "5.5p10", Cmp::Eq),
+        ("5.5p1", "5.5p10", Cmp::Lt),
+        ("5.5p10", "5.5p1", Cmp::Gt),
+        ("10xyz", "10.1xyz", Cmp::Lt),
+        ("10.1xyz", "10xyz", Cmp::Gt),
+        ("xyz10", "xyz10", Cmp::Eq),
+        ("xyz10", "xyz10.1", Cmp::Lt),
+        ("xyz10.1", "xyz10", Cmp::Gt),
+        ("xyz.4", "xyz.4", Cmp::Eq),
+        ("xyz.4", "8", Cmp::Lt),
+        ("8", "xyz.4", Cmp::Gt),
+        ("xyz.4", "2", Cmp::Lt),
+        ("2", "xyz.4", Cmp::Gt),
+        ("5.5p2", "5.6p1", Cmp::Lt),
+        ("5.6p1", "5.5p2", Cmp::Gt),
+        ("5.6p1", "6.5p1", Cmp::Lt),
+        ("6.5p1", "5.6p1", Cmp::Gt),
+        ("6.0.rc1", "6.0", Cmp::Gt),
+        ("6.0", "6.0.rc1", Cmp::Lt),
+        ("10b2", "10a1", Cmp::Gt),
+        ("10a2", "10b2", Cmp::Lt),
+        ("1.0aa", "1.0aa", Cmp::Eq),
+        ("1.0a", "1.0aa", Cmp::Lt),
+        ("1.0aa", "1.0a", Cmp::Gt),
+        ("10.0001", "10.0001", Cmp::Eq),
+        ("10.0001", "10.1", Cmp::Eq),
+        ("10.1", "10.0001", Cmp::Eq),
+        ("10.0001", "10.0039", Cmp::Lt),
+        ("10.0039", "10.0001", Cmp::Gt),
+        ("4.999.9", "5.0", Cmp::Lt),
+        ("5.0", "4.999.9", Cmp::Gt),
+        ("20101121", "20101121", Cmp::Eq),
+        ("20101121", "20101122", Cmp::Lt),
+        ("20101122", "20101121", Cmp::Gt),
+        ("2_0", "2_0", Cmp::Eq),
+        ("2.0", "2_0", Cmp::Eq),
+        ("2_0", "2.0", Cmp::Eq),
+        ("a", "a", Cmp::Eq),
+        ("a+", "a+", Cmp::Eq),
+        ("a+", "a_", Cmp::Eq),
+        ("a_", "a+", Cmp::Eq),
+        ("+a", "+a", Cmp::Eq),
+        ("+a", "_a", Cmp::Eq),
+        ("_a", "+a", Cmp::Eq),
+        ("+_", "+_", Cmp::Eq),
+        ("_+", "+_", Cmp::Eq),
+        ("_+", "_", Cmp::Eq),
+        ("+", "_", Cmp::Eq),
+        ("1.0~rc1", "1.0~rc1", Cmp::Eq),
+        ("1.0~rc1", "1.0", Cmp::Lt),
+        ("1.0", "1.0~rc1", Cmp::Gt),
+        ("1.0~rc1", "1.0~rc2", Cmp::Lt),
+        ("1.0~rc2", "1.0~rc1", Cmp::Gt),
+        ("1.0~rc1~git123", "1.0~rc1~git123", Cmp::Eq),
+        ("1.0~rc1~git123", "1.0~rc1", Cmp::Lt),
+        ("1.0~rc1", "1.0~rc1~git123", Cmp::Gt),
+        ("1.0^", "1.0^", Cmp::Eq),
+        ("1.0^", "1.0", Cmp::Gt),
+        ("1.0", "1.0^", Cmp::Lt),
+        ("1.0^git1", "1.0^git1", Cmp::Eq),
+        ("1.0^git1", "1.0", Cmp::Gt),
+        ("1.0", "1.0^git1", Cmp::Lt),
+        ("1.0^git1", "1.0^git2", Cmp::Lt),
+        ("1.0^git2", "1.0^git1", Cmp::Gt),
+        ("1.0^git1", "1.01", Cmp::Lt),
+        ("1.01", "1.0^git1", Cmp::Gt),
+        ("1.0^20160101", "1.0^20160101", Cmp::Eq),
+        ("1.0^20160101", "1.0.1", Cmp::Lt),
+        ("1.0.1", "1.0^20160101", Cmp::Gt),
+        ("1.0^20160101^git1", "1.0^20160101^git1", Cmp::Eq),
+        ("1.0^20160102", "1.0^20160101^git1", Cmp::Gt),
+        ("1.0^20160101^git1", "1.0^20160102", Cmp::Lt),
+        ("1.0~rc1^git1", "1.0~rc1^git1", Cmp::Eq),
+        ("1.0~rc1^git1", "1.0~rc1", Cmp::Gt),
+        ("1.0~rc1", "1.0~rc1^git1", Cmp::Lt),
+        ("1.0^git1~pre", "1.0^git1~pre", Cmp::Eq),
+        ("1.0^git1", "1.0^git1~pre", Cmp::Gt),
+        ("1.0^git1~pre", "1.0^git1", Cmp::Lt),
+    ];
+
+    #[test]
+    fn parse() {
+        let version = RpmVersion::parse("2:1.0-1-3.fc39").unwrap();
+        assert_eq!(version.epoch(), 2);
+        assert_eq!(version.version(), "1.0-1");
+        assert_eq!(version.release(), Some("3.fc39"));
+        assert_eq!(version.as_str(), "2:1.0-1-3.fc39");
+
+        let version = RpmVersion::parse(":1.0").unwrap();
+        assert_eq!(version.epoch(), 0);
+        assert_eq!(version.version(), "1.0");
+        assert_eq!(version.release(), None);
+
+        // A colon after anything but digits doesn't end an epoch
+        let version = RpmVersion::parse("a:1.0").unwrap();
+        assert_eq!(version.epoch(), 0);
+        assert_eq!(version.version(), "a:1.0");
+    }
+
+    #[test]
+    fn parse_error() {
+        let errors = [
+            ("", Error::EmptyPart { at: 0 }),
+            ("1:", Error::EmptyPart { at: 2 }),
+            ("-1", Error::EmptyPart { at: 0 }),
+            ("1.0-", Error::EmptyPart { at: 4 }),
+            ("1.0 -1", Error::UnexpectedChar { at: 3 }),
+            ("4294967296:1.0", Error::NumberOverflow { at: 0 }),
+        ];
+        for (version, err) in &errors {
+            assert_eq!(
+                RpmVersion::parse(version).as_ref(),
+                Err(err),
+                "Parsing {:?}",
+                version,
+            );
+        }
+    }
+
+    #[test]
+    fn rpmvercmp_parity() {
+        for (a, b, cmp) in RPMVERCMP {
+            assert_eq!(Cmp::from(rpmvercmp(a, b)), *cmp, "Comparing {} to {}", a, b);
+        }
+    }
+
+    #[test]
+    fn compare() {
+        let combis = [
+            ("1:1.0-1", "2.0-1", Cmp::Gt),
+            ("0:1.0-1", "1.0-1", Cmp::Eq),
+            ("1.0-1", "1.0-2", Cmp::Lt),
+            ("1.0-1.el9", "1.0-1.el9_2", Cmp::Lt),
+            ("1.0-10", "1.0-9", Cmp::Gt),
+            ("1.0", "1.0-1", Cmp::Lt),
+            ("1.0~rc1-5", "1.0-1", Cmp::Lt),
+        ];
+        for (a, b, cmp) in &combis {
+            let (a, b) = (RpmVersion::parse(a).unwrap(), RpmVersion::parse(b).unwrap());
+            assert_eq!(Cmp::from(a.cmp(&b)), *cmp, "Comparing {} to {}", a, b);
+        }
+    }
+
+    #[test]
+    fn hash_eq() {
+        for (a, b, cmp) in RPMVERCMP {
+            let (a, b) = (RpmVersion::parse(a).unwrap(), RpmVersion::parse(b).unwrap());
+            if *cmp == Cmp::Eq {
+                assert_eq!(a.cmp(&b), Ordering::Equal);
+                assert_eq!(hash_of(&a), hash_of(&b), "Hashing {} and {}", a, b);
+            }
+        }
+        assert_eq!(
+            hash_of(&RpmVersion::parse("1.01-_").unwrap()),
+            hash_of(&RpmVersion::parse("1_1").unwrap()),
+        );
+    }
+}
@@ -0,0 +1,349 @@
+//! RubyGems version scheme, which provides the `GemVersion` struct.
+//!
+//! Gem versions are dot separated numbers and letters, such as `1.2.0.rc1`, and are ordered like
+//! RubyGems' `Gem::Version`. Any letter makes a version a prerelease.
+
+use std::cmp::Ordering;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+
+use crate::{Error, Number, Part};
+
+/// RubyGems version, ordered like `Gem::Version`.
+///
+/// Versions are split into numeric and alphabetic segments, a `-` being read as `.pre.`.
+/// Alphabetic segments sort before numeric ones, so `1.0.a` is less than `1.0`. Trailing zeros
+/// are ignored, so `1.0` equals `1`.
+///
+/// # Examples
+///
+/// ```
+/// use version_compare::GemVersion;
+///
+/// let a = GemVersion::parse("1.0.a").unwrap();
+/// let b = GemVersion::parse("1.0").unwrap();
+/// let c = GemVersion::parse("1").unwrap();
+///
+/// assert!(a.is_prerelease());
+/// assert!(a < b);
+/// assert_eq!(b, c);
+/// ```
+#[derive(Debug, Clone)]
+pub struct GemVersion<'a> {
+    version: &'a str,
+    segments: Vec<Part<'a>>,
+    canonical: Vec<Part<'a>>,
+}
+
+impl<'a> GemVersion<'a> {
+    /// Parse a gem version string.
+    ///
+    /// Surrounding whitespace is ignored, and an empty version is `0`, like `Gem::Version`. An
+    /// error is returned for anything `Gem::Version` rejects as malformed, such as a version not
+    /// starting with a number, or empty segments.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use version_compare::{Error, GemVersion};
+    ///
+    /// assert_eq!(GemVersion::parse("").unwrap().as_str(), "0");
+    /// assert!(GemVersion::parse(" 1.2.0-rc.1 ").is_ok());
+    /// assert_eq!(GemVersion::parse("1..2").unwrap_err(), Error::EmptyPart { at: 2 });
+    /// assert_eq!(GemVersion::parse("v1.2").unwrap_err(), Error::UnexpectedChar { at: 0 });
+    /// ```
+    pub fn parse(version: &'a str) -> Result<Self, Error> {
+        let start = version.len() - version.trim_start().len();
+        let version = match version.trim() {
+            "" => "0",
+            version => version,
+        };
+        let bytes = version.as_bytes();
+        let run = |at: usize, hyphen: bool| {
+            bytes[at..]
+                .iter()
+                .take_while(|c| c.is_ascii_alphanumeric() || (hyphen && **c == b'-'))
+                .count()
+        };
+        let empty_or_unexpected = |at: usize| {
+            if bytes.get(at).map_or(true, |c| *c == b'.' || *c == b'-') {
+                Error::EmptyPart { at: start + at }
+            } else {
+                Error::UnexpectedChar { at: start + at }
+            }
+        };
+
+        // A number, then dot separated alphanumeric segments
+        let mut at = bytes.iter().take_while(|c| c.is_ascii_digit()).count();
+        if at == 0 {
+            return Err(empty_or_unexpected(0));
+        }
+        while bytes.get(at) == Some(&b'.') {
+            let len = run(at + 1, false);
+            if len == 0 {
+                return Err(empty_or_unexpected(at + 1));
+            }
+            at += len + 1;
+        }
+
+        // A pre-release of dot separated alphanumeric segments, which may contain hyphens
+        if bytes.get(at) == Some(&b'-') {
+            at += 1;
+            loop {
+                let len = run(at, true);
+                if len == 0 {
+                    return Err(empty_or_unexpected(at));
+                }
+                at += len;
+                if bytes.get(at) != Some(&b'.') {
+                    break;
+                }
+                at += 1;
+            }
+        }
+        if at < bytes.len() {
+            return Err(Error::UnexpectedChar { at: start + at });
+        }
+
+        let segments = segments(version);
+        let canonical = canonical(&segments);
+        Ok(GemVersion {
+            version,
+            segments,
+            canonical,
+        })
+    }
+
+    /// Get the numeric and alphabetic segments, with `pre` for each `-`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use version_compare::{GemVersion, Part};
+    ///
+    /// let version = GemVersion::parse("1.0-rc1").unwrap();
+    /// assert_eq!(
+    ///     version.segments(),
+    ///     &[
+    ///         Part::Number(1.into()),
+    ///         Part::Number(0.into()),
+    ///         Part::Text("pre"),
+    ///         Part::Text("rc"),
+    ///         Part::Number(1.into()),
+    ///     ],
+    /// );
+    /// ```
+    pub fn segments(&self) -> &[Part<'a>] {
+        &self.segments
+    }
+
+    /// Check whether this is a prerelease, containing any letter.
+    pub fn is_prerelease(&self) -> bool {
+        self.segments.iter().any(|s| matches!(s, Part::Text(_)))
+    }
+
+    /// Get the version string, without surrounding whitespace.
+    pub fn as_str(&self) -> &'a str {
+        self.version
+    }
+
+    /// Get the numeric segments before the first alphabetic segment, like `Gem::Version#release`.
+    pub(super) fn release_segments(&self) -> &[Part<'a>] {
+        let end = self
+            .segments
+            .iter()
+            .position(|s| matches!(s, Part::Text(_)))
+            .unwrap_or(self.segments.len());
+        &self.segments[..end]
+    }
+}
+
+impl<'a> Ord for GemVersion<'a> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        cmp_segments(&self.canonical, &other.canonical)
+    }
+}
+
+impl<'a> PartialOrd for GemVersion<'a> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<'a> PartialEq for GemVersion<'a> {
+    fn eq(&self, other: &Self) -> bool {
+        self.canonical == other.canonical
+    }
+}
+
+impl<'a> Eq for GemVersion<'a> {}
+
+/// Only the canonical segments are hashed, in which `1.0` is just `1`.
+impl<'a> Hash for GemVersion<'a> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.canonical.len().hash(state);
+        for segment in &self.canonical {
+            match segment {
+                Part::Number(n) => n.hash(state),
+                Part::Text(t) => t.hash(state),
+            }
+        }
+    }
+}
+
+impl<'a> fmt::Display for GemVersion<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.version)
+    }
+}
+
+/// Split a valid version into numeric and alphabetic segments, like `Gem::Version#segments`.
+fn segments(version: &str) -> Vec<Part<'_>> {
+    let bytes = version.as_bytes();
+    let mut segments = Vec::new();
+    let mut at = 0;
+    while at < bytes.len() {
+        let c = bytes[at];
+        let len = bytes[at..]
+            .iter()
+            .take_while(|b| {
+                (c.is_ascii_digit() && b.is_ascii_digit())
+                    || (c.is_ascii_alphabetic() && b.is_ascii_alphabetic())
+            })
+            .count();
+        match len {
+            0 if c == b'-' => segments.push(Part::Text("pre")),
+            0 => {}
+            _ if c.is_ascii_digit() => {
+                segments.push(Part::Number(Number::parse(&version[at..at + len]).unwrap()))
+            }
+            _ => segments.push(Part::Text(&version[at..at + len])),
+        }
+        at += len.max(1);
+    }
+    segments
+}
+
+/// Get the canonical segments, without trailing zeros in both the numeric and the alphabetic
+/// part, like `Gem::Version#canonical_segments`.
+fn canonical<'a>(segments: &[Part<'a>]) -> Vec<Part<'a>> {
+    let split = segments
+        .iter()
+        .position(|s| matches!(s, Part::Text(_)))
+        .unwrap_or(segments.len());
+    let trim = |segments: &[Part<'a>]| {
+        let len = segments
+            .iter()
+            .rposition(|s| !matches!(s, Part::Number(n) if n.is_zero()))
+            .map_or(0, |i| i + 1);
+        segments[..len].to_vec()
+    };
+
+    let mut canonical = trim(&segments[..split]);
+    canonical.extend(trim(&segments[split..]));
+    canonical
+}
+
+/// Compare two lists of segments, missing segments being zero, like `Gem::Version#<=>`.
+fn cmp_segments(a: &[Part], b: &[Part]) -> Ordering {
+    let zero = Part::Number(0.into());
+    for i in 0..a.len().max(b.len()) {
+        let ord = match (a.get(i).unwrap_or(&zero), b.get(i).unwrap_or(&zero)) {
+            (Part::Number(a), Part::Number(b)) => a.cmp(b),
+            (Part::Text(_), Part::Number(_)) => Ordering::Less,
+            (Part::Number(_), Part::Text(_)) => Ordering::Greater,
+            (Part::Text(a), Part::Text(b)) => a.cmp(b),
+        };
+        if ord != Ordering::Equal {
+            return ord;
+        }
+    }
+    Ordering::Equal
+}
+
+#[cfg_attr(tarpaulin, skip)]
+#[cfg(test)]
+mod tests {
+
+    use crate::test::hash_of;
+    use crate::{Cmp, Error};
+
+    use super::GemVersion;
+
+    /// Version comparisons, as `Gem::Version#<=>` reports them.
+    const SPACESHIP: &[(&str, &str, Cmp)] = &[
+        ("1.0", "1.0.0", Cmp::Eq),
+        ("1.0", "1.0.a", Cmp::Gt),
+        ("1.8.2", "0.0.0", Cmp::Gt),
+        ("1.8.2", "1.8.2.a", Cmp::Gt),
+        ("1.8.2.b", "1.8.2.a", Cmp::Gt),
+        ("1.8.2.a", "1.8.2", Cmp::Lt),
+        ("1.8.2.a10", "1.8.2.a9", Cmp::Gt),
+        ("", "0", Cmp::Eq),
+        ("0.beta.1", "0.0.beta.1", Cmp::Eq),
+        ("0.0.beta", "0.0.beta.1", Cmp::Lt),
+        ("0.0.beta", "0.beta.1", Cmp::Lt),
+        ("5.a", "5.0.0.rc2", Cmp::Lt),
+        ("5.x", "5.0.0.rc2", Cmp::Gt),
+        ("1.0-beta", "1.0.pre.beta", Cmp::Eq),
+        ("1.0-1", "1.0", Cmp::Lt),
+        ("1.0.0.rc1", "1.0.0.RC1", Cmp::Gt),
+        ("1.10", "1.9", Cmp::Gt),
+        ("99999999999999999999", "99999999999999999999.0", Cmp::Eq),
+    ];
+
+    #[test]
+    fn parse() {
+        let version = GemVersion::parse(" 1.2b-rc.1 ").unwrap();
+        assert_eq!(version.as_str(), "1.2b-rc.1");
+        assert_eq!(version.segments().len(), 6);
+        assert!(version.is_prerelease());
+        assert!(!GemVersion::parse("22.1.50.0").unwrap().is_prerelease());
+        assert!(GemVersion::parse("1.A").unwrap().is_prerelease());
+        assert!(GemVersion::parse("1-1").unwrap().is_prerelease());
+    }
+
+    #[test]
+    fn parse_error() {
+        let errors = [
+            (".1", Error::EmptyPart { at: 0 }),
+            ("a", Error::UnexpectedChar { at: 0 }),
+            ("1.", Error::EmptyPart { at: 2 }),
+            ("1..2", Error::EmptyPart { at: 2 }),
+            ("1-", Error::EmptyPart { at: 2 }),
+            ("1-a..b", Error::EmptyPart { at: 4 }),
+            ("1.2 3", Error::UnexpectedChar { at: 3 }),
+            ("1.2_3", Error::UnexpectedChar { at: 3 }),
+            ("1.2-a_b", Error::UnexpectedChar { at: 5 }),
+            ("1.2-a+b", Error::UnexpectedChar { at: 5 }),
+            (" junk", Error::UnexpectedChar { at: 1 }),
+        ];
+        for (version, err) in &errors {
+            assert_eq!(
+                GemVersion::parse(version).unwrap_err(),
+                *err,
+                "Parsing {:?}",
+                version,
+            );
+        }
+    }
+
+    #[test]
+    fn compare() {
+        for (a, b, cmp) in SPACESHIP {
+            let (a, b) = (GemVersion::parse(a).unwrap(), GemVersion::parse(b).unwrap());
+            assert_eq!(Cmp::from(a.cmp(&b)), *cmp, "Comparing {} to {}", a, b);
+            assert_eq!(Cmp::from(b.cmp(&a)), cmp.flip(), "Comparing {} to {}", b, a);
+        }
+    }
+
+    #[test]
+    fn hash_eq() {
+        for (a, b, cmp) in SPACESHIP {
+            if *cmp == Cmp::Eq {
+                let (a, b) = (GemVersion::parse(a).unwrap(), GemVersion::parse(b).unwrap());
+                assert_eq!(hash_of(&a), hash_of(&b), "Hashing {} and {}", a, b);
+            }
+        }
+    }
+}
@@ -0,0 +1,410 @@
+//! Semantic versioning scheme, which provides the `SemverVersion` struct.
+//!
+//! Semantic versions are specified by Semantic Versioning 2.0.0, and have the strict form
+//! `major.minor.patch[-pre][+build]`.
+
+use std::cmp::Ordering;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+
+use crate::{Error, Number, Part};
+
+/// Semantic version, as specified by Semantic Versioning 2.0.0.
+///
+/// Versions are validated strictly. Pre-release identifiers are ordered as specified in section
+/// 11 of the specification: numeric identifiers are compared numerically and sort before
+/// alphanumeric identifiers, which are compared in ASCII order. Build metadata is ignored when
+/// comparing, so `1.0.0+a` equals `1.0.0+b`.
+///
+/// # Examples
+///
+/// ```
+/// use version_compare::SemverVersion;
+///
+/// let version = SemverVersion::parse("1.2.3-rc.1+build.5").unwrap();
+///
+/// assert_eq!(version.major(), 1.into());
+/// assert_eq!(version.minor(), 2.into());
+/// assert_eq!(version.patch(), 3.into());
+/// assert_eq!(version.pre(), Some("rc.1"));
+/// assert_eq!(version.build(), Some("build.5"));
+///
+/// let a = SemverVersion::parse("1.0.0-alpha.1").unwrap();
+/// let b = SemverVersion::parse("1.0.0-alpha.beta+exp.sha.5114f85").unwrap();
+/// let c = SemverVersion::parse("1.0.0-alpha.beta").unwrap();
+///
+/// assert!(a < b);
+/// assert_eq!(b, c);
+/// ```
+#[derive(Debug, Clone)]
+pub struct SemverVersion<'a> {
+    version: &'a str,
+    major: Number<'a>,
+    minor: Number<'a>,
+    patch: Number<'a>,
+    pre: Option<&'a str>,
+    pre_identifiers: Vec<Part<'a>>,
+    build: Option<&'a str>,
+}
+
+impl<'a> SemverVersion<'a> {
+    /// Parse a semantic version string.
+    ///
+    /// An error is returned for anything that isn't a valid semantic version, including missing
+    /// components, leading zeros in numeric identifiers, empty identifiers, invalid characters and
+    /// surrounding whitespace.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use version_compare::{Error, SemverVersion};
+    ///
+    /// assert_eq!(SemverVersion::parse("1.2").unwrap_err(), Error::EmptyPart { at: 3 });
+    /// assert_eq!(SemverVersion::parse("1.02.3").unwrap_err(), Error::LeadingZero { at: 2 });
+    /// assert_eq!(SemverVersion::parse("1.2.3-rc_1").unwrap_err(), Error::UnexpectedChar { at: 8 });
+    /// ```
+    pub fn parse(version: &'a str) -> Result<Self, Error> {
+        // Split off build metadata, then the pre-release
+        let (rest, build) = match version.find('+') {
+            Some(plus) => (&version[..plus], Some((plus + 1, &version[plus + 1..]))),
+            None => (version, None),
+        };
+        let (core, pre) = match rest.find('-') {
+            Some(hyphen) => (&rest[..hyphen], Some((hyphen + 1, &rest[hyphen + 1..]))),
+            None => (rest, None),
+        };
+
+        // The core has exactly three numeric identifiers
+        let mut numbers = Vec::with_capacity(3);
+        let mut at = 0;
+        for (i, number) in core.split('.').enumerate() {
+            if i == 3 {
+                return Err(Error::UnexpectedChar { at: at - 1 });
+            }
+            numbers.push(numeric(number, at)?.ok_or(Error::UnexpectedChar {
+                at: at + invalid_char(number, |c| c.is_ascii_digit()).unwrap_or(0),
+            })?);
+            at += number.len() + 1;
+        }
+        if numbers.len() < 3 {
+            return Err(Error::EmptyPart { at: core.len() });
+        }
+
+        // Pre-release identifiers are numeric without leading zeros, or alphanumeric
+        let mut pre_identifiers = Vec::new();
+        if let Some((at, pre)) = pre {
+            for_identifiers(pre, at, |identifier, at| {
+                pre_identifiers.push(match numeric(identifier, at)? {
+                    Some(number) => Part::Number(number),
+                    None => Part::Text(identifier),
+                });
+                Ok(())
+            })?;
+        }
+        if let Some((at, build)) = build {
+            for_identifiers(build, at, |_, _| Ok(()))?;
+        }
+
+        Ok(SemverVersion {
+            version,
+            major: numbers[0],
+            minor: numbers[1],
+            patch: numbers[2],
+            pre: pre.map(|(_, pre)| pre),
+            pre_identifiers,
+            build: build.map(|(_, build)| build),
+        })
+    }
+
+    /// Get the major version number.
+    pub fn major(&self) -> Number<'a> {
+        self.major
+    }
+
+    /// Get the minor version number.
+    pub fn minor(&self) -> Number<'a> {
+        self.minor
+    }
+
+    /// Get the patch version number.
+    pub fn patch(&self) -> Number<'a> {
+        self.patch
+    }
+
+    /// Get the pre-release, without leading `-`, if any.
+    pub fn pre(&self) -> Option<&'a str> {
+        self.pre
+    }
+
+    /// Get the build metadata, without leading `+`, if any.
+    pub fn build(&self) -> Option<&'a str> {
+        self.build
+    }
+
+    /// Check whether this is a pre-release.
+    pub fn is_prerelease(&self) -> bool {
+        self.pre.is_some()
+    }
+
+    /// Get the version string.
+    pub fn as_str(&self) -> &'a str {
+        self.version
+    }
+}
+
+impl<'a> Ord for SemverVersion<'a> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.major
+            .cmp(&other.major)
+            .then_with(|| self.minor.cmp(&other.minor))
+            .then_with(|| self.patch.cmp(&other.patch))
+            .then_with(|| match (self.pre, other.pre) {
+                (None, None) => Ordering::Equal,
+                (None, Some(_)) => Ordering::Greater,
+                (Some(_), None) => Ordering::Less,
+                (Some(_), Some(_)) => {
+                    let pairs = self.pre_identifiers.iter().zip(&other.pre_identifiers);
+                    for (a, b) in pairs {
+                        let ord = match (a, b) {
+                            (Part::Number(a), Part::Number(b)) => a.cmp(b),
+                            (Part::Number(_), Part::Text(_)) => Ordering::Less,
+                            (Part::Text(_), Part::Number(_)) => Ordering::Greater,
+                            (Part::Text(a), Part::Text(b)) => a.cmp(b),
+                        };
+                        if ord != Ordering::Equal {
+                            return ord;
+                        }
+                    }
+                    self.pre_identifiers.len().cmp(&other.pre_identifiers.len())
+                }
+            })
+    }
+}
+
+impl<'a> PartialOrd for SemverVersion<'a> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// Equality ignoring build metadata.
+impl<'a> PartialEq for SemverVersion<'a> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl<'a> Eq for SemverVersion<'a> {}
+
+/// Build metadata isn't hashed, as it's ignored when comparing.
+impl<'a> Hash for SemverVersion<'a> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.major.hash(state);
+        self.minor.hash(state);
+        self.patch.hash(state);
+        self.pre.is_some().hash(state);
+        self.pre_identifiers.len().hash(state);
+        for identifier in &self.pre_identifiers {
+            match identifier {
+                Part::Number(n) => n.hash(state),
+                Part::Text(t) => t.hash(state),
+            }
+        }
+    }
+}
+
+impl<'a> fmt::Display for SemverVersion<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.version)
+    }
+}
+
+/// Parse a numeric identifier located at byte offset `at`.
+///
+/// Returns `None` if the identifier isn't all digits, and an error if it is empty or has a
+/// leading zero.
+pub(super) fn numeric(identifier: &str, at: usize) -> Result<Option<Number<'_>>, Error> {
+    if identifier.is_empty() {
+        return Err(Error::EmptyPart { at });
+    }
+    match Number::parse(identifier) {
+        Some(_) if identifier.len() > 1 && identifier.starts_with('0') => {
+            Err(Error::LeadingZero { at })
+        }
+        number => Ok(number),
+    }
+}
+
+/// Get the offset of the first character in `s` not matching `valid`.
+fn invalid_char<F: Fn(char) -> bool>(s: &str, valid: F) -> Option<usize> {
+    s.char_indices().find(|(_, c)| !valid(*c)).map(|(i, _)| i)
+}
+
+/// Validate dot separated identifiers located at byte offset `at`, and call `f` for each.
+pub(super) fn for_identifiers<'a, F>(
+    identifiers: &'a str,
+    mut at: usize,
+    mut f: F,
+) -> Result<(), Error>
+where
+    F: FnMut(&'a str, usize) -> Result<(), Error>,
+{
+    for identifier in identifiers.split('.') {
+        if identifier.is_empty() {
+            return Err(Error::EmptyPart { at });
+        }
+        if let Some(i) = invalid_char(identifier, |c| c.is_ascii_alphanumeric() || c == '-') {
+            return Err(Error::UnexpectedChar { at: at + i });
+        }
+        f(identifier, at)?;
+        at += identifier.len() + 1;
+    }
+    Ok(())
+}
+
+#[cfg_attr(tarpaulin, skip)]
+#[cfg(test)]
+mod tests {
+
+    use crate::test::hash_of;
+    use crate::{Cmp, Error};
+
+    use super::SemverVersion;
+
+    /// Valid versions, from the test suite of the regular expression suggested by semver.org.
+    const VALID: &[&str] = &[
+        "0.0.4",
+        "1.2.3",
+        "10.20.30",
+        "1.1.2-prerelease+meta",
+        "1.1.2+meta",
+        "1.1.2+meta-valid",
+        "1.0.0-alpha",
+        "1.0.0-beta",
+        "1.0.0-alpha.beta",
+        "1.0.0-alpha.beta.1",
+        "1.0.0-alpha.1",
+        "1.0.0-alpha0.valid",
+        "1.0.0-alpha.0valid",
+        "1.0.0-alpha-a.b-c-somethinglong+build.1-aef.1-its-okay",
+        "1.0.0-rc.1+build.1",
+        "2.0.0-rc.1+build.123",
+        "1.2.3-beta",
+        "10.2.3-DEV-SNAPSHOT",
+        "1.2.3-SNAPSHOT-123",
+        "2.0.0+build.1848",
+        "2.0.1-alpha.1227",
+        "1.0.0-alpha+beta",
+        "1.2.3----RC-SNAPSHOT.12.9.1--.12+788",
+        "1.2.3----R-S.12.9.1--.12+meta",
+        "1.2.3----RC-SNAPSHOT.12.9.1--.12",
+        "1.0.0+0.build.1-rc.10000aaa-kk-0.1",
+        "99999999999999999999999.999999999999999999.99999999999999999",
+        "1.0.0-0A.is.legal",
+    ];
+
+    /// Invalid versions and their error, from the same test suite.
+    const INVALID: &[(&str, Error)] = &[
+        ("1", Error::EmptyPart { at: 1 }),
+        ("1.2", Error::EmptyPart { at: 3 }),
+        ("1.2.3-0123", Error::LeadingZero { at: 6 }),
+        ("1.2.3-0123.0123", Error::LeadingZero { at: 6 }),
+        ("1.1.2+.123", Error::EmptyPart { at: 6 }),
+        ("+invalid", Error::EmptyPart { at: 0 }),
+        ("-invalid", Error::EmptyPart { at: 0 }),
+        ("-invalid+invalid", Error::EmptyPart { at: 0 }),
+        ("alpha", Error::UnexpectedChar { at: 0 }),
+        ("1.2.3.DEV", Error::UnexpectedChar { at: 5 }),
+        ("1.2-SNAPSHOT", Error::EmptyPart { at: 3 }),
+        ("01.1.1", Error::LeadingZero { at: 0 }),
+        ("1.01.1", Error::LeadingZero { at: 2 }),
+        ("1.1.01", Error::LeadingZero { at: 4 }),
+        ("1.2.3-", Error::EmptyPart { at: 6 }),
+        ("1.2.3+", Error::EmptyPart { at: 6 }),
+        ("1.2.3-a..b", Error::EmptyPart { at: 8 }),
+        ("1.2.3-rc_1", Error::UnexpectedChar { at: 8 }),
+        ("v1.2.3", Error::UnexpectedChar { at: 0 }),
+        (" 1.2.3", Error::UnexpectedChar { at: 0 }),
+        ("1.2.3 ", Error::UnexpectedChar { at: 5 }),
+    ];
+
+    /// Versions in ascending order, from section 11 of the specification.
+    const ORDERED: &[&str] = &[
+        "1.0.0-alpha",
+        "1.0.0-alpha.1",
+        "1.0.0-alpha.beta",
+        "1.0.0-beta",
+        "1.0.0-beta.2",
+        "1.0.0-beta.11",
+        "1.0.0-rc.1",
+        "1.0.0",
+        "2.0.0",
+        "2.1.0",
+        "2.1.1",
+    ];
+
+    #[test]
+    fn parse() {
+        for version in VALID {
+            assert!(SemverVersion::parse(version).is_ok(), "Parsing {}", version);
+        }
+
+        let version = SemverVersion::parse("1.0.0-alpha+beta").unwrap();
+        assert_eq!(version.pre(), Some("alpha"));
+        assert_eq!(version.build(), Some("beta"));
+        assert!(version.is_prerelease());
+
+        let version = SemverVersion::parse("1.0.0").unwrap();
+        assert_eq!(version.pre(), None);
+        assert_eq!(version.build(), None);
+        assert!(!version.is_prerelease());
+    }
+
+    #[test]
+    fn parse_error() {
+        for (version, err) in INVALID {
+            assert_eq!(
+                SemverVersion::parse(version)
+                    .map(|v| v.to_string())
+                    .as_ref(),
+                Err(err),
+                "Parsing {:?}",
+                version,
+            );
+        }
+    }
+
+    #[test]
+    fn ord() {
+        let versions: Vec<_> = ORDERED
+            .iter()
+            .map(|v| SemverVersion::parse(v).unwrap())
+            .collect();
+        for (i, a) in versions.iter().enumerate() {
+            for (j, b) in versions.iter().enumerate() {
+                assert_eq!(a.cmp(b), i.cmp(&j), "Comparing {} to {}", a, b);
+            }
+        }
+
+        // Numeric identifiers sort before alphanumeric ones, and case matters
+        let combis = [
+            ("1.0.0-alpha.1", "1.0.0-alpha.beta", Cmp::Lt),
+            ("1.0.0-1", "1.0.0-a", Cmp::Lt),
+            ("1.0.0-RC1", "1.0.0-rc1", Cmp::Lt),
+            ("1.0.0-rc.2", "1.0.0-rc.10", Cmp::Lt),
+            ("1.0.0-rc2", "1.0.0-rc10", Cmp::Gt),
+            ("1.0.0+a", "1.0.0+b", Cmp::Eq),
+            ("1.0.0-rc.1+a", "1.0.0-rc.1", Cmp::Eq),
+        ];
+        for (a, b, cmp) in &combis {
+            let (a, b) = (
+                SemverVersion::parse(a).unwrap(),
+                SemverVersion::parse(b).unwrap(),
+            );
+            assert_eq!(Cmp::from(a.cmp(&b)), *cmp, "Comparing {} to {}", a, b);
+            if *cmp == Cmp::Eq {
+                assert_eq!(hash_of(&a), hash_of(&b));
+            }
+        }
+    }
+}
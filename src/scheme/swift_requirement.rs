@@ -0,0 +1,466 @@
+//! Swift Package Manager requirement module, which provides the `SwiftRequirement` struct.
+//!
+//! SwiftPM requirements are written in `Package.swift` files, such as `from: "1.2.3"`,
+//! `.upToNextMinor(from: "1.2.3")`, `.exact("1.2.3")` or `"1.2.3"..<"1.5.0"`.
+
+use std::convert::TryFrom;
+use std::fmt;
+use std::str::FromStr;
+
+use crate::version::compare_version;
+use crate::{Cmp, Error, Part, PartBuf, SemverVersion, Version, VersionBuf};
+
+/// Swift Package Manager version requirement, like a package dependency requirement.
+///
+/// Supports `from:`, `.upToNextMajor(from:)`, `.upToNextMinor(from:)`, `exact:` and `.exact()`,
+/// and half-open `..<` and closed `...` ranges of version strings. Versions in requirements must
+/// be strict semantic versions.
+///
+/// Like SwiftPM, a pre-release version only matches if a bound is a pre-release, and never if it
+/// has the same version numbers as a release upper bound. Versions are compared like `Version`,
+/// using the manifest of the matched version if it has any.
+///
+/// # Examples
+///
+/// ```
+/// use version_compare::{SwiftRequirement, Version};
+///
+/// let req = SwiftRequirement::parse(".upToNextMinor(from: \"1.2.3\")").unwrap();
+///
+/// assert!(req.matches(&Version::from("1.2.9").unwrap()));
+/// assert!(!req.matches(&Version::from("1.3.0").unwrap()));
+/// assert!(!req.matches(&Version::from("1.2.4-beta").unwrap()));
+/// assert_eq!(req.upper().unwrap().as_str(), "1.3.0");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SwiftRequirement {
+    kind: Kind,
+    lower: VersionBuf,
+    upper: VersionBuf,
+    lower_prerelease: bool,
+    upper_prerelease: bool,
+}
+
+/// Kind of requirement, as written.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Kind {
+    /// `from:` or `.upToNextMajor(from:)`.
+    UpToNextMajor,
+
+    /// `.upToNextMinor(from:)`.
+    UpToNextMinor,
+
+    /// `exact:` or `.exact()`.
+    Exact,
+
+    /// Range of versions, a closed range having its upper bound's patch incremented.
+    Range,
+}
+
+impl SwiftRequirement {
+    /// Parse a requirement, as written in a package dependency.
+    ///
+    /// An error is returned for unsupported requirements, such as `.branch("main")`, and for
+    /// versions that aren't strict semantic versions or reversed ranges.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use version_compare::{Error, SwiftRequirement};
+    ///
+    /// assert!(SwiftRequirement::parse("from: \"1.0.0\"").is_ok());
+    /// assert!(SwiftRequirement::parse("\"1.0.0\"...\"1.2.0\"").is_ok());
+    /// assert_eq!(SwiftRequirement::parse("from: \"1.0\"").unwrap_err(), Error::EmptyPart { at: 10 });
+    /// assert_eq!(SwiftRequirement::parse(".branch(\"main\")").unwrap_err(), Error::UnexpectedChar { at: 1 });
+    /// ```
+    pub fn parse(req: &str) -> Result<Self, Error> {
+        let start = skip_whitespace(req, 0);
+
+        // Ranges of two version strings
+        if req[start..].starts_with('"') {
+            let (lower, at) = quoted(req, start)?;
+            let at = skip_whitespace(req, at);
+            let closed = if req[at..].starts_with("..<") {
+                false
+            } else if req[at..].starts_with("...") {
+                true
+            } else {
+                return Err(unexpected(req, at));
+            };
+            let upper_at = skip_whitespace(req, at + 3);
+            let (upper, end) = quoted(req, upper_at)?;
+            finish(req, end)?;
+
+            let bound = if closed {
+                let patch = upper.patch().as_u64().and_then(|p| p.checked_add(1));
+                let patch = patch.ok_or(Error::NumberOverflow { at: upper_at + 1 })?;
+                let mut bumped = format!("{}.{}.{}", upper.major(), upper.minor(), patch);
+                if let Some(pre) = upper.pre() {
+                    bumped.push('-');
+                    bumped.push_str(pre);
+                }
+                bumped
+            } else {
+                upper.as_str().to_owned()
+            };
+            let requirement = Self::new(Kind::Range, &lower, bound, upper.is_prerelease())?;
+            if requirement.upper.compare(&requirement.lower) != Cmp::Gt {
+                return Err(Error::InvalidRange { at: start });
+            }
+            return Ok(requirement);
+        }
+
+        // Labeled versions, with an optional leading `.`
+        let name_at = if req[start..].starts_with('.') {
+            start + 1
+        } else {
+            start
+        };
+        let name_len = req[name_at..]
+            .find(|c: char| !c.is_ascii_alphabetic())
+            .unwrap_or(req.len() - name_at);
+        let name = &req[name_at..name_at + name_len];
+        let at = name_at + name_len;
+        let (kind, version, end) = match name {
+            "from" => {
+                let (version, end) = quoted(req, expect(req, at, ":")?)?;
+                (Kind::UpToNextMajor, version, end)
+            }
+            "exact" if req[skip_whitespace(req, at)..].starts_with(':') => {
+                let (version, end) = quoted(req, expect(req, at, ":")?)?;
+                (Kind::Exact, version, end)
+            }
+            "exact" => {
+                let open = skip_whitespace(req, at);
+                let (version, end) = quoted(req, expect(req, at, "(")?)?;
+                (Kind::Exact, version, close(req, end, open)?)
+            }
+            "upToNextMajor" | "upToNextMinor" => {
+                let open = skip_whitespace(req, at);
+                let at = expect(req, expect(req, expect(req, at, "(")?, "from")?, ":")?;
+                let (version, end) = quoted(req, at)?;
+                let kind = if name == "upToNextMajor" {
+                    Kind::UpToNextMajor
+                } else {
+                    Kind::UpToNextMinor
+                };
+                (kind, version, close(req, end, open)?)
+            }
+            _ => return Err(unexpected(req, name_at)),
+        };
+        finish(req, end)?;
+
+        let overflow = Error::NumberOverflow { at: name_at };
+        let upper = match kind {
+            Kind::UpToNextMajor => {
+                let major = version.major().as_u64().and_then(|n| n.checked_add(1));
+                format!("{}.0.0", major.ok_or(overflow)?)
+            }
+            Kind::UpToNextMinor => {
+                let minor = version.minor().as_u64().and_then(|n| n.checked_add(1));
+                format!("{}.{}.0", version.major(), minor.ok_or(overflow)?)
+            }
+            _ => version.as_str().to_owned(),
+        };
+        let prerelease = kind == Kind::Exact && version.is_prerelease();
+        Self::new(kind, &version, upper, prerelease)
+    }
+
+    /// Create a requirement of the given kind from its bounds.
+    fn new(
+        kind: Kind,
+        lower: &SemverVersion,
+        upper: String,
+        upper_prerelease: bool,
+    ) -> Result<Self, Error> {
+        Ok(SwiftRequirement {
+            kind,
+            lower: VersionBuf::try_from(lower.as_str())?,
+            upper: VersionBuf::try_from(upper)?,
+            lower_prerelease: lower.is_prerelease(),
+            upper_prerelease,
+        })
+    }
+
+    /// Get the inclusive lower bound of this requirement, or the exact version.
+    pub fn lower(&self) -> &VersionBuf {
+        &self.lower
+    }
+
+    /// Get the exclusive upper bound of this requirement, `None` for an exact version.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use version_compare::SwiftRequirement;
+    ///
+    /// let upper = |req| SwiftRequirement::parse(req).unwrap().upper().map(|v| v.as_str().to_owned());
+    /// assert_eq!(upper("from: \"1.2.3\"").unwrap(), "2.0.0");
+    /// assert_eq!(upper("\"1.0.0\"...\"1.2.0\"").unwrap(), "1.2.1");
+    /// assert!(upper(".exact(\"1.2.3\")").is_none());
+    /// ```
+    pub fn upper(&self) -> Option<&VersionBuf> {
+        match self.kind {
+            Kind::Exact => None,
+            _ => Some(&self.upper),
+        }
+    }
+
+    /// Check whether the given `version` matches this requirement.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use version_compare::{SwiftRequirement, Version};
+    ///
+    /// let req = SwiftRequirement::parse("\"1.0.0-beta.1\"..<\"2.0.0\"").unwrap();
+    ///
+    /// assert!(req.matches(&Version::from("1.0.0-beta.2").unwrap()));
+    /// assert!(req.matches(&Version::from("1.5.0-rc.1").unwrap()));
+    /// assert!(!req.matches(&Version::from("2.0.0-alpha").unwrap()));
+    /// ```
+    pub fn matches(&self, version: &Version) -> bool {
+        let compare = |bound: &VersionBuf| {
+            compare_version(
+                (version.as_str(), version.parts().iter().copied()),
+                (bound.as_str(), bound.parts().iter().map(PartBuf::as_part)),
+                version.manifest(),
+            )
+        };
+        let lower = compare(&self.lower);
+        if self.kind == Kind::Exact || lower == Cmp::Eq {
+            return lower == Cmp::Eq;
+        }
+
+        // Pre-releases only match pre-release ranges, and not the upper bound's pre-releases
+        let mut numbers = version
+            .parts()
+            .iter()
+            .copied()
+            .skip_while(|part| !matches!(part, Part::Number(_)));
+        let prerelease = numbers.any(|part| matches!(part, Part::Text(_)));
+        if prerelease {
+            if !self.lower_prerelease && !self.upper_prerelease {
+                return false;
+            }
+            let release = version
+                .parts()
+                .iter()
+                .copied()
+                .skip_while(|part| !matches!(part, Part::Number(_)))
+                .take_while(|part| matches!(part, Part::Number(_)));
+            let upper = self.upper.parts().iter().map(PartBuf::as_part);
+            if !self.upper_prerelease
+                && compare_version(("", release), ("", upper), None) == Cmp::Eq
+            {
+                return false;
+            }
+        }
+
+        lower == Cmp::Gt && compare(&self.upper) == Cmp::Lt
+    }
+}
+
+impl FromStr for SwiftRequirement {
+    type Err = Error;
+
+    fn from_str(req: &str) -> Result<Self, Self::Err> {
+        SwiftRequirement::parse(req)
+    }
+}
+
+/// Format the requirement as written in `Package.swift`, closed ranges as half-open ranges.
+impl fmt::Display for SwiftRequirement {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.kind {
+            Kind::UpToNextMajor => write!(f, ".upToNextMajor(from: \"{}\")", self.lower),
+            Kind::UpToNextMinor => write!(f, ".upToNextMinor(from: \"{}\")", self.lower),
+            Kind::Exact => write!(f, ".exact(\"{}\")", self.lower),
+            Kind::Range => write!(f, "\"{}\"..<\"{}\"", self.lower, self.upper),
+        }
+    }
+}
+
+/// Parse the quoted semantic version at byte offset `at`, returning it with the offset after it.
+fn quoted(req: &str, at: usize) -> Result<(SemverVersion<'_>, usize), Error> {
+    if !req[at..].starts_with('"') {
+        return Err(unexpected(req, at));
+    }
+    let end = req[at + 1..]
+        .find('"')
+        .ok_or(Error::UnexpectedChar { at })?
+        + at
+        + 1;
+    let version = SemverVersion::parse(&req[at + 1..end]).map_err(|err| err.offset(at + 1))?;
+    Ok((version, end + 1))
+}
+
+/// Expect `token` after whitespace at byte offset `at`, returning the offset after it.
+fn expect(req: &str, at: usize, token: &str) -> Result<usize, Error> {
+    let at = skip_whitespace(req, at);
+    if req[at..].starts_with(token) {
+        Ok(skip_whitespace(req, at + token.len()))
+    } else {
+        Err(unexpected(req, at))
+    }
+}
+
+/// Expect a closing parenthesis after whitespace at byte offset `at`, for the one opened at byte
+/// offset `open`, returning the offset after it.
+fn close(req: &str, at: usize, open: usize) -> Result<usize, Error> {
+    let at = skip_whitespace(req, at);
+    if at == req.len() {
+        return Err(Error::UnclosedBracket { at: open });
+    }
+    expect(req, at, ")")
+}
+
+/// Check that only whitespace follows byte offset `at`.
+fn finish(req: &str, at: usize) -> Result<(), Error> {
+    let at = skip_whitespace(req, at);
+    if at < req.len() {
+        return Err(Error::UnexpectedChar { at });
+    }
+    Ok(())
+}
+
+/// Get an error for the unexpected character at byte offset `at`, or a missing version at the
+/// end of the requirement.
+fn unexpected(req: &str, at: usize) -> Error {
+    if at < req.len() {
+        Error::UnexpectedChar { at }
+    } else {
+        Error::MissingVersion { at }
+    }
+}
+
+/// Get the byte offset of the first non-whitespace character at or after `at`.
+fn skip_whitespace(req: &str, at: usize) -> usize {
+    req[at..]
+        .find(|c: char| !c.is_whitespace())
+        .map_or(req.len(), |i| i + at)
+}
+
+#[cfg_attr(tarpaulin, skip)]
+#[cfg(test)]
+mod tests {
+    use crate::{Error, Version};
+
+    use super::SwiftRequirement;
+
+    #[test]
+    fn matches() {
+        let cases: &[(&str, &[&str], &[&str])] = &[
+            (
+                "from: \"1.2.3\"",
+                &["1.2.3", "1.9.0", "1.2.3.1"],
+                &["1.2.2", "2.0.0", "1.5.0-beta"],
+            ),
+            (
+                ".upToNextMajor(from: \"0.2.0\")",
+                &["0.2.0", "0.9.9"],
+                &["1.0.0", "0.1.9"],
+            ),
+            (
+                ".upToNextMinor(from: \"1.2.3\")",
+                &["1.2.3", "1.2.10"],
+                &["1.3.0", "1.2.2"],
+            ),
+            (
+                ".exact(\"1.2.3\")",
+                &["1.2.3", "1.2.3.0"],
+                &["1.2.4", "1.2.3-rc.1"],
+            ),
+            ("exact: \"1.2.3-rc.1\"", &["1.2.3-rc.1"], &["1.2.3"]),
+            (
+                "\"1.2.3\"..<\"1.5.0\"",
+                &["1.2.3", "1.4.9"],
+                &["1.5.0", "1.2.2", "1.4.0-rc.1"],
+            ),
+            (
+                "\"1.2.3\"...\"1.5.0\"",
+                &["1.5.0"],
+                &["1.5.1", "1.5.1-beta"],
+            ),
+            (
+                "\"1.0.0-beta.1\"..<\"2.0.0\"",
+                &["1.0.0-beta.1", "1.0.0-beta.2", "1.0.0", "1.9.0-rc.1"],
+                &["1.0.0-alpha", "2.0.0-alpha", "2.0.0"],
+            ),
+            (
+                "\"1.0.0\"..<\"2.0.0-beta\"",
+                &["1.5.0", "2.0.0-alpha", "1.5.0-rc"],
+                &["2.0.0-beta", "2.0.0"],
+            ),
+            ("  from :\"1.0.0\"  ", &["1.0.0"], &["0.9.0"]),
+        ];
+        for (req, included, excluded) in cases {
+            let parsed = SwiftRequirement::parse(req).unwrap();
+            for version in *included {
+                let version = Version::from(version).unwrap();
+                assert!(parsed.matches(&version), "{} should match {}", req, version);
+            }
+            for version in *excluded {
+                let version = Version::from(version).unwrap();
+                assert!(
+                    !parsed.matches(&version),
+                    "{} should not match {}",
+                    req,
+                    version
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn parse_error() {
+        let errors = [
+            ("", Error::MissingVersion { at: 0 }),
+            ("from:", Error::MissingVersion { at: 5 }),
+            ("from \"1.0.0\"", Error::UnexpectedChar { at: 5 }),
+            ("from: \"1.0\"", Error::EmptyPart { at: 10 }),
+            ("from: \"1.0.0", Error::UnexpectedChar { at: 6 }),
+            ("from: \"1.0.0\")", Error::UnexpectedChar { at: 13 }),
+            (
+                ".upToNextMinor(from: \"1.0.0\"",
+                Error::UnclosedBracket { at: 14 },
+            ),
+            (".branch(\"main\")", Error::UnexpectedChar { at: 1 }),
+            ("\"1.0.0\"..\"2.0.0\"", Error::UnexpectedChar { at: 7 }),
+            ("\"2.0.0\"..<\"1.0.0\"", Error::InvalidRange { at: 0 }),
+            ("\"1.0.0\"..<\"1.0.0\"", Error::InvalidRange { at: 0 }),
+            ("\"v1.0.0\"..<\"2.0.0\"", Error::UnexpectedChar { at: 1 }),
+        ];
+        for (req, err) in &errors {
+            assert_eq!(
+                SwiftRequirement::parse(req).unwrap_err(),
+                *err,
+                "Parsing {:?}",
+                req,
+            );
+        }
+    }
+
+    #[test]
+    fn display() {
+        let reqs = [
+            ("from: \"1.2.3\"", ".upToNextMajor(from: \"1.2.3\")"),
+            (
+                ".upToNextMinor( from : \"1.2.3\" )",
+                ".upToNextMinor(from: \"1.2.3\")",
+            ),
+            ("exact: \"1.0.0\"", ".exact(\"1.0.0\")"),
+            ("\"1.0.0\"...\"1.2.0\"", "\"1.0.0\"..<\"1.2.1\""),
+        ];
+        for (req, display) in &reqs {
+            let parsed = SwiftRequirement::parse(req).unwrap();
+            assert_eq!(parsed.to_string(), *display);
+            assert_eq!(
+                SwiftRequirement::parse(display).unwrap(),
+                parsed,
+                "Parsing {}",
+                display
+            );
+        }
+    }
+}
@@ -1,10 +1,21 @@
-use crate::{Cmp, Manifest};
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+
+use crate::{Cmp, Manifest, Scheme};
+
+/// Hash the given value with the default hasher.
+pub fn hash_of<T: Hash>(value: &T) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    value.hash(&mut hasher);
+    hasher.finish()
+}
 
 /// A manifest configuration for GNU versions.
-const MANIFEST_GNU: Option<Manifest> = Some(Manifest {
+pub const MANIFEST_GNU: Option<Manifest> = Some(Manifest {
     gnu_ordering: true,
     max_depth: None,
     ignore_text: false,
+    scheme: Scheme::Generic,
 });
 
 /// Struct containing a version number with some meta data.
@@ -38,8 +49,10 @@ pub const VERSIONS: &[Version] = &[
     // Issue: https://github.com/timvisee/version-compare/issues/26
     Version("0.0.1-test.0222426166a", 6),
     Version("0.0.1-test.0222426166565421816516584651684351354", 5),
-    Version("0.0.1-test.02224261665a", 5),
-    Version("0.0.1-test.02224261665d7b1b689816d12f6bcacb", 5),
+    Version("0.0.1-test.02224261665a", 6),
+    Version("0.0.1-test.02224261665d7b1b689816d12f6bcacb", 6),
+    Version("20240131123045", 1),
+    Version("1.0.123456789012345678901234567890", 3),
 ];
 
 /// List of version numbers that contain errors with metadata for dynamic tests
@@ -67,7 +80,7 @@ pub struct VersionCombi(
 
 impl VersionCombi {
     /// Get versions.
-    pub fn versions(&self) -> (crate::Version, crate::Version) {
+    pub fn versions(&self) -> (crate::Version<'_>, crate::Version<'_>) {
         match self.3 {
             Some(ref manifest) => (
                 crate::Version::from_manifest(self.0, manifest).unwrap(),
@@ -150,6 +163,40 @@ pub const COMBIS: &[VersionCombi] = &[
         Cmp::Lt,
         MANIFEST_GNU,
     ),
+    // Numbers that don't fit any integer type
+    VersionCombi("1.20240131123045", "1.20240131123046", Cmp::Lt, None),
+    VersionCombi("20240131123045", "2147483648", Cmp::Gt, None),
+    VersionCombi(
+        "1.123456789012345678901234567890",
+        "1.123456789012345678901234567891",
+        Cmp::Lt,
+        None,
+    ),
+    VersionCombi(
+        "1.99999999999999999999999999999",
+        "1.100000000000000000000000000000",
+        Cmp::Lt,
+        None,
+    ),
+    VersionCombi("1.000000000000000000000000000001", "1.1", Cmp::Eq, None),
+    VersionCombi(
+        "1.18446744073709551616",
+        "1.18446744073709551615",
+        Cmp::Gt,
+        None,
+    ),
+    VersionCombi("1.0.2147483648a", "1.0.2147483648b", Cmp::Lt, None),
+    VersionCombi(
+        "v0.0.0-20191109021931-daa7c04131f5",
+        "v0.0.0-20191109021932-daa7c04131f5",
+        Cmp::Lt,
+        None,
+    ),
+    // Trailing zeros and case are insignificant
+    VersionCombi("1.2.3", "1.2.3.0.0", Cmp::Eq, None),
+    VersionCombi("1.2.ALPHA", "1.2.alpha", Cmp::Eq, None),
+    VersionCombi("1.2-ß", "1.2-SS", Cmp::Gt, None),
+    VersionCombi("1.004", "1.04", Cmp::Lt, MANIFEST_GNU),
     // TODO: inspect these cases
     VersionCombi("snapshot.1.2.3", "1.2.3.alpha", Cmp::Lt, None),
     VersionCombi("snapshot-1.2.3", "1.2.3-alpha", Cmp::Lt, None),
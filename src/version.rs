@@ -7,10 +7,9 @@
 use std::borrow::Borrow;
 use std::cmp::Ordering;
 use std::fmt;
-use std::iter::Peekable;
-use std::slice::Iter;
+use std::hash::{Hash, Hasher};
 
-use crate::{Cmp, Manifest, Part};
+use crate::{Cmp, Error, Manifest, Number, Part, PartBuf, Scheme, VersionBuf};
 
 /// Version struct, wrapping a string, providing useful comparison functions.
 ///
@@ -51,8 +50,8 @@ impl<'a> Version<'a> {
     ///
     /// assert_eq!(a.compare(b), Cmp::Lt);
     /// ```
-    pub fn from(version: &'a str) -> Option<Self> {
-        Some(Version {
+    pub fn from(version: &'a str) -> Result<Self, Error> {
+        Ok(Version {
             version,
             parts: split_version_str(version, None)?,
             manifest: None,
@@ -67,7 +66,7 @@ impl<'a> Version<'a> {
     /// ```
     /// use version_compare::{Cmp, Version, Part};
     ///
-    /// let ver = Version::from_parts("1.0", vec![Part::Number(1), Part::Number(0)]);
+    /// let ver = Version::from_parts("1.0", vec![Part::Number(1.into()), Part::Number(0.into())]);
     /// ```
     pub fn from_parts(version: &'a str, parts: Vec<Part<'a>>) -> Self {
         Version {
@@ -91,8 +90,8 @@ impl<'a> Version<'a> {
     ///
     /// assert_eq!(ver.compare(Version::from("1.2.3").unwrap()), Cmp::Eq);
     /// ```
-    pub fn from_manifest(version: &'a str, manifest: &'a Manifest) -> Option<Self> {
-        Some(Version {
+    pub fn from_manifest(version: &'a str, manifest: &'a Manifest) -> Result<Self, Error> {
+        Ok(Version {
             version,
             parts: split_version_str(version, Some(manifest))?,
             manifest: Some(manifest),
@@ -142,6 +141,9 @@ impl<'a> Version<'a> {
 
     /// Set the version manifest.
     ///
+    /// Like `VersionBuf::set_manifest`, this doesn't validate the version against the scheme of
+    /// the new manifest, invalid versions fall back to the generic ordering.
+    ///
     /// # Examples
     ///
     /// ```
@@ -174,7 +176,7 @@ impl<'a> Version<'a> {
     }
 
     /// Get a specific version part by it's `index`.
-    /// An error is returned if the given index is out of bound.
+    /// An `Error::PartOutOfRange` is returned if the given index is out of bound.
     ///
     /// # Examples
     ///
@@ -183,15 +185,18 @@ impl<'a> Version<'a> {
     ///
     /// let ver = Version::from("1.2.3").unwrap();
     ///
-    /// assert_eq!(ver.part(0), Ok(Part::Number(1)));
-    /// assert_eq!(ver.part(1), Ok(Part::Number(2)));
-    /// assert_eq!(ver.part(2), Ok(Part::Number(3)));
+    /// assert_eq!(ver.part(0), Ok(Part::Number(1.into())));
+    /// assert_eq!(ver.part(1), Ok(Part::Number(2.into())));
+    /// assert_eq!(ver.part(2), Ok(Part::Number(3.into())));
+    /// assert!(ver.part(3).is_err());
     /// ```
-    #[allow(clippy::result_unit_err)]
-    pub fn part(&self, index: usize) -> Result<Part<'a>, ()> {
+    pub fn part(&self, index: usize) -> Result<Part<'a>, Error> {
         // Make sure the index is in-bound
         if index >= self.parts.len() {
-            return Err(());
+            return Err(Error::PartOutOfRange {
+                index,
+                len: self.parts.len(),
+            });
         }
 
         Ok(self.parts[index])
@@ -207,9 +212,9 @@ impl<'a> Version<'a> {
     /// let ver = Version::from("1.2.3").unwrap();
     ///
     /// assert_eq!(ver.parts(), [
-    ///     Part::Number(1),
-    ///     Part::Number(2),
-    ///     Part::Number(3)
+    ///     Part::Number(1.into()),
+    ///     Part::Number(2.into()),
+    ///     Part::Number(3.into())
     /// ]);
     /// ```
     pub fn parts(&self) -> &[Part<'a>] {
@@ -242,9 +247,10 @@ impl<'a> Version<'a> {
     where
         V: Borrow<Version<'a>>,
     {
-        compare_iter(
-            self.parts.iter().peekable(),
-            other.borrow().parts.iter().peekable(),
+        let other = other.borrow();
+        compare_version(
+            (self.version, self.parts.iter().copied()),
+            (other.version, other.parts.iter().copied()),
             self.manifest,
         )
     }
@@ -278,6 +284,29 @@ impl<'a> Version<'a> {
             _ => unreachable!(),
         }
     }
+
+    /// Create an owned copy of this version, that doesn't borrow from the version string.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use version_compare::{Version, VersionBuf};
+    ///
+    /// let owned: VersionBuf = {
+    ///     let string = String::from("1.2.3");
+    ///     Version::from(&string).unwrap().to_owned()
+    /// };
+    ///
+    /// assert_eq!(owned.as_str(), "1.2.3");
+    /// ```
+    pub fn to_owned(&self) -> VersionBuf {
+        let mut owned = VersionBuf::from_parts(
+            self.version,
+            self.parts.iter().copied().map(PartBuf::from).collect(),
+        );
+        owned.set_manifest(self.manifest.copied());
+        owned
+    }
 }
 
 impl<'a> fmt::Display for Version<'a> {
@@ -297,10 +326,21 @@ impl<'a> fmt::Debug for Version<'a> {
     }
 }
 
+/// Implement the ordering trait for the version struct, to allow sorting and use in ordered
+/// collections.
+///
+/// Versions are compared using the manifest of `self`, so only versions sharing the same manifest
+/// should be ordered together.
+impl<'a> Ord for Version<'a> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.compare(other).ord().unwrap()
+    }
+}
+
 /// Implement the partial ordering trait for the version struct, to easily allow version comparison.
 impl<'a> PartialOrd for Version<'a> {
     fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-        Some(self.compare(other).ord().unwrap())
+        Some(self.cmp(other))
     }
 }
 
@@ -311,21 +351,40 @@ impl<'a> PartialEq for Version<'a> {
     }
 }
 
+/// Implement the hash trait for the version struct, consistent with version equality.
+///
+/// Versions that are equal have the same hash, for example `1.0` and `1.0.0`, or `1.RC` and
+/// `1.rc`.
+impl<'a> Hash for Version<'a> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        hash_version(
+            (self.version, self.parts.iter().copied()),
+            self.manifest,
+            state,
+        )
+    }
+}
+
 /// Split the given version string, in it's version parts.
-fn split_version_str<'a>(
+///
+/// An error is returned if the version string contains text parts, but no numeric part.
+pub(crate) fn split_version_str<'a>(
     version: &'a str,
     manifest: Option<&'a Manifest>,
-) -> Option<Vec<Part<'a>>> {
-    // Split the version string, and create a vector to put the parts in
-    let split = version.split(|c| !char::is_alphanumeric(c));
-    let mut parts = Vec::new();
-
+) -> Result<Vec<Part<'a>>, Error> {
     // Get the manifest to follow
     let mut used_manifest = &Manifest::default();
     if let Some(m) = manifest {
         used_manifest = m;
     }
 
+    // The version string must be valid for the version scheme
+    used_manifest.scheme.validate(version)?;
+
+    // Split the version string, and create a vector to put the parts in
+    let split = version.split(|c| !char::is_alphanumeric(c));
+    let mut parts = Vec::new();
+
     // Loop over the parts, and parse them
     for part in split {
         // We may not go over the maximum depth
@@ -339,11 +398,11 @@ fn split_version_str<'a>(
             continue;
         }
 
-        // Try to parse the value as an number
-        match part.parse::<i32>() {
-            Ok(number) => {
+        // Try to parse the value as an number, of any length
+        match Number::parse(part) {
+            Some(number) => {
                 // For GNU ordering we parse numbers with leading zero as string
-                if number > 0
+                if !number.is_zero()
                     && part.starts_with('0')
                     && manifest.map(|m| m.gnu_ordering).unwrap_or(false)
                 {
@@ -354,14 +413,13 @@ fn split_version_str<'a>(
                 // Push the number part to the vector
                 parts.push(Part::Number(number));
             }
-            Err(_) => {
+            None => {
                 // Ignore text parts if specified
                 if used_manifest.ignore_text {
                     continue;
                 }
 
-                // Numbers suffixed by text should be split into a number and text as well,
-                // if the number overflows, handle it as text
+                // Numbers suffixed by text should be split into a number and text as well
                 let split_at = part
                     .char_indices()
                     .take(part.len() - 1)
@@ -371,12 +429,8 @@ fn split_version_str<'a>(
                     .map(|(i, _, _)| i)
                     .next();
                 if let Some(at) = split_at {
-                    if let Ok(n) = part[..=at].parse() {
-                        parts.push(Part::Number(n));
-                        parts.push(Part::Text(&part[at + 1..]));
-                    } else {
-                        parts.push(Part::Text(part));
-                    }
+                    parts.push(Part::Number(Number::parse(&part[..=at]).unwrap()));
+                    parts.push(Part::Text(&part[at + 1..]));
                     continue;
                 }
 
@@ -386,13 +440,54 @@ fn split_version_str<'a>(
         }
     }
 
-    // The version must contain a number part if any part was parsed
-    if !parts.is_empty() && !parts.iter().any(|p| matches!(p, Part::Number(_))) {
-        return None;
+    // The version must contain a number part if any part was parsed, other schemes validate
+    // version strings themselves
+    if used_manifest.scheme == Scheme::Generic
+        && !parts.is_empty()
+        && !parts.iter().any(|p| matches!(p, Part::Number(_)))
+    {
+        let at = match parts[0] {
+            Part::Text(text) => text.as_ptr() as usize - version.as_ptr() as usize,
+            Part::Number(_) => unreachable!(),
+        };
+        return Err(Error::NoNumericPart { at });
     }
 
     // Return the list of parts
-    Some(parts)
+    Ok(parts)
+}
+
+/// Compare two versions, each given as version string and iterator of its version parts.
+///
+/// The version scheme of the manifest is used if it has any, the version parts are compared
+/// with `compare_iter` otherwise.
+pub(crate) fn compare_version<'a, 'b, A, B>(
+    (version, iter): (&str, A),
+    (other, other_iter): (&str, B),
+    manifest: Option<&Manifest>,
+) -> Cmp
+where
+    A: Iterator<Item = Part<'a>>,
+    B: Iterator<Item = Part<'b>>,
+{
+    manifest
+        .and_then(|m| m.scheme.compare(version, other))
+        .unwrap_or_else(|| compare_iter(iter, other_iter, manifest))
+}
+
+/// Hash a version, given as version string and iterator of its version parts, consistent with
+/// `compare_version`.
+pub(crate) fn hash_version<'a, I, H>(
+    (version, iter): (&str, I),
+    manifest: Option<&Manifest>,
+    state: &mut H,
+) where
+    I: Iterator<Item = Part<'a>>,
+    H: Hasher,
+{
+    if !manifest.map_or(false, |m| m.scheme.hash(version, state)) {
+        hash_iter(iter, manifest, state);
+    }
 }
 
 /// Compare two version numbers based on the iterators of their version parts.
@@ -404,66 +499,154 @@ fn split_version_str<'a>(
 /// * `Gt`
 ///
 /// Other comparison operators can be used when comparing, but aren't returned by this method.
-fn compare_iter<'a>(
-    mut iter: Peekable<Iter<Part<'a>>>,
-    mut other_iter: Peekable<Iter<Part<'a>>>,
-    manifest: Option<&Manifest>,
-) -> Cmp {
-    // Iterate over the iterator, without consuming it
-    for part in &mut iter {
-        match (part, other_iter.next()) {
-            // If we only have a zero on the lhs, continue
-            (Part::Number(lhs), None) if lhs == &0 => {
-                continue;
+///
+/// Parts are compared one by one, the shorter version being padded with zeros, so trailing text
+/// makes a version less. Leading text, such as an application name, is compared last and is less
+/// than anything, so `snapshot.1.2.3` is less than `1.2.3.alpha` while `app 3.2.0` is between
+/// `3.1.1` and `3.2.5`.
+///
+/// A number and text at the same position used to be skipped, which made the order
+/// inconsistent: `1.a.3` equaled both `1.2.3` and `1.5.3`. To keep the order total, text is less
+/// than a number at the same position, like it is less than the end of a version.
+fn compare_iter<'a, 'b, A, B>(iter: A, other_iter: B, manifest: Option<&Manifest>) -> Cmp
+where
+    A: Iterator<Item = Part<'a>>,
+    B: Iterator<Item = Part<'b>>,
+{
+    let gnu = manifest.map(|m| m.gnu_ordering).unwrap_or(false);
+    let mut iter = leading_last(iter, gnu).into_iter();
+    let mut other_iter = leading_last(other_iter, gnu).into_iter();
+
+    loop {
+        let (lhs, rhs) = match (iter.next(), other_iter.next()) {
+            (None, None) => return Cmp::Eq,
+            (lhs, rhs) => (
+                lhs.unwrap_or((false, Part::Number(0.into()))),
+                rhs.unwrap_or((false, Part::Number(0.into()))),
+            ),
+        };
+
+        let cmp = match (lhs, rhs) {
+            ((false, lhs), (false, rhs)) | ((true, lhs), (true, rhs)) => {
+                compare_part(&lhs, &rhs, gnu)
             }
+            ((true, _), (false, _)) => Cmp::Lt,
+            ((false, _), (true, _)) => Cmp::Gt,
+        };
+        if cmp != Cmp::Eq {
+            return cmp;
+        }
+    }
+}
 
-            // If we only have text on the lhs, it is less
-            (Part::Text(_), None) => return Cmp::Lt,
-
-            // If we have anything else on the lhs, it is greater
-            (_, None) => return Cmp::Gt,
+/// Move the leading text parts of a version after the other parts, marking them as leading.
+fn leading_last<'a, I>(iter: I, gnu: bool) -> Vec<(bool, Part<'a>)>
+where
+    I: Iterator<Item = Part<'a>>,
+{
+    let parts: Vec<_> = iter.collect();
+    let leading = parts
+        .iter()
+        .take_while(|part| !is_number(part, gnu))
+        .count();
+    parts[leading..]
+        .iter()
+        .map(|part| (false, *part))
+        .chain(parts[..leading].iter().map(|part| (true, *part)))
+        .collect()
+}
 
-            // Compare numbers
-            (Part::Number(lhs), Some(Part::Number(rhs))) => match Cmp::from(lhs.cmp(rhs)) {
-                Cmp::Eq => {}
-                cmp => return cmp,
-            },
+/// Compare two version parts.
+///
+/// Text is always less than a number, so `1.2.alpha` is less than `1.2.0`.
+fn compare_part(lhs: &Part, rhs: &Part, gnu: bool) -> Cmp {
+    match (is_number(lhs, gnu), is_number(rhs, gnu)) {
+        // For GNU ordering we have a special number/text comparison
+        (true, true) if gnu => compare_gnu_number_text(lhs, rhs).unwrap_or(Cmp::Eq),
+
+        // Compare numbers
+        (true, true) => match (lhs, rhs) {
+            (Part::Number(lhs), Part::Number(rhs)) => lhs.cmp(rhs).into(),
+            _ => unreachable!(),
+        },
 
-            // Compare text
-            (Part::Text(lhs), Some(Part::Text(rhs))) => {
-                // Normalize case and compare text: "RC1" will be less than "RC2"
-                match Cmp::from(lhs.to_lowercase().cmp(&rhs.to_lowercase())) {
-                    Cmp::Eq => {}
-                    cmp => return cmp,
-                }
-            }
+        // Normalize case and compare text: "RC1" will be less than "RC2"
+        (false, false) => match (lhs, rhs) {
+            (Part::Text(lhs), Part::Text(rhs)) => lowercase(lhs).cmp(lowercase(rhs)).into(),
+            _ => unreachable!(),
+        },
 
-            // For GNU ordering we have a special number/text comparison
-            (lhs @ Part::Number(_), Some(rhs @ Part::Text(_)))
-            | (lhs @ Part::Text(_), Some(rhs @ Part::Number(_)))
-                if manifest.map(|m| m.gnu_ordering).unwrap_or(false) =>
-            {
-                match compare_gnu_number_text(lhs, rhs) {
-                    Some(Cmp::Eq) | None => {}
-                    Some(cmp) => return cmp,
-                }
-            }
+        (false, true) => Cmp::Lt,
+        (true, false) => Cmp::Gt,
+    }
+}
 
-            // TODO: decide what to do for other type combinations
-            _ => {}
+/// Hash the given version parts, consistent with `compare_iter`.
+///
+/// Versions that compare equal hash equally: leading text is hashed last, trailing zeros are
+/// skipped, and text is hashed case-insensitively.
+fn hash_iter<'a, I, H>(iter: I, manifest: Option<&Manifest>, state: &mut H)
+where
+    I: Iterator<Item = Part<'a>>,
+    H: Hasher,
+{
+    let gnu = manifest.map(|m| m.gnu_ordering).unwrap_or(false);
+
+    // Zeros are only hashed once we know they aren't trailing
+    let mut zeros = 0;
+    for (leading, part) in leading_last(iter, gnu) {
+        if matches!(part, Part::Number(n) if n.is_zero()) {
+            zeros += 1;
+            continue;
+        }
+        for _ in 0..zeros {
+            false.hash(state);
+            hash_part(&Part::Number(0.into()), gnu, state);
         }
+        zeros = 0;
+        leading.hash(state);
+        hash_part(&part, gnu, state);
     }
+}
 
-    // Check whether we should iterate over the other iterator, if it has any items left
-    match other_iter.peek() {
-        // Compare based on the other iterator
-        Some(_) => compare_iter(other_iter, iter, manifest).flip(),
+/// Hash a single version part, consistent with `compare_part`.
+fn hash_part<H: Hasher>(part: &Part, gnu: bool, state: &mut H) {
+    match part {
+        Part::Number(n) => {
+            true.hash(state);
+            n.hash(state);
+        }
+        // Numbers with a leading zero are only equal to the same text for GNU ordering
+        Part::Text(t) if gnu && is_number(part, gnu) => {
+            true.hash(state);
+            Number::parse(t).unwrap().hash(state);
+            if t.starts_with('0') {
+                t.hash(state);
+            }
+        }
+        Part::Text(t) => {
+            false.hash(state);
+            lowercase(t).for_each(|c| c.hash(state));
+            0xffu8.hash(state);
+        }
+    }
+}
 
-        // Nothing more to iterate over, the versions should be equal
-        None => Cmp::Eq,
+/// Check whether the given part is a number.
+///
+/// For GNU ordering, numbers with a leading zero are text parts, but they still count as number.
+fn is_number(part: &Part, gnu: bool) -> bool {
+    match part {
+        Part::Number(_) => true,
+        Part::Text(t) => gnu && Number::parse(t).is_some(),
     }
 }
 
+/// Iterate over the lowercase characters of the given text, without allocating.
+fn lowercase(text: &str) -> impl Iterator<Item = char> + '_ {
+    text.chars().flat_map(char::to_lowercase)
+}
+
 /// Special logic for comparing a number and text with GNU ordering.
 ///
 /// Numbers should be ordered like this:
@@ -476,11 +659,11 @@ fn compare_gnu_number_text(lhs: &Part, rhs: &Part) -> Option<Cmp> {
     // Both values must be parsable as numbers
     let lhs_num = match lhs {
         Part::Number(n) => *n,
-        Part::Text(n) => n.parse().ok()?,
+        Part::Text(n) => Number::parse(n)?,
     };
     let rhs_num = match rhs {
         Part::Number(n) => *n,
-        Part::Text(n) => n.parse().ok()?,
+        Part::Text(n) => Number::parse(n)?,
     };
 
     // Return ordering if numeric values are different
@@ -512,9 +695,10 @@ fn compare_gnu_number_text(lhs: &Part, rhs: &Part) -> Option<Cmp> {
 #[cfg(test)]
 mod tests {
     use std::cmp;
+    use std::collections::{BTreeSet, HashSet};
 
-    use crate::test::{COMBIS, VERSIONS, VERSIONS_ERROR};
-    use crate::{Cmp, Manifest, Part};
+    use crate::test::{hash_of, COMBIS, VERSIONS, VERSIONS_ERROR};
+    use crate::{Cmp, Error, Manifest, Part};
 
     use super::Version;
 
@@ -523,15 +707,27 @@ mod tests {
     fn from() {
         // Test whether parsing works for each test version
         for version in VERSIONS {
-            assert!(Version::from(version.0).is_some());
+            assert!(Version::from(version.0).is_ok());
         }
 
         // Test whether parsing works for each test invalid version
         for version in VERSIONS_ERROR {
-            assert!(Version::from(version.0).is_none());
+            assert!(Version::from(version.0).is_err());
         }
     }
 
+    #[test]
+    fn from_error() {
+        assert_eq!(
+            Version::from("abc").unwrap_err(),
+            Error::NoNumericPart { at: 0 },
+        );
+        assert_eq!(
+            Version::from("  -alpha.dev").unwrap_err(),
+            Error::NoNumericPart { at: 3 },
+        );
+    }
+
     #[test]
     // TODO: This doesn't really test whether this method fully works
     fn from_manifest() {
@@ -550,7 +746,7 @@ mod tests {
 
         // Test whether parsing works for each test invalid version
         for version in VERSIONS_ERROR {
-            assert!(Version::from_manifest(version.0, &manifest).is_none());
+            assert!(Version::from_manifest(version.0, &manifest).is_err());
         }
     }
 
@@ -612,7 +808,13 @@ mod tests {
             }
 
             // A value outside the range must return an error
-            assert!(ver.part(version.1).is_err());
+            assert_eq!(
+                ver.part(version.1),
+                Err(Error::PartOutOfRange {
+                    index: version.1,
+                    len: version.1,
+                }),
+            );
         }
     }
 
@@ -640,10 +842,10 @@ mod tests {
                 // Create a version object, and count it's parts
                 let ver = Version::from_manifest(version.0, &manifest);
 
-                // Some versions might be none, because not all of the start with a number when the
-                // maximum depth is 1. A version string with only text isn't allowed,
-                // resulting in none.
-                if ver.is_none() {
+                // Some versions might be an error, because not all of the start with a number when
+                // the maximum depth is 1. A version string with only text isn't allowed,
+                // resulting in an error.
+                if ver.is_err() {
                     continue;
                 }
 
@@ -791,4 +993,116 @@ mod tests {
         // Assert an exceptional case, compare to not equal
         assert!(Version::from("1.2").unwrap() != Version::from("1.2.3").unwrap());
     }
+
+    /// Get all versions in the version combination set, along with their manifest.
+    fn combi_versions() -> Vec<(&'static str, Option<Manifest>)> {
+        COMBIS
+            .iter()
+            .flat_map(|c| vec![(c.0, c.3), (c.1, c.3)])
+            .collect()
+    }
+
+    #[test]
+    fn hash() {
+        // Compare every version in the version set to every other version with the same manifest,
+        // equal versions must have an equal hash
+        let versions = combi_versions();
+        for (a, manifest) in &versions {
+            for (b, _) in versions.iter().filter(|(_, m)| m == manifest) {
+                let (a, b) = match manifest {
+                    Some(m) => (
+                        Version::from_manifest(a, m).unwrap(),
+                        Version::from_manifest(b, m).unwrap(),
+                    ),
+                    None => (Version::from(a).unwrap(), Version::from(b).unwrap()),
+                };
+
+                if a == b {
+                    assert_eq!(
+                        hash_of(&a),
+                        hash_of(&b),
+                        "Testing that {} and {} have the same hash",
+                        a,
+                        b,
+                    );
+                }
+            }
+        }
+
+        // Assert some exceptional cases that must hash differently
+        let hash = |v| hash_of(&Version::from(v).unwrap());
+        assert_ne!(hash("1.2"), hash("1.2.3"));
+        assert_ne!(hash("1.0.2"), hash("1.2"));
+        assert_ne!(hash("1.ab"), hash("1.a.b"));
+    }
+
+    #[test]
+    fn ord() {
+        // Sort all versions, and versions per manifest. Every version must be less or equal than
+        // all versions sorted after it, which proves the ordering is total
+        let versions = combi_versions();
+        for manifest in &[None, crate::test::MANIFEST_GNU] {
+            let mut sorted: Vec<Version> = versions
+                .iter()
+                .filter(|(_, m)| m == manifest || manifest.is_none())
+                .map(|(v, _)| match manifest {
+                    Some(m) => Version::from_manifest(v, m).unwrap(),
+                    None => Version::from(v).unwrap(),
+                })
+                .collect();
+            sorted.sort();
+
+            for (i, a) in sorted.iter().enumerate() {
+                for b in &sorted[i..] {
+                    assert!(a <= b, "Testing that {} <= {}", a, b);
+                    assert_eq!(a.cmp(b), b.cmp(a).reverse());
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn compare_number_text() {
+        // Text is less than a number at the same position, these pairs used to be skipped
+        let combis = [
+            ("1.a.3", "1.2.3", Cmp::Lt, None),
+            ("1.a.9", "1.2.3", Cmp::Lt, None),
+            ("1.2.alpha", "1.2.0", Cmp::Lt, None),
+            ("1.2.3.alpha", "1.2.3.0.beta", Cmp::Lt, None),
+            ("1.04", "1.abc", Cmp::Gt, crate::test::MANIFEST_GNU),
+            ("1.04", "1", Cmp::Gt, crate::test::MANIFEST_GNU),
+            // Leading text is compared last, and is less than anything
+            ("MyApp 1.2", "1.2.0", Cmp::Lt, None),
+            ("MyApp 1.2", "1.1.9", Cmp::Gt, None),
+            ("MyApp 1.2", "myapp 1.2", Cmp::Eq, None),
+            ("MyApp 1.2", "Other 1.2", Cmp::Lt, None),
+            ("MyApp 1.2", "1.2.alpha", Cmp::Lt, None),
+        ];
+        for (a, b, cmp, manifest) in &combis {
+            let (a, b) = match manifest {
+                Some(m) => (
+                    Version::from_manifest(a, m).unwrap(),
+                    Version::from_manifest(b, m).unwrap(),
+                ),
+                None => (Version::from(a).unwrap(), Version::from(b).unwrap()),
+            };
+            assert_eq!(a.compare(&b), *cmp, "Comparing {} to {}", a, b);
+            assert_eq!(b.compare(&a), cmp.flip(), "Comparing {} to {}", b, a);
+        }
+    }
+
+    #[test]
+    fn collections() {
+        let versions = ["1.0", "1.0.0", "1", "1.2", "1.2.RC", "1.2.rc", "0.9"];
+        let versions: Vec<Version> = versions.iter().map(|v| Version::from(v).unwrap()).collect();
+
+        let set: HashSet<&Version> = versions.iter().collect();
+        assert_eq!(set.len(), 4);
+
+        let set: BTreeSet<&Version> = versions.iter().collect();
+        assert_eq!(set.len(), 4);
+        assert_eq!(set.iter().next().unwrap().as_str(), "0.9");
+
+        assert_eq!(versions.iter().max().unwrap().as_str(), "1.2");
+    }
 }
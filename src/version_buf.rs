@@ -0,0 +1,520 @@
+//! Owned version module, which provides the `VersionBuf` struct.
+//!
+//! A `VersionBuf` is the owned counterpart of `Version`. It owns its version string, parts and
+//! manifest, so it can be stored in long-lived structures or sent across threads without keeping
+//! the original version string alive.
+
+use std::borrow::Borrow;
+use std::cmp::Ordering;
+use std::convert::TryFrom;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::str::FromStr;
+
+use crate::version::{compare_version, hash_version, split_version_str};
+use crate::{Cmp, Error, Manifest, Part, PartBuf, Version};
+
+/// Owned version struct, providing useful comparison functions.
+///
+/// This is the owned counterpart of `Version`, which doesn't borrow from the version string or
+/// manifest. Use `version.to_owned()` to get a `VersionBuf` from a `Version`, and
+/// `version.as_version()` to borrow a `VersionBuf` as `Version`.
+///
+/// # Examples
+///
+/// ```
+/// use std::convert::TryFrom;
+/// use version_compare::{Cmp, VersionBuf};
+///
+/// let a = VersionBuf::try_from(String::from("1.2.3")).unwrap();
+/// let b = VersionBuf::try_from("1.3").unwrap();
+///
+/// assert_eq!(a.compare(&b), Cmp::Lt);
+/// ```
+#[derive(Clone, Eq)]
+pub struct VersionBuf {
+    version: String,
+    parts: Vec<PartBuf>,
+    manifest: Option<Manifest>,
+}
+
+impl VersionBuf {
+    /// Create a `VersionBuf` instance from already existing parts
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use version_compare::{VersionBuf, PartBuf};
+    ///
+    /// let ver = VersionBuf::from_parts("1.0", vec![PartBuf::Number(1.into()), PartBuf::Number(0.into())]);
+    /// ```
+    pub fn from_parts<S: Into<String>>(version: S, parts: Vec<PartBuf>) -> Self {
+        VersionBuf {
+            version: version.into(),
+            parts,
+            manifest: None,
+        }
+    }
+
+    /// Create a `VersionBuf` instance from a version string with the given `manifest`.
+    ///
+    /// The version string should be passed to the `version` parameter. The manifest is copied
+    /// into the version.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::convert::TryFrom;
+    /// use version_compare::{Cmp, Manifest, VersionBuf};
+    ///
+    /// let ver = VersionBuf::from_manifest("1.2.3", &Manifest::default()).unwrap();
+    ///
+    /// assert_eq!(ver.compare(VersionBuf::try_from("1.2.3").unwrap()), Cmp::Eq);
+    /// ```
+    pub fn from_manifest<S: Into<String>>(version: S, manifest: &Manifest) -> Result<Self, Error> {
+        let version = version.into();
+        let parts = split_version_str(&version, Some(manifest))?
+            .into_iter()
+            .map(PartBuf::from)
+            .collect();
+        Ok(VersionBuf {
+            version,
+            parts,
+            manifest: Some(*manifest),
+        })
+    }
+
+    /// Get the version manifest, if available.
+    pub fn manifest(&self) -> Option<&Manifest> {
+        self.manifest.as_ref()
+    }
+
+    /// Check whether this version has a manifest.
+    pub fn has_manifest(&self) -> bool {
+        self.manifest.is_some()
+    }
+
+    /// Set the version manifest.
+    ///
+    /// The version string isn't parsed again, so it isn't validated against the scheme of the new
+    /// manifest. A version that isn't valid for that scheme is compared with the generic ordering,
+    /// use `from_manifest` instead to reject it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::convert::TryFrom;
+    /// use version_compare::{Cmp, Manifest, Scheme, VersionBuf};
+    ///
+    /// let mut manifest = Manifest::default();
+    /// manifest.scheme = Scheme::Semver;
+    ///
+    /// let mut version = VersionBuf::try_from("1.2").unwrap();
+    /// version.set_manifest(Some(manifest));
+    ///
+    /// // Not a semantic version, so compared with the generic ordering
+    /// assert_eq!(version.compare(VersionBuf::try_from("1.2.0").unwrap()), Cmp::Eq);
+    /// assert!(VersionBuf::from_manifest("1.2", &manifest).is_err());
+    /// ```
+    pub fn set_manifest(&mut self, manifest: Option<Manifest>) {
+        self.manifest = manifest;
+    }
+
+    /// Get the original version string.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::convert::TryFrom;
+    /// use version_compare::VersionBuf;
+    ///
+    /// let ver = VersionBuf::try_from("1.2.3").unwrap();
+    ///
+    /// assert_eq!(ver.as_str(), "1.2.3");
+    /// ```
+    pub fn as_str(&self) -> &str {
+        &self.version
+    }
+
+    /// Get a specific version part by it's `index`.
+    /// An `Error::PartOutOfRange` is returned if the given index is out of bound.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::convert::TryFrom;
+    /// use version_compare::{Part, VersionBuf};
+    ///
+    /// let ver = VersionBuf::try_from("1.2.3").unwrap();
+    ///
+    /// assert_eq!(ver.part(0), Ok(Part::Number(1.into())));
+    /// assert_eq!(ver.part(2), Ok(Part::Number(3.into())));
+    /// ```
+    pub fn part(&self, index: usize) -> Result<Part<'_>, Error> {
+        self.parts
+            .get(index)
+            .map(PartBuf::as_part)
+            .ok_or(Error::PartOutOfRange {
+                index,
+                len: self.parts.len(),
+            })
+    }
+
+    /// Get a vector of all version parts.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::convert::TryFrom;
+    /// use version_compare::{PartBuf, VersionBuf};
+    ///
+    /// let ver = VersionBuf::try_from("1.2").unwrap();
+    ///
+    /// assert_eq!(ver.parts(), [PartBuf::Number(1.into()), PartBuf::Number(2.into())]);
+    /// ```
+    pub fn parts(&self) -> &[PartBuf] {
+        self.parts.as_slice()
+    }
+
+    /// Borrow this version as `Version`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::convert::TryFrom;
+    /// use version_compare::{Version, VersionBuf};
+    ///
+    /// let ver = VersionBuf::try_from("1.2.3").unwrap();
+    ///
+    /// assert_eq!(ver.as_version(), Version::from("1.2.3").unwrap());
+    /// ```
+    pub fn as_version(&self) -> Version<'_> {
+        let mut version = Version::from_parts(
+            &self.version,
+            self.parts.iter().map(PartBuf::as_part).collect(),
+        );
+        version.set_manifest(self.manifest.as_ref());
+        version
+    }
+
+    /// Compare this version to the given `other` version.
+    ///
+    /// This behaves exactly like `Version::compare`, and returns one of the following comparison
+    /// operators:
+    ///
+    /// * `Lt`
+    /// * `Eq`
+    /// * `Gt`
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// use std::convert::TryFrom;
+    /// use version_compare::{Cmp, VersionBuf};
+    ///
+    /// let a = VersionBuf::try_from("1.2").unwrap();
+    /// let b = VersionBuf::try_from("1.3.2").unwrap();
+    ///
+    /// assert_eq!(a.compare(&b), Cmp::Lt);
+    /// assert_eq!(b.compare(&a), Cmp::Gt);
+    /// assert_eq!(a.compare(&a), Cmp::Eq);
+    /// ```
+    pub fn compare<V>(&self, other: V) -> Cmp
+    where
+        V: Borrow<VersionBuf>,
+    {
+        let other = other.borrow();
+        compare_version(
+            (&self.version, self.parts.iter().map(PartBuf::as_part)),
+            (&other.version, other.parts.iter().map(PartBuf::as_part)),
+            self.manifest.as_ref(),
+        )
+    }
+
+    /// Compare this version to the given `other` version,
+    /// and check whether the given comparison operator is valid.
+    ///
+    /// All comparison operators can be used.
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// use std::convert::TryFrom;
+    /// use version_compare::{Cmp, VersionBuf};
+    ///
+    /// let a = VersionBuf::try_from("1.2").unwrap();
+    /// let b = VersionBuf::try_from("1.3.2").unwrap();
+    ///
+    /// assert!(a.compare_to(&b, Cmp::Lt));
+    /// assert!(a.compare_to(&a, Cmp::Le));
+    /// ```
+    pub fn compare_to<V>(&self, other: V, operator: Cmp) -> bool
+    where
+        V: Borrow<VersionBuf>,
+    {
+        match self.compare(other) {
+            Cmp::Eq => matches!(operator, Cmp::Eq | Cmp::Le | Cmp::Ge),
+            Cmp::Lt => matches!(operator, Cmp::Ne | Cmp::Lt | Cmp::Le),
+            Cmp::Gt => matches!(operator, Cmp::Ne | Cmp::Gt | Cmp::Ge),
+            _ => unreachable!(),
+        }
+    }
+}
+
+impl<'a> From<Version<'a>> for VersionBuf {
+    fn from(version: Version<'a>) -> Self {
+        version.to_owned()
+    }
+}
+
+/// Create a `VersionBuf` instance from a version string.
+///
+/// # Examples
+///
+/// ```
+/// use std::convert::TryFrom;
+/// use version_compare::{Cmp, VersionBuf};
+///
+/// let a = VersionBuf::try_from(String::from("1.2.3")).unwrap();
+/// let b: VersionBuf = "1.3.0".parse().unwrap();
+///
+/// assert_eq!(a.compare(b), Cmp::Lt);
+/// assert!(VersionBuf::try_from("dev").is_err());
+/// ```
+impl TryFrom<String> for VersionBuf {
+    type Error = Error;
+
+    fn try_from(version: String) -> Result<Self, Self::Error> {
+        let parts = split_version_str(&version, None)?
+            .into_iter()
+            .map(PartBuf::from)
+            .collect();
+        Ok(VersionBuf {
+            version,
+            parts,
+            manifest: None,
+        })
+    }
+}
+
+impl<'a> TryFrom<&'a str> for VersionBuf {
+    type Error = Error;
+
+    fn try_from(version: &'a str) -> Result<Self, Self::Error> {
+        VersionBuf::try_from(version.to_string())
+    }
+}
+
+impl FromStr for VersionBuf {
+    type Err = Error;
+
+    fn from_str(version: &str) -> Result<Self, Self::Err> {
+        VersionBuf::try_from(version)
+    }
+}
+
+impl fmt::Display for VersionBuf {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.version)
+    }
+}
+
+// Show just the version component parts as debug output
+impl fmt::Debug for VersionBuf {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if f.alternate() {
+            write!(f, "{:#?}", self.parts)
+        } else {
+            write!(f, "{:?}", self.parts)
+        }
+    }
+}
+
+/// Implement the ordering trait for the version struct, to allow sorting and use in ordered
+/// collections.
+impl Ord for VersionBuf {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.compare(other).ord().unwrap()
+    }
+}
+
+/// Implement the partial ordering trait for the version struct, to easily allow version comparison.
+impl PartialOrd for VersionBuf {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// Implement the partial equality trait for the version struct, to easily allow version comparison.
+impl PartialEq for VersionBuf {
+    fn eq(&self, other: &Self) -> bool {
+        self.compare_to(other, Cmp::Eq)
+    }
+}
+
+/// Implement the hash trait for the version struct, consistent with version equality.
+impl Hash for VersionBuf {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        hash_version(
+            (&self.version, self.parts.iter().map(PartBuf::as_part)),
+            self.manifest.as_ref(),
+            state,
+        )
+    }
+}
+
+#[cfg_attr(tarpaulin, skip)]
+#[cfg(test)]
+mod tests {
+    use std::convert::TryFrom;
+
+    use crate::test::{hash_of, COMBIS, VERSIONS, VERSIONS_ERROR};
+    use crate::{Cmp, Manifest, PartBuf, Version};
+
+    use super::VersionBuf;
+
+    #[test]
+    fn from() {
+        // Test whether parsing works for each test version
+        for version in VERSIONS {
+            assert!(VersionBuf::try_from(version.0).is_ok());
+            assert!(VersionBuf::try_from(version.0.to_string()).is_ok());
+            assert!(version.0.parse::<VersionBuf>().is_ok());
+        }
+
+        // Test whether parsing works for each test invalid version
+        for version in VERSIONS_ERROR {
+            assert!(VersionBuf::try_from(version.0).is_err());
+            assert!(version.0.parse::<VersionBuf>().is_err());
+        }
+    }
+
+    #[test]
+    fn from_manifest() {
+        let manifest = Manifest::default();
+
+        for version in VERSIONS {
+            assert_eq!(
+                VersionBuf::from_manifest(version.0, &manifest)
+                    .unwrap()
+                    .manifest(),
+                Some(&manifest)
+            );
+        }
+
+        for version in VERSIONS_ERROR {
+            assert!(VersionBuf::from_manifest(version.0, &manifest).is_err());
+        }
+    }
+
+    #[test]
+    fn set_manifest() {
+        let mut version = VersionBuf::try_from("1.2.3").unwrap();
+
+        version.set_manifest(Some(Manifest::default()));
+        assert!(version.has_manifest());
+
+        version.set_manifest(None);
+        assert!(!version.has_manifest());
+    }
+
+    #[test]
+    fn parts() {
+        for version in VERSIONS {
+            let owned = VersionBuf::try_from(version.0).unwrap();
+            assert_eq!(owned.as_str(), version.0);
+            assert_eq!(owned.parts().len(), version.1);
+
+            for i in 0..version.1 {
+                assert_eq!(owned.part(i), Ok(owned.parts()[i].as_part()));
+            }
+            assert!(owned.part(version.1).is_err());
+        }
+    }
+
+    #[test]
+    fn to_owned_as_version() {
+        for version in VERSIONS {
+            let borrowed = Version::from(version.0).unwrap();
+            let owned = borrowed.to_owned();
+
+            assert_eq!(owned.as_str(), borrowed.as_str());
+            assert_eq!(owned.as_version().parts(), borrowed.parts());
+            assert_eq!(owned.parts().len(), borrowed.parts().len());
+        }
+
+        // The manifest must be carried over
+        let manifest = Manifest::default();
+        let owned = Version::from_manifest("1.2", &manifest).unwrap().to_owned();
+        assert_eq!(owned.manifest(), Some(&manifest));
+        assert_eq!(owned.as_version().manifest(), Some(&manifest));
+    }
+
+    #[test]
+    fn compare() {
+        // Owned versions must compare exactly like borrowed versions
+        for entry in COMBIS {
+            let (a, b) = entry.versions();
+            let (a, b) = (a.to_owned(), b.to_owned());
+
+            assert_eq!(
+                a.compare(&b),
+                entry.2,
+                "Testing that {} is {} {}",
+                entry.0,
+                entry.2.sign(),
+                entry.1,
+            );
+            assert!(a.compare_to(&b, entry.2));
+            assert!(!a.compare_to(&b, entry.2.invert()));
+
+            match entry.2 {
+                Cmp::Eq => assert!(a == b),
+                Cmp::Lt => assert!(a < b),
+                Cmp::Gt => assert!(a > b),
+                _ => {}
+            }
+        }
+    }
+
+    #[test]
+    fn hash() {
+        // Owned versions must hash exactly like borrowed versions
+        for entry in COMBIS {
+            let (a, b) = entry.versions();
+            assert_eq!(hash_of(&a), hash_of(&a.to_owned()));
+            assert_eq!(hash_of(&b), hash_of(&b.to_owned()));
+        }
+    }
+
+    #[test]
+    fn from_parts() {
+        let ver = VersionBuf::from_parts(
+            "1.0",
+            vec![PartBuf::Number(1.into()), PartBuf::Number(0.into())],
+        );
+        assert_eq!(ver, VersionBuf::try_from("1").unwrap());
+    }
+
+    #[test]
+    fn display() {
+        assert_eq!(
+            format!("{}", VersionBuf::try_from("1.2.3").unwrap()),
+            "1.2.3"
+        );
+    }
+
+    #[test]
+    fn debug() {
+        assert_eq!(
+            format!("{:?}", VersionBuf::try_from("1.2.dev").unwrap()),
+            format!("{:?}", Version::from("1.2.dev").unwrap()),
+        );
+    }
+
+    #[test]
+    fn send_sync_static() {
+        fn assert_bounds<T: Send + Sync + 'static>() {}
+        assert_bounds::<VersionBuf>();
+    }
+}
@@ -0,0 +1,506 @@
+//! Version range module, which provides the `VersionRange` struct.
+//!
+//! A version range is a union of disjoint intervals of versions. Each interval has a lower and
+//! upper bound, which may be inclusive, exclusive or unbounded. Ranges support set operations, such
+//! as intersection, union and complement.
+
+use std::cmp::Ordering;
+use std::ops::Bound;
+
+use crate::version::compare_version;
+use crate::{Cmp, PartBuf, Version, VersionBuf, VersionReq};
+
+/// Version range, a union of disjoint version intervals.
+///
+/// Intervals are kept sorted, and overlapping or adjacent intervals are merged, so two ranges
+/// covering the same versions are equal.
+///
+/// # Examples
+///
+/// ```
+/// use std::convert::TryFrom;
+/// use version_compare::{Cmp, VersionBuf, VersionRange};
+///
+/// // Vulnerable from 1.0 up to 1.4.2, and from 2.0 up to 2.1.1
+/// let vulnerable = VersionRange::from_req(&">= 1.0, < 1.4.2".parse().unwrap())
+///     .union(&VersionRange::from_req(&">= 2.0, < 2.1.1".parse().unwrap()));
+///
+/// // Patched releases cover everything from 1.4.2
+/// let patched = VersionRange::from_cmp(Cmp::Ge, VersionBuf::try_from("1.4.2").unwrap());
+///
+/// // The patched releases don't cover the vulnerable 2.0 releases
+/// assert!(!patched.is_subset_of(&vulnerable.complement()));
+/// assert!(!vulnerable.intersect(&patched).is_empty());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VersionRange {
+    intervals: Vec<(Bound<VersionBuf>, Bound<VersionBuf>)>,
+}
+
+impl VersionRange {
+    /// Create a range with a single interval between the `lower` and `upper` bound.
+    ///
+    /// The range is empty if the lower bound is above the upper bound.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::convert::TryFrom;
+    /// use std::ops::Bound;
+    /// use version_compare::{Version, VersionBuf, VersionRange};
+    ///
+    /// let range = VersionRange::new(
+    ///     Bound::Included(VersionBuf::try_from("1.0").unwrap()),
+    ///     Bound::Excluded(VersionBuf::try_from("2.0").unwrap()),
+    /// );
+    ///
+    /// assert!(range.contains(&Version::from("1.0.0").unwrap()));
+    /// assert!(!range.contains(&Version::from("2").unwrap()));
+    /// ```
+    pub fn new(lower: Bound<VersionBuf>, upper: Bound<VersionBuf>) -> Self {
+        VersionRange::from_intervals(vec![(lower, upper)])
+    }
+
+    /// Create a range containing all versions.
+    pub fn full() -> Self {
+        VersionRange::new(Bound::Unbounded, Bound::Unbounded)
+    }
+
+    /// Create a range containing no versions.
+    pub fn empty() -> Self {
+        VersionRange {
+            intervals: Vec::new(),
+        }
+    }
+
+    /// Create a range of all versions matching the given comparison `operator` against `version`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::convert::TryFrom;
+    /// use version_compare::{Cmp, Version, VersionBuf, VersionRange};
+    ///
+    /// let range = VersionRange::from_cmp(Cmp::Ne, VersionBuf::try_from("1.2").unwrap());
+    ///
+    /// assert!(range.contains(&Version::from("1.1").unwrap()));
+    /// assert!(!range.contains(&Version::from("1.2.0").unwrap()));
+    /// ```
+    pub fn from_cmp(operator: Cmp, version: VersionBuf) -> Self {
+        match operator {
+            Cmp::Eq => {
+                VersionRange::new(Bound::Included(version.clone()), Bound::Included(version))
+            }
+            Cmp::Ne => VersionRange::from_cmp(Cmp::Eq, version).complement(),
+            Cmp::Lt => VersionRange::new(Bound::Unbounded, Bound::Excluded(version)),
+            Cmp::Le => VersionRange::new(Bound::Unbounded, Bound::Included(version)),
+            Cmp::Ge => VersionRange::new(Bound::Included(version), Bound::Unbounded),
+            Cmp::Gt => VersionRange::new(Bound::Excluded(version), Bound::Unbounded),
+        }
+    }
+
+    /// Create a range of all versions matching the given requirement.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use version_compare::{Version, VersionRange};
+    ///
+    /// let range = VersionRange::from_req(&">= 1.2, < 2.0".parse().unwrap());
+    ///
+    /// assert!(range.contains(&Version::from("1.5").unwrap()));
+    /// assert!(!range.contains(&Version::from("2.0").unwrap()));
+    /// ```
+    pub fn from_req(req: &VersionReq) -> Self {
+        req.clauses()
+            .iter()
+            .fold(VersionRange::full(), |range, (operator, version)| {
+                range.intersect(&VersionRange::from_cmp(*operator, version.clone()))
+            })
+    }
+
+    /// Get the disjoint intervals of this range, sorted from low to high.
+    pub fn intervals(&self) -> &[(Bound<VersionBuf>, Bound<VersionBuf>)] {
+        &self.intervals
+    }
+
+    /// Check whether this range contains no versions.
+    pub fn is_empty(&self) -> bool {
+        self.intervals.is_empty()
+    }
+
+    /// Check whether this range contains the given `version`.
+    ///
+    /// The version is compared using its own manifest, if it has any.
+    pub fn contains(&self, version: &Version) -> bool {
+        let cmp = |other: &VersionBuf| {
+            compare_version(
+                (version.as_str(), version.parts().iter().copied()),
+                (other.as_str(), other.parts().iter().map(PartBuf::as_part)),
+                version.manifest(),
+            )
+        };
+
+        self.intervals.iter().any(|(lower, upper)| {
+            let above = match lower {
+                Bound::Included(v) => cmp(v) != Cmp::Lt,
+                Bound::Excluded(v) => cmp(v) == Cmp::Gt,
+                Bound::Unbounded => true,
+            };
+            let below = match upper {
+                Bound::Included(v) => cmp(v) != Cmp::Gt,
+                Bound::Excluded(v) => cmp(v) == Cmp::Lt,
+                Bound::Unbounded => true,
+            };
+            above && below
+        })
+    }
+
+    /// Get the intersection of this and the `other` range.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use version_compare::VersionRange;
+    ///
+    /// let a = VersionRange::from_req(&">= 1.0, < 2.0".parse().unwrap());
+    /// let b = VersionRange::from_req(&">= 1.5".parse().unwrap());
+    ///
+    /// assert_eq!(a.intersect(&b), VersionRange::from_req(&">= 1.5, < 2.0".parse().unwrap()));
+    /// ```
+    #[must_use]
+    pub fn intersect(&self, other: &VersionRange) -> Self {
+        let mut intervals = Vec::new();
+        for (lower, upper) in &self.intervals {
+            for (other_lower, other_upper) in &other.intervals {
+                let lower = match cmp_lower(lower, other_lower) {
+                    Ordering::Less => other_lower,
+                    _ => lower,
+                };
+                let upper = match cmp_upper(upper, other_upper) {
+                    Ordering::Greater => other_upper,
+                    _ => upper,
+                };
+                intervals.push((lower.clone(), upper.clone()));
+            }
+        }
+        VersionRange::from_intervals(intervals)
+    }
+
+    /// Get the union of this and the `other` range.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use version_compare::VersionRange;
+    ///
+    /// let a = VersionRange::from_req(&">= 1.0, < 2.0".parse().unwrap());
+    /// let b = VersionRange::from_req(&">= 2.0, < 3.0".parse().unwrap());
+    ///
+    /// assert_eq!(a.union(&b), VersionRange::from_req(&">= 1.0, < 3.0".parse().unwrap()));
+    /// ```
+    #[must_use]
+    pub fn union(&self, other: &VersionRange) -> Self {
+        VersionRange::from_intervals(
+            self.intervals
+                .iter()
+                .chain(other.intervals.iter())
+                .cloned()
+                .collect(),
+        )
+    }
+
+    /// Get the complement of this range, containing all versions not in this range.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use version_compare::VersionRange;
+    ///
+    /// let range = VersionRange::from_req(&">= 1.0, < 2.0".parse().unwrap());
+    ///
+    /// assert_eq!(
+    ///     range.complement(),
+    ///     VersionRange::from_req(&"< 1.0".parse().unwrap())
+    ///         .union(&VersionRange::from_req(&">= 2.0".parse().unwrap())),
+    /// );
+    /// ```
+    #[must_use]
+    pub fn complement(&self) -> Self {
+        let mut intervals = Vec::new();
+        let mut lower = Bound::Unbounded;
+        for (start, end) in &self.intervals {
+            // The gap before this interval ends where the interval starts
+            let upper = match start {
+                Bound::Included(v) => Bound::Excluded(v.clone()),
+                Bound::Excluded(v) => Bound::Included(v.clone()),
+                Bound::Unbounded => Bound::Unbounded,
+            };
+            if upper != Bound::Unbounded {
+                intervals.push((lower, upper));
+            }
+
+            // The next gap starts where this interval ends
+            lower = match end {
+                Bound::Included(v) => Bound::Excluded(v.clone()),
+                Bound::Excluded(v) => Bound::Included(v.clone()),
+                Bound::Unbounded => return VersionRange::from_intervals(intervals),
+            };
+        }
+        intervals.push((lower, Bound::Unbounded));
+        VersionRange::from_intervals(intervals)
+    }
+
+    /// Check whether all versions in this range are also in the `other` range.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use version_compare::VersionRange;
+    ///
+    /// let patched = VersionRange::from_req(&">= 1.4.2".parse().unwrap());
+    /// let safe = VersionRange::from_req(&">= 1.4".parse().unwrap());
+    ///
+    /// assert!(patched.is_subset_of(&safe));
+    /// assert!(!safe.is_subset_of(&patched));
+    /// ```
+    pub fn is_subset_of(&self, other: &VersionRange) -> bool {
+        self.intersect(&other.complement()).is_empty()
+    }
+
+    /// Create a range from the given intervals, which may be empty, unsorted or overlapping.
+    fn from_intervals(mut intervals: Vec<(Bound<VersionBuf>, Bound<VersionBuf>)>) -> Self {
+        intervals.retain(|(lower, upper)| !is_empty(lower, upper));
+        intervals.sort_by(|a, b| cmp_lower(&a.0, &b.0));
+
+        // Merge overlapping and adjacent intervals
+        let mut merged: Vec<(Bound<VersionBuf>, Bound<VersionBuf>)> = Vec::new();
+        for (lower, upper) in intervals {
+            match merged.last_mut() {
+                Some(last) if reaches(&last.1, &lower) => {
+                    if cmp_upper(&upper, &last.1) == Ordering::Greater {
+                        last.1 = upper;
+                    }
+                }
+                _ => merged.push((lower, upper)),
+            }
+        }
+
+        VersionRange { intervals: merged }
+    }
+}
+
+/// Compare two lower bounds, an unbounded lower bound is the lowest.
+fn cmp_lower(a: &Bound<VersionBuf>, b: &Bound<VersionBuf>) -> Ordering {
+    match (a, b) {
+        (Bound::Unbounded, Bound::Unbounded) => Ordering::Equal,
+        (Bound::Unbounded, _) => Ordering::Less,
+        (_, Bound::Unbounded) => Ordering::Greater,
+        (Bound::Included(a), Bound::Excluded(b)) => a.cmp(b).then(Ordering::Less),
+        (Bound::Excluded(a), Bound::Included(b)) => a.cmp(b).then(Ordering::Greater),
+        (Bound::Included(a), Bound::Included(b)) | (Bound::Excluded(a), Bound::Excluded(b)) => {
+            a.cmp(b)
+        }
+    }
+}
+
+/// Compare two upper bounds, an unbounded upper bound is the highest.
+fn cmp_upper(a: &Bound<VersionBuf>, b: &Bound<VersionBuf>) -> Ordering {
+    match (a, b) {
+        (Bound::Unbounded, Bound::Unbounded) => Ordering::Equal,
+        (Bound::Unbounded, _) => Ordering::Greater,
+        (_, Bound::Unbounded) => Ordering::Less,
+        (Bound::Included(a), Bound::Excluded(b)) => a.cmp(b).then(Ordering::Greater),
+        (Bound::Excluded(a), Bound::Included(b)) => a.cmp(b).then(Ordering::Less),
+        (Bound::Included(a), Bound::Included(b)) | (Bound::Excluded(a), Bound::Excluded(b)) => {
+            a.cmp(b)
+        }
+    }
+}
+
+/// Check whether the interval between the given bounds contains no versions.
+fn is_empty(lower: &Bound<VersionBuf>, upper: &Bound<VersionBuf>) -> bool {
+    match (lower, upper) {
+        (Bound::Included(a), Bound::Included(b)) => a > b,
+        (Bound::Included(a), Bound::Excluded(b))
+        | (Bound::Excluded(a), Bound::Included(b))
+        | (Bound::Excluded(a), Bound::Excluded(b)) => a >= b,
+        _ => false,
+    }
+}
+
+/// Check whether an interval ending at `upper` overlaps or touches an interval starting at `lower`.
+fn reaches(upper: &Bound<VersionBuf>, lower: &Bound<VersionBuf>) -> bool {
+    match (upper, lower) {
+        (Bound::Excluded(a), Bound::Excluded(b)) => b < a,
+        (Bound::Included(a), Bound::Included(b))
+        | (Bound::Included(a), Bound::Excluded(b))
+        | (Bound::Excluded(a), Bound::Included(b)) => b <= a,
+        _ => true,
+    }
+}
+
+#[cfg_attr(tarpaulin, skip)]
+#[cfg(test)]
+mod tests {
+    use std::convert::TryFrom;
+    use std::ops::Bound;
+
+    use crate::{Cmp, Version, VersionBuf};
+
+    use super::VersionRange;
+
+    /// Create a range from a requirement.
+    fn range(req: &str) -> VersionRange {
+        VersionRange::from_req(&req.parse().unwrap())
+    }
+
+    /// Create a version for testing.
+    fn version(version: &str) -> VersionBuf {
+        VersionBuf::try_from(version).unwrap()
+    }
+
+    #[test]
+    fn new() {
+        assert!(!VersionRange::new(Bound::Unbounded, Bound::Unbounded).is_empty());
+        assert!(VersionRange::new(
+            Bound::Included(version("2.0")),
+            Bound::Included(version("1.0"))
+        )
+        .is_empty());
+        assert!(VersionRange::new(
+            Bound::Included(version("1.0")),
+            Bound::Excluded(version("1.0.0"))
+        )
+        .is_empty());
+        assert!(!VersionRange::new(
+            Bound::Included(version("1.0")),
+            Bound::Included(version("1.0.0"))
+        )
+        .is_empty());
+        assert!(VersionRange::new(
+            Bound::Excluded(version("1.0")),
+            Bound::Excluded(version("1.0"))
+        )
+        .is_empty());
+    }
+
+    #[test]
+    fn from_cmp() {
+        let v = version("1.2");
+        let tests = [
+            (Cmp::Eq, ["1.2.0"].as_ref(), ["1.1", "1.3"].as_ref()),
+            (Cmp::Ne, ["1.1", "1.3"].as_ref(), ["1.2"].as_ref()),
+            (Cmp::Lt, ["1.1", "0"].as_ref(), ["1.2", "1.3"].as_ref()),
+            (Cmp::Le, ["1.1", "1.2"].as_ref(), ["1.3"].as_ref()),
+            (Cmp::Ge, ["1.2", "1.3"].as_ref(), ["1.1"].as_ref()),
+            (Cmp::Gt, ["1.3"].as_ref(), ["1.2", "1.1"].as_ref()),
+        ];
+        for (operator, inside, outside) in &tests {
+            let range = VersionRange::from_cmp(*operator, v.clone());
+            for inside in *inside {
+                assert!(range.contains(&Version::from(inside).unwrap()));
+            }
+            for outside in *outside {
+                assert!(!range.contains(&Version::from(outside).unwrap()));
+            }
+        }
+    }
+
+    #[test]
+    fn from_req() {
+        assert_eq!(range(""), VersionRange::full());
+        assert!(range(">= 2, < 1").is_empty());
+        assert!(range("== 1, == 2").is_empty());
+        assert_eq!(range(">= 1, <= 1"), range("== 1.0"));
+    }
+
+    #[test]
+    fn union() {
+        // Overlapping, adjacent and disjoint intervals
+        assert_eq!(range(">=1, <3").union(&range(">=2, <4")), range(">=1, <4"));
+        assert_eq!(range(">=1, <2").union(&range(">=2, <3")), range(">=1, <3"));
+        assert_eq!(range(">=1, <=2").union(&range(">2, <3")), range(">=1, <3"));
+        assert_eq!(range(">=1, <2").union(&range(">2")).intervals().len(), 2);
+        assert_eq!(range("<2").union(&range(">=1")), VersionRange::full());
+        assert_eq!(range("<2").union(&VersionRange::empty()), range("<2"));
+
+        // Union of multiple intervals must stay sorted
+        let union = range(">=5, <6")
+            .union(&range(">=1, <2"))
+            .union(&range(">=3, <4"));
+        let lowers: Vec<&Bound<VersionBuf>> = union.intervals().iter().map(|i| &i.0).collect();
+        assert_eq!(
+            lowers,
+            [
+                &Bound::Included(version("1")),
+                &Bound::Included(version("3")),
+                &Bound::Included(version("5")),
+            ]
+        );
+    }
+
+    #[test]
+    fn intersect() {
+        assert_eq!(range(">=1, <3").intersect(&range(">=2")), range(">=2, <3"));
+        assert!(range("<2").intersect(&range(">=2")).is_empty());
+        assert_eq!(range("<=2").intersect(&range(">=2")), range("==2"));
+
+        let a = range("<1").union(&range(">=2, <4"));
+        let b = range(">=0.5, <3").union(&range(">=3.5"));
+        assert_eq!(
+            a.intersect(&b),
+            range(">=0.5, <1")
+                .union(&range(">=2, <3"))
+                .union(&range(">=3.5, <4"))
+        );
+    }
+
+    #[test]
+    fn complement() {
+        assert_eq!(VersionRange::full().complement(), VersionRange::empty());
+        assert_eq!(VersionRange::empty().complement(), VersionRange::full());
+        assert_eq!(range("<1").complement(), range(">=1"));
+        assert_eq!(range("==1").complement(), range("!=1"));
+        assert_eq!(
+            range(">1, <=2").complement(),
+            range("<=1").union(&range(">2"))
+        );
+
+        // The complement of the complement must be the same
+        let a = range("<1").union(&range(">=2, <4")).union(&range(">5"));
+        assert_eq!(a.complement().complement(), a);
+        assert!(a.intersect(&a.complement()).is_empty());
+        assert_eq!(a.union(&a.complement()), VersionRange::full());
+    }
+
+    #[test]
+    fn is_subset_of() {
+        let vulnerable = range(">=1.0, <1.4.2").union(&range(">=2.0, <2.1.1"));
+        let unpatched = range("<1.4.2").union(&range(">=2.0, <2.1.1"));
+        assert!(vulnerable.is_subset_of(&unpatched));
+        assert!(!unpatched.is_subset_of(&vulnerable));
+        assert!(VersionRange::empty().is_subset_of(&vulnerable));
+        assert!(vulnerable.is_subset_of(&VersionRange::full()));
+        assert!(vulnerable.is_subset_of(&vulnerable));
+    }
+
+    #[test]
+    fn contains() {
+        let range = range(">=1.0, <1.4.2").union(&range(">2.0, <=2.1.1"));
+        for inside in &["1", "1.4.1", "1.4.2-rc1", "2.0.1", "2.1.1"] {
+            assert!(
+                range.contains(&Version::from(inside).unwrap()),
+                "{}",
+                inside
+            );
+        }
+        for outside in &["0.9", "1.4.2", "2.0", "2.1.2", "3"] {
+            assert!(
+                !range.contains(&Version::from(outside).unwrap()),
+                "{}",
+                outside
+            );
+        }
+    }
+}
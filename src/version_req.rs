@@ -0,0 +1,330 @@
+//! Version requirement module, which provides the `VersionReq` struct.
+//!
+//! A version requirement is a list of clauses, each being a comparison operator and a version,
+//! such as `>= 1.2, < 2.0`. A version matches the requirement if it matches all clauses.
+
+use std::convert::TryFrom;
+use std::fmt;
+use std::str::FromStr;
+
+use crate::version::compare_version;
+use crate::{Cmp, Error, PartBuf, Version, VersionBuf};
+
+/// Version requirement, a list of comparison operator and version clauses.
+///
+/// Clauses are separated by commas or whitespace. Each clause has a comparison operator sign as
+/// supported by `Cmp::from_sign`, optionally followed by whitespace, and a version. A clause
+/// without operator requires the version to be equal.
+///
+/// # Examples
+///
+/// ```
+/// use version_compare::{Version, VersionReq};
+///
+/// let req = VersionReq::parse(">= 1.2, < 2.0").unwrap();
+///
+/// assert!(req.matches(&Version::from("1.5.3").unwrap()));
+/// assert!(!req.matches(&Version::from("2.0").unwrap()));
+/// assert_eq!(req.to_string(), ">=1.2, <2.0");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct VersionReq {
+    clauses: Vec<(Cmp, VersionBuf)>,
+}
+
+impl VersionReq {
+    /// Parse a version requirement.
+    ///
+    /// An empty requirement has no clauses, and matches any version.
+    ///
+    /// An error is returned for malformed clauses, such as an unknown operator sign, an operator
+    /// without version, an invalid version, an operator sign inside a version such as in
+    /// `>=1.2<2.0`, or an empty clause between two commas.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use version_compare::{Error, VersionReq};
+    ///
+    /// assert!(VersionReq::parse(">=1.2 <2.0 !=1.5").is_ok());
+    /// assert_eq!(
+    ///     VersionReq::parse(">= 1.2, =>").unwrap_err(),
+    ///     Error::UnknownOperatorSign { at: 8 },
+    /// );
+    /// assert_eq!(
+    ///     VersionReq::parse(">= 1.2, <").unwrap_err(),
+    ///     Error::MissingVersion { at: 9 },
+    /// );
+    /// ```
+    pub fn parse(req: &str) -> Result<Self, Error> {
+        let mut clauses = Vec::new();
+
+        // An operator waiting for its version, and the offset of the last unused comma
+        let mut pending: Option<Cmp> = None;
+        let mut comma: Option<usize> = None;
+
+        for (at, token) in tokenize(req) {
+            if token == "," {
+                if pending.is_some() {
+                    return Err(Error::MissingVersion { at });
+                }
+                if clauses.is_empty() || comma.is_some() {
+                    return Err(Error::EmptyClause { at });
+                }
+                comma = Some(at);
+                continue;
+            }
+
+            // Split the operator sign from the version
+            let sign_len = token.find(|c| !is_sign_char(c)).unwrap_or(token.len());
+            let (sign, version) = token.split_at(sign_len);
+            let operator = match (sign.is_empty(), pending.take()) {
+                (true, Some(operator)) => operator,
+                (true, None) => Cmp::Eq,
+                (false, None) => Cmp::from_sign(sign).map_err(|err| err.offset(at))?,
+                (false, Some(_)) => return Err(Error::MissingVersion { at }),
+            };
+
+            if version.is_empty() {
+                pending = Some(operator);
+                continue;
+            }
+            if let Some(i) = version.find(is_sign_char) {
+                return Err(Error::UnexpectedChar {
+                    at: at + sign_len + i,
+                });
+            }
+
+            let version = VersionBuf::try_from(version).map_err(|err| err.offset(at + sign_len))?;
+            clauses.push((operator, version));
+            comma = None;
+        }
+
+        if pending.is_some() {
+            return Err(Error::MissingVersion { at: req.len() });
+        }
+        if let Some(at) = comma {
+            return Err(Error::EmptyClause { at });
+        }
+
+        Ok(VersionReq { clauses })
+    }
+
+    /// Get the clauses of this requirement, each being a comparison operator and version.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use version_compare::{Cmp, VersionReq};
+    ///
+    /// let req = VersionReq::parse(">=1.2, <2").unwrap();
+    ///
+    /// assert_eq!(req.clauses()[0].0, Cmp::Ge);
+    /// assert_eq!(req.clauses()[1].1.as_str(), "2");
+    /// ```
+    pub fn clauses(&self) -> &[(Cmp, VersionBuf)] {
+        &self.clauses
+    }
+
+    /// Create a requirement from the given clauses.
+    pub(crate) fn from_clauses(clauses: Vec<(Cmp, VersionBuf)>) -> Self {
+        VersionReq { clauses }
+    }
+
+    /// Check whether the given `version` matches all clauses of this requirement.
+    ///
+    /// The version is compared using its own manifest, if it has any.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use version_compare::{Version, VersionReq};
+    ///
+    /// let req = VersionReq::parse(">1.0 !=1.3").unwrap();
+    ///
+    /// assert!(req.matches(&Version::from("1.2").unwrap()));
+    /// assert!(!req.matches(&Version::from("1.3.0").unwrap()));
+    /// assert!(!req.matches(&Version::from("1").unwrap()));
+    /// ```
+    pub fn matches(&self, version: &Version) -> bool {
+        self.clauses.iter().all(|(operator, other)| {
+            let cmp = compare_version(
+                (version.as_str(), version.parts().iter().copied()),
+                (other.as_str(), other.parts().iter().map(PartBuf::as_part)),
+                version.manifest(),
+            );
+            match cmp {
+                Cmp::Eq => matches!(operator, Cmp::Eq | Cmp::Le | Cmp::Ge),
+                Cmp::Lt => matches!(operator, Cmp::Ne | Cmp::Lt | Cmp::Le),
+                Cmp::Gt => matches!(operator, Cmp::Ne | Cmp::Gt | Cmp::Ge),
+                _ => unreachable!(),
+            }
+        })
+    }
+}
+
+impl FromStr for VersionReq {
+    type Err = Error;
+
+    fn from_str(req: &str) -> Result<Self, Self::Err> {
+        VersionReq::parse(req)
+    }
+}
+
+/// Format the requirement in a normalized form, that parses into an equal requirement.
+impl fmt::Display for VersionReq {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (i, (operator, version)) in self.clauses.iter().enumerate() {
+            if i > 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{}{}", operator.sign(), version)?;
+        }
+        Ok(())
+    }
+}
+
+/// Check whether the given character is part of a comparison operator sign.
+fn is_sign_char(c: char) -> bool {
+    matches!(c, '<' | '>' | '=' | '!')
+}
+
+/// Split a requirement into tokens along with their byte offset.
+///
+/// Whitespace separates tokens, and each comma is a token by itself.
+fn tokenize(req: &str) -> Vec<(usize, &str)> {
+    let mut tokens = Vec::new();
+    let mut start = None;
+    for (i, c) in req.char_indices() {
+        if c == ',' || c.is_whitespace() {
+            if let Some(start) = start.take() {
+                tokens.push((start, &req[start..i]));
+            }
+            if c == ',' {
+                tokens.push((i, ","));
+            }
+        } else if start.is_none() {
+            start = Some(i);
+        }
+    }
+    if let Some(start) = start {
+        tokens.push((start, &req[start..]));
+    }
+    tokens
+}
+
+#[cfg_attr(tarpaulin, skip)]
+#[cfg(test)]
+mod tests {
+    use crate::test::MANIFEST_GNU;
+    use crate::{Cmp, Error, Version};
+
+    use super::VersionReq;
+
+    #[test]
+    fn parse() {
+        let req = VersionReq::parse(">= 1.2, < 2.0").unwrap();
+        assert_eq!(req.clauses().len(), 2);
+        assert_eq!(req.clauses()[0].0, Cmp::Ge);
+        assert_eq!(req.clauses()[0].1.as_str(), "1.2");
+        assert_eq!(req.clauses()[1].0, Cmp::Lt);
+        assert_eq!(req.clauses()[1].1.as_str(), "2.0");
+
+        // Separators and spacing must not matter
+        for other in &[
+            ">=1.2 <2.0",
+            ">=1.2,<2.0",
+            "  >=  1.2 ,\t< 2.0  ",
+            ">= 1.2 < 2.0",
+        ] {
+            assert_eq!(VersionReq::parse(other).unwrap(), req, "Parsing {}", other);
+        }
+
+        // All signs must be supported, a bare version must be equal
+        let req = VersionReq::parse("==1 =2 !=3 !4 <>5 <6 <=7 >=8 >9 10").unwrap();
+        let operators: Vec<Cmp> = req.clauses().iter().map(|c| c.0).collect();
+        assert_eq!(
+            operators,
+            [
+                Cmp::Eq,
+                Cmp::Eq,
+                Cmp::Ne,
+                Cmp::Ne,
+                Cmp::Ne,
+                Cmp::Lt,
+                Cmp::Le,
+                Cmp::Ge,
+                Cmp::Gt,
+                Cmp::Eq,
+            ],
+        );
+
+        // An empty requirement has no clauses
+        assert!(VersionReq::parse("").unwrap().clauses().is_empty());
+        assert!(VersionReq::parse("  ").unwrap().clauses().is_empty());
+    }
+
+    #[test]
+    fn parse_error() {
+        let errors = [
+            ("=> 1.2", Error::UnknownOperatorSign { at: 0 }),
+            (">= 1.2, >>2", Error::UnknownOperatorSign { at: 8 }),
+            (">=", Error::MissingVersion { at: 2 }),
+            (">= , 1.2", Error::MissingVersion { at: 3 }),
+            (">= <1.2", Error::MissingVersion { at: 3 }),
+            (">= 1.2,, <2", Error::EmptyClause { at: 7 }),
+            (", 1.2", Error::EmptyClause { at: 0 }),
+            (">= 1.2,", Error::EmptyClause { at: 6 }),
+            (">= 1.2, < dev", Error::NoNumericPart { at: 10 }),
+            ("<dev", Error::NoNumericPart { at: 1 }),
+            (">=1.2<2.0", Error::UnexpectedChar { at: 5 }),
+            (">= 1.2, <2.0=", Error::UnexpectedChar { at: 12 }),
+        ];
+        for (req, err) in &errors {
+            assert_eq!(VersionReq::parse(req).as_ref(), Err(err), "Parsing {}", req);
+        }
+    }
+
+    #[test]
+    fn matches() {
+        let req = VersionReq::parse(">= 1.2, < 2.0").unwrap();
+        for version in &["1.2", "1.2.0", "1.5.3", "1.99", "1.2.1-dev"] {
+            assert!(req.matches(&Version::from(version).unwrap()), "{}", version);
+        }
+        for version in &["1.1", "1.2-dev", "2", "2.0.0", "3"] {
+            assert!(
+                !req.matches(&Version::from(version).unwrap()),
+                "{}",
+                version
+            );
+        }
+
+        // An empty requirement matches anything
+        assert!(VersionReq::default().matches(&Version::from("1").unwrap()));
+
+        // The manifest of the version must be used
+        let manifest = MANIFEST_GNU.unwrap();
+        let req = VersionReq::parse("< 1.2").unwrap();
+        assert!(!req.matches(&Version::from("1.02").unwrap()));
+        assert!(req.matches(&Version::from_manifest("1.02", &manifest).unwrap()));
+    }
+
+    #[test]
+    fn display() {
+        let reqs = [
+            (">= 1.2, < 2.0", ">=1.2, <2.0"),
+            ("1.2 !3", "==1.2, !=3"),
+            ("<>1.2.3-rc.1", "!=1.2.3-rc.1"),
+            ("", ""),
+        ];
+        for (req, display) in &reqs {
+            let parsed = VersionReq::parse(req).unwrap();
+            assert_eq!(parsed.to_string(), *display);
+
+            // Displayed requirements must round trip
+            let reparsed: VersionReq = parsed.to_string().parse().unwrap();
+            assert_eq!(reparsed, parsed);
+            assert_eq!(reparsed.to_string(), *display);
+        }
+    }
+}